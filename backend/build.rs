@@ -0,0 +1,4 @@
+// Recompila cuando cambian las migraciones embebidas con sqlx::migrate!
+fn main() {
+    println!("cargo:rerun-if-changed=migrations");
+}
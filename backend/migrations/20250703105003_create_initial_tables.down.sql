@@ -0,0 +1,2 @@
+DROP TABLE platform_settings;
+DROP TABLE pricing_rules;
@@ -0,0 +1,2 @@
+DROP TABLE hotels;
+DROP TYPE hotel_status;
@@ -0,0 +1,15 @@
+-- Regresa role y status a los tipos enum originales
+CREATE TYPE user_role AS ENUM ('admin', 'hotel_owner', 'customer');
+CREATE TYPE hotel_status AS ENUM ('pending', 'approved', 'rejected');
+
+ALTER TABLE users DROP CONSTRAINT users_role_check;
+ALTER TABLE hotels DROP CONSTRAINT hotels_status_check;
+
+ALTER TABLE users ALTER COLUMN role DROP DEFAULT;
+ALTER TABLE hotels ALTER COLUMN status DROP DEFAULT;
+
+ALTER TABLE users ALTER COLUMN role TYPE user_role USING role::user_role;
+ALTER TABLE hotels ALTER COLUMN status TYPE hotel_status USING status::hotel_status;
+
+ALTER TABLE users ALTER COLUMN role SET DEFAULT 'customer';
+ALTER TABLE hotels ALTER COLUMN status SET DEFAULT 'pending';
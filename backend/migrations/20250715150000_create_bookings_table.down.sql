@@ -0,0 +1,3 @@
+DROP TABLE bookings;
+DROP FUNCTION set_booking_reference();
+DROP FUNCTION generate_booking_reference();
@@ -0,0 +1,8 @@
+DROP TABLE business_images;
+DROP TABLE businesses;
+
+-- Los dueños de negocio vuelven a ser clientes antes de restaurar la restricción
+UPDATE users SET role = 'customer' WHERE role = 'business_owner';
+ALTER TABLE users DROP CONSTRAINT users_role_check;
+ALTER TABLE users ADD CONSTRAINT users_role_check
+    CHECK (role IN ('admin', 'hotel_owner', 'customer'));
@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_businesses_status_type;
+DROP FUNCTION business_is_open(JSONB, TIMESTAMP);
@@ -0,0 +1,35 @@
+-- Directorio público de negocios (restaurantes, tours, etc.)
+
+-- Indica si un negocio está abierto en un momento dado según su JSON de horarios.
+-- Formato esperado: {"monday": {"open": "09:00", "close": "22:00", "closed": false}, ...}
+CREATE OR REPLACE FUNCTION business_is_open(hours JSONB, at_time TIMESTAMP)
+RETURNS BOOLEAN AS $$
+DECLARE
+    day_hours JSONB;
+    open_time TIME;
+    close_time TIME;
+    time_of_day TIME := at_time::time;
+BEGIN
+    day_hours := hours -> trim(lower(to_char(at_time, 'day')));
+
+    IF day_hours IS NULL OR COALESCE((day_hours ->> 'closed')::boolean, false) THEN
+        RETURN false;
+    END IF;
+
+    open_time := NULLIF(day_hours ->> 'open', '')::time;
+    close_time := NULLIF(day_hours ->> 'close', '')::time;
+
+    IF open_time IS NULL OR close_time IS NULL THEN
+        RETURN false;
+    END IF;
+
+    IF close_time > open_time THEN
+        RETURN time_of_day >= open_time AND time_of_day < close_time;
+    END IF;
+
+    -- Horario que cruza la medianoche (p. ej. 18:00 - 02:00)
+    RETURN time_of_day >= open_time OR time_of_day < close_time;
+END;
+$$ LANGUAGE plpgsql STABLE;
+
+CREATE INDEX IF NOT EXISTS idx_businesses_status_type ON businesses(status, business_type);
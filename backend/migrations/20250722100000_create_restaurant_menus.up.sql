@@ -0,0 +1,41 @@
+-- Menús de restaurantes: secciones y platillos
+
+CREATE TABLE menu_sections (
+    id SERIAL PRIMARY KEY,
+    business_id INTEGER NOT NULL REFERENCES businesses(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    description TEXT,
+    display_order INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE menu_items (
+    id SERIAL PRIMARY KEY,
+    section_id INTEGER NOT NULL REFERENCES menu_sections(id) ON DELETE CASCADE,
+    business_id INTEGER NOT NULL REFERENCES businesses(id) ON DELETE CASCADE,
+    name VARCHAR(150) NOT NULL,
+    description TEXT,
+    price DECIMAL(10,2) NOT NULL CHECK (price >= 0),
+    dietary_tags TEXT[] NOT NULL DEFAULT '{}',
+    allergens TEXT[] NOT NULL DEFAULT '{}',
+    -- Ventana de disponibilidad diaria (p. ej. desayunos de 07:00 a 12:00)
+    available_from TIME,
+    available_until TIME,
+    image_url TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Fecha (hora local) en la que el platillo se marcó como agotado
+    sold_out_on DATE,
+    display_order INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+
+    CONSTRAINT menu_items_window_check CHECK (
+        (available_from IS NULL AND available_until IS NULL) OR
+        (available_from IS NOT NULL AND available_until IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_menu_sections_business_id ON menu_sections(business_id);
+CREATE INDEX idx_menu_items_business_id ON menu_items(business_id);
+CREATE INDEX idx_menu_items_section_id ON menu_items(section_id);
@@ -0,0 +1,6 @@
+DROP TABLE order_items;
+DROP TABLE orders;
+DROP TABLE cart_items;
+DROP TABLE product_images;
+DROP TABLE product_variants;
+DROP TABLE products;
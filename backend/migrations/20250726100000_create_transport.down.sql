@@ -0,0 +1,5 @@
+DROP FUNCTION transport_route_fare(DECIMAL, DECIMAL, INTEGER);
+DROP TABLE transport_bookings;
+DROP TABLE transport_trips;
+DROP TABLE transport_routes;
+DROP TABLE transport_vehicles;
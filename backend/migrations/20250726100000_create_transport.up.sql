@@ -0,0 +1,94 @@
+-- Transporte y traslados: vehículos, rutas, salidas y reservas de asientos
+
+CREATE TABLE transport_vehicles (
+    id SERIAL PRIMARY KEY,
+    business_id INTEGER NOT NULL REFERENCES businesses(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    vehicle_type VARCHAR(50) NOT NULL,
+    plate VARCHAR(20),
+    seat_capacity INTEGER NOT NULL CHECK (seat_capacity > 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE TABLE transport_routes (
+    id SERIAL PRIMARY KEY,
+    business_id INTEGER NOT NULL REFERENCES businesses(id) ON DELETE CASCADE,
+    origin VARCHAR(255) NOT NULL,
+    destination VARCHAR(255) NOT NULL,
+    distance_km DECIMAL(8,2) NOT NULL CHECK (distance_km > 0),
+    duration_minutes INTEGER NOT NULL CHECK (duration_minutes > 0),
+    -- Si el dueño no fija un precio se calcula con las reglas de pricing_rules
+    fixed_price DECIMAL(10,2) CHECK (fixed_price > 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- Salidas: programadas (compartidas, se venden por asiento) o a demanda (privadas)
+CREATE TABLE transport_trips (
+    id SERIAL PRIMARY KEY,
+    route_id INTEGER NOT NULL REFERENCES transport_routes(id) ON DELETE CASCADE,
+    vehicle_id INTEGER NOT NULL REFERENCES transport_vehicles(id) ON DELETE CASCADE,
+    trip_type VARCHAR(20) NOT NULL,
+    departure_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    arrival_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    price_per_seat DECIMAL(10,2) NOT NULL CHECK (price_per_seat >= 0),
+    status VARCHAR(20) NOT NULL DEFAULT 'scheduled',
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+
+    CONSTRAINT transport_trips_type_check CHECK (trip_type IN ('scheduled', 'on_demand')),
+    CONSTRAINT transport_trips_status_check CHECK (status IN ('scheduled', 'cancelled', 'completed')),
+    CONSTRAINT transport_trips_times_check CHECK (arrival_at > departure_at)
+);
+
+CREATE TABLE transport_bookings (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    trip_id INTEGER NOT NULL REFERENCES transport_trips(id) ON DELETE CASCADE,
+    seats INTEGER NOT NULL CHECK (seats > 0),
+    total_price DECIMAL(10,2) NOT NULL CHECK (total_price >= 0),
+    passenger_name VARCHAR(200),
+    flight_number VARCHAR(20),
+    special_requests TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    payment_status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    booking_reference VARCHAR(20) UNIQUE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
+    cancelled_at TIMESTAMP WITH TIME ZONE,
+    cancellation_reason TEXT,
+
+    CONSTRAINT transport_bookings_status_check
+        CHECK (status IN ('pending', 'confirmed', 'cancelled', 'completed')),
+    CONSTRAINT transport_bookings_payment_status_check
+        CHECK (payment_status IN ('pending', 'paid', 'refunded', 'failed'))
+);
+
+-- Tarifa de una ruta: precio fijo del dueño o tarifa base + km + minutos
+CREATE OR REPLACE FUNCTION transport_route_fare(
+    fixed_price DECIMAL,
+    distance_km DECIMAL,
+    duration_minutes INTEGER
+)
+RETURNS DECIMAL AS $$
+    SELECT COALESCE(
+        fixed_price,
+        ROUND(
+            COALESCE((SELECT value FROM pricing_rules WHERE rule_name = 'BASE_FARE_MXN'), 0)
+            + distance_km * COALESCE((SELECT value FROM pricing_rules WHERE rule_name = 'PER_KM_RATE_MXN'), 0)
+            + duration_minutes * COALESCE((SELECT value FROM pricing_rules WHERE rule_name = 'PER_MINUTE_RATE_MXN'), 0),
+            2
+        )
+    );
+$$ LANGUAGE sql STABLE;
+
+CREATE INDEX idx_transport_vehicles_business_id ON transport_vehicles(business_id);
+CREATE INDEX idx_transport_routes_business_id ON transport_routes(business_id);
+CREATE INDEX idx_transport_routes_origin_destination ON transport_routes(origin, destination);
+CREATE INDEX idx_transport_trips_route_departure ON transport_trips(route_id, departure_at);
+CREATE INDEX idx_transport_trips_vehicle_departure ON transport_trips(vehicle_id, departure_at);
+CREATE INDEX idx_transport_bookings_user_id ON transport_bookings(user_id);
+CREATE INDEX idx_transport_bookings_trip_id ON transport_bookings(trip_id);
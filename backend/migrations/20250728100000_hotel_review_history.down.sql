@@ -0,0 +1 @@
+DROP TABLE hotel_review_history;
@@ -0,0 +1,25 @@
+-- Historial de moderación de hoteles: envíos, reenvíos y decisiones del administrador
+
+CREATE TABLE hotel_review_history (
+    id SERIAL PRIMARY KEY,
+    hotel_id INTEGER NOT NULL REFERENCES hotels(id) ON DELETE CASCADE,
+    action VARCHAR(20) NOT NULL,
+    actor_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    notes TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT hotel_review_history_action_check
+        CHECK (action IN ('submitted', 'resubmitted', 'approved', 'rejected'))
+);
+
+CREATE INDEX idx_hotel_review_history_hotel_id ON hotel_review_history(hotel_id, created_at DESC);
+
+-- Los hoteles existentes arrancan con su envío original y, si ya se decidieron, con la decisión
+INSERT INTO hotel_review_history (hotel_id, action, actor_id, created_at)
+SELECT id, 'submitted', owner_id, COALESCE(created_at, NOW())
+FROM hotels;
+
+INSERT INTO hotel_review_history (hotel_id, action, actor_id, notes, created_at)
+SELECT id, status, approved_by, admin_notes, COALESCE(approved_at, updated_at, NOW())
+FROM hotels
+WHERE status IN ('approved', 'rejected');
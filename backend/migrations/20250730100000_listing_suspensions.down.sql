@@ -0,0 +1,8 @@
+DROP TABLE notifications;
+DROP TABLE listing_suspensions;
+
+-- Los hoteles suspendidos vuelven a quedar aprobados
+UPDATE hotels SET status = 'approved' WHERE status = 'suspended';
+ALTER TABLE hotels DROP CONSTRAINT hotels_status_check;
+ALTER TABLE hotels ADD CONSTRAINT hotels_status_check
+    CHECK (status IN ('pending', 'approved', 'rejected'));
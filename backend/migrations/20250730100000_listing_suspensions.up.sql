@@ -0,0 +1,44 @@
+-- Suspensiones de hoteles y negocios, y notificaciones para los propietarios
+
+ALTER TABLE hotels DROP CONSTRAINT hotels_status_check;
+ALTER TABLE hotels ADD CONSTRAINT hotels_status_check
+    CHECK (status IN ('pending', 'approved', 'rejected', 'suspended'));
+
+CREATE TABLE listing_suspensions (
+    id SERIAL PRIMARY KEY,
+    listing_type VARCHAR(20) NOT NULL,
+    listing_id INTEGER NOT NULL,
+    reason TEXT NOT NULL,
+    suspended_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    suspended_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    -- Sin fecha de fin la suspensión dura hasta que un administrador la levante
+    ends_at TIMESTAMP WITH TIME ZONE,
+    lifted_at TIMESTAMP WITH TIME ZONE,
+    lifted_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    lift_reason TEXT,
+
+    CONSTRAINT listing_suspensions_type_check CHECK (listing_type IN ('hotel', 'business')),
+    CONSTRAINT listing_suspensions_dates_check CHECK (ends_at IS NULL OR ends_at > suspended_at)
+);
+
+-- Solo puede haber una suspensión activa por publicación
+CREATE UNIQUE INDEX idx_listing_suspensions_active
+    ON listing_suspensions(listing_type, listing_id)
+    WHERE lifted_at IS NULL;
+
+CREATE INDEX idx_listing_suspensions_ends_at
+    ON listing_suspensions(ends_at)
+    WHERE lifted_at IS NULL AND ends_at IS NOT NULL;
+
+CREATE TABLE notifications (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind VARCHAR(50) NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    message TEXT NOT NULL,
+    data JSONB NOT NULL DEFAULT '{}',
+    read_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_notifications_user_id ON notifications(user_id, created_at DESC);
@@ -0,0 +1,5 @@
+DROP INDEX idx_hotel_review_history_submissions;
+DROP INDEX idx_businesses_status_created_at;
+DROP INDEX idx_hotels_status_created_at;
+
+ALTER TABLE businesses DROP COLUMN admin_notes;
@@ -0,0 +1,9 @@
+-- Cola de moderación: notas del revisor en negocios e índices para ordenar por envío
+
+ALTER TABLE businesses ADD COLUMN admin_notes TEXT;
+
+CREATE INDEX idx_hotels_status_created_at ON hotels(status, created_at);
+CREATE INDEX idx_businesses_status_created_at ON businesses(status, created_at);
+CREATE INDEX idx_hotel_review_history_submissions
+    ON hotel_review_history(hotel_id, created_at DESC)
+    WHERE action IN ('submitted', 'resubmitted');
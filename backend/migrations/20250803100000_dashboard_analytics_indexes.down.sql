@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_businesses_created_at;
+DROP INDEX IF EXISTS idx_hotels_created_at;
+DROP INDEX IF EXISTS idx_users_created_at;
+DROP INDEX IF EXISTS idx_bookings_created_at;
@@ -0,0 +1,5 @@
+-- Índices para las series de tiempo del panel de administración
+CREATE INDEX IF NOT EXISTS idx_bookings_created_at ON bookings(created_at);
+CREATE INDEX IF NOT EXISTS idx_users_created_at ON users(created_at);
+CREATE INDEX IF NOT EXISTS idx_hotels_created_at ON hotels(created_at);
+CREATE INDEX IF NOT EXISTS idx_businesses_created_at ON businesses(created_at);
@@ -0,0 +1,8 @@
+ALTER TABLE businesses DROP CONSTRAINT businesses_rating_check;
+ALTER TABLE businesses DROP COLUMN review_count;
+ALTER TABLE businesses DROP COLUMN rating;
+
+ALTER TABLE hotels DROP CONSTRAINT hotels_rating_check;
+ALTER TABLE hotels DROP COLUMN review_count;
+
+DROP TABLE reviews;
@@ -0,0 +1,48 @@
+-- Reseñas de huéspedes y clientes. Cada reseña proviene de una estancia o compra completada
+-- y solo cuenta para la calificación pública una vez aprobada por un administrador.
+
+CREATE TABLE reviews (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    hotel_id INTEGER REFERENCES hotels(id) ON DELETE CASCADE,
+    business_id INTEGER REFERENCES businesses(id) ON DELETE CASCADE,
+    booking_id INTEGER REFERENCES bookings(id) ON DELETE SET NULL,
+    order_id INTEGER REFERENCES orders(id) ON DELETE SET NULL,
+    transport_booking_id INTEGER REFERENCES transport_bookings(id) ON DELETE SET NULL,
+    overall_rating SMALLINT NOT NULL CHECK (overall_rating BETWEEN 1 AND 5),
+    cleanliness_rating SMALLINT CHECK (cleanliness_rating BETWEEN 1 AND 5),
+    service_rating SMALLINT CHECK (service_rating BETWEEN 1 AND 5),
+    location_rating SMALLINT CHECK (location_rating BETWEEN 1 AND 5),
+    value_rating SMALLINT CHECK (value_rating BETWEEN 1 AND 5),
+    title VARCHAR(200),
+    comment TEXT NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    moderated_by INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    moderated_at TIMESTAMP WITH TIME ZONE,
+    moderation_notes TEXT,
+    owner_reply TEXT,
+    owner_replied_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT reviews_status_check CHECK (status IN ('pending', 'approved', 'rejected')),
+    CONSTRAINT reviews_target_check CHECK ((hotel_id IS NULL) <> (business_id IS NULL)),
+    CONSTRAINT reviews_source_check CHECK (num_nonnulls(booking_id, order_id, transport_booking_id) <= 1)
+);
+
+-- Una reseña por reserva (y por negocio dentro de un pedido con varios artesanos)
+CREATE UNIQUE INDEX idx_reviews_booking_id ON reviews(booking_id) WHERE booking_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_reviews_order_business ON reviews(order_id, business_id) WHERE order_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_reviews_transport_booking_id ON reviews(transport_booking_id) WHERE transport_booking_id IS NOT NULL;
+
+CREATE INDEX idx_reviews_hotel_id ON reviews(hotel_id, status, created_at DESC);
+CREATE INDEX idx_reviews_business_id ON reviews(business_id, status, created_at DESC);
+CREATE INDEX idx_reviews_status_created_at ON reviews(status, created_at);
+CREATE INDEX idx_reviews_user_id ON reviews(user_id);
+
+ALTER TABLE hotels ADD COLUMN review_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE hotels ADD CONSTRAINT hotels_rating_check CHECK (rating >= 0 AND rating <= 5);
+
+ALTER TABLE businesses ADD COLUMN rating DECIMAL(2,1) NOT NULL DEFAULT 0.0;
+ALTER TABLE businesses ADD COLUMN review_count INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE businesses ADD CONSTRAINT businesses_rating_check CHECK (rating >= 0 AND rating <= 5);
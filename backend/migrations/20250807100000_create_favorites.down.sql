@@ -0,0 +1,2 @@
+DROP TABLE favorite_items;
+DROP TABLE favorite_lists;
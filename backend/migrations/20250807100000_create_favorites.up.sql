@@ -0,0 +1,33 @@
+-- Listas de favoritos de los clientes: hoteles, negocios y productos artesanales.
+-- Una lista con share_token puede consultarse sin iniciar sesión.
+
+CREATE TABLE favorite_lists (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    share_token VARCHAR(64) UNIQUE,
+    shared_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    UNIQUE (user_id, name)
+);
+
+CREATE TABLE favorite_items (
+    id SERIAL PRIMARY KEY,
+    list_id INTEGER NOT NULL REFERENCES favorite_lists(id) ON DELETE CASCADE,
+    hotel_id INTEGER REFERENCES hotels(id) ON DELETE CASCADE,
+    business_id INTEGER REFERENCES businesses(id) ON DELETE CASCADE,
+    product_id INTEGER REFERENCES products(id) ON DELETE CASCADE,
+    notes TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT favorite_items_target_check CHECK (num_nonnulls(hotel_id, business_id, product_id) = 1)
+);
+
+-- Cada elemento aparece una sola vez por lista
+CREATE UNIQUE INDEX idx_favorite_items_hotel ON favorite_items(list_id, hotel_id) WHERE hotel_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_favorite_items_business ON favorite_items(list_id, business_id) WHERE business_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_favorite_items_product ON favorite_items(list_id, product_id) WHERE product_id IS NOT NULL;
+
+CREATE INDEX idx_favorite_lists_user_id ON favorite_lists(user_id);
@@ -0,0 +1,5 @@
+DROP FUNCTION hotel_rooms_free(INTEGER, DATE, DATE);
+DROP TABLE hotel_calendar_blocks;
+DROP TABLE hotel_calendar_feeds;
+
+ALTER TABLE hotels DROP COLUMN ical_token;
@@ -0,0 +1,65 @@
+-- Sincronización de calendarios con otras plataformas (OTAs).
+-- Cada hotel expone un feed ICS secreto con sus noches reservadas e importa
+-- calendarios externos cuyos eventos bloquean noches en nuestra disponibilidad.
+
+ALTER TABLE hotels ADD COLUMN ical_token VARCHAR(64) UNIQUE;
+
+CREATE TABLE hotel_calendar_feeds (
+    id SERIAL PRIMARY KEY,
+    hotel_id INTEGER NOT NULL REFERENCES hotels(id) ON DELETE CASCADE,
+    name VARCHAR(100) NOT NULL,
+    -- NULL para calendarios subidos como archivo
+    source_url TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    last_synced_at TIMESTAMP WITH TIME ZONE,
+    last_status VARCHAR(20),
+    last_error TEXT,
+    last_conflict_nights INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    UNIQUE (hotel_id, name),
+    CONSTRAINT hotel_calendar_feeds_status_check CHECK (last_status IN ('ok', 'error'))
+);
+
+-- Cada evento importado ocupa una habitación en las noches [start_date, end_date)
+CREATE TABLE hotel_calendar_blocks (
+    id SERIAL PRIMARY KEY,
+    feed_id INTEGER NOT NULL REFERENCES hotel_calendar_feeds(id) ON DELETE CASCADE,
+    hotel_id INTEGER NOT NULL REFERENCES hotels(id) ON DELETE CASCADE,
+    external_uid TEXT NOT NULL,
+    summary TEXT,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    UNIQUE (feed_id, external_uid, start_date),
+    CONSTRAINT hotel_calendar_blocks_dates_check CHECK (end_date > start_date)
+);
+
+CREATE INDEX idx_hotel_calendar_feeds_sync ON hotel_calendar_feeds(is_active, last_synced_at)
+    WHERE source_url IS NOT NULL;
+CREATE INDEX idx_hotel_calendar_blocks_hotel_dates ON hotel_calendar_blocks(hotel_id, start_date, end_date);
+
+-- Habitaciones libres en la noche más ocupada de [p_check_in, p_check_out),
+-- contando reservas activas y bloqueos importados de calendarios activos
+CREATE OR REPLACE FUNCTION hotel_rooms_free(p_hotel_id INTEGER, p_check_in DATE, p_check_out DATE)
+RETURNS INTEGER AS $$
+    SELECT (SELECT COALESCE(rooms_available, 1) FROM hotels WHERE id = p_hotel_id)
+         - COALESCE(MAX(
+               (SELECT COALESCE(SUM(b.rooms), 0) FROM bookings b
+                WHERE b.hotel_id = p_hotel_id
+                  AND b.status != 'cancelled'
+                  AND b.check_in <= n.night
+                  AND b.check_out > n.night)
+             + (SELECT COUNT(*) FROM hotel_calendar_blocks cb
+                JOIN hotel_calendar_feeds f ON cb.feed_id = f.id
+                WHERE cb.hotel_id = p_hotel_id
+                  AND f.is_active
+                  AND cb.start_date <= n.night
+                  AND cb.end_date > n.night)
+           ), 0)::int
+    FROM (
+        SELECT generate_series(p_check_in, p_check_out - 1, INTERVAL '1 day')::date AS night
+    ) n;
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,3 @@
+DROP TABLE booking_invoices;
+
+DELETE FROM platform_settings WHERE setting_name IN ('IVA_RATE', 'LODGING_TAX_RATE');
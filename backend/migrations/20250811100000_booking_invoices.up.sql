@@ -0,0 +1,20 @@
+-- Facturas de reservas completadas y pagadas. Los importes e impuestos se fijan al emitirla;
+-- los precios de las reservas ya incluyen impuestos.
+
+INSERT INTO platform_settings (setting_name, setting_value) VALUES
+    ('IVA_RATE', '0.16'),
+    ('LODGING_TAX_RATE', '0.05')
+ON CONFLICT (setting_name) DO NOTHING;
+
+CREATE TABLE booking_invoices (
+    id SERIAL PRIMARY KEY,
+    booking_id INTEGER NOT NULL UNIQUE REFERENCES bookings(id) ON DELETE CASCADE,
+    invoice_number VARCHAR(30) NOT NULL UNIQUE,
+    subtotal DECIMAL(10,2) NOT NULL,
+    iva_rate DECIMAL(5,4) NOT NULL,
+    iva_amount DECIMAL(10,2) NOT NULL,
+    lodging_tax_rate DECIMAL(5,4) NOT NULL,
+    lodging_tax_amount DECIMAL(10,2) NOT NULL,
+    total DECIMAL(10,2) NOT NULL,
+    issued_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
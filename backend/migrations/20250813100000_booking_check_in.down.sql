@@ -0,0 +1,12 @@
+-- Las reservas ya registradas vuelven a quedar confirmadas
+UPDATE transport_bookings SET status = 'confirmed' WHERE status = 'checked_in';
+ALTER TABLE transport_bookings DROP COLUMN checked_in_by, DROP COLUMN checked_in_at;
+ALTER TABLE transport_bookings DROP CONSTRAINT transport_bookings_status_check;
+ALTER TABLE transport_bookings ADD CONSTRAINT transport_bookings_status_check
+    CHECK (status IN ('pending', 'confirmed', 'cancelled', 'completed'));
+
+UPDATE bookings SET status = 'confirmed' WHERE status = 'checked_in';
+ALTER TABLE bookings DROP COLUMN checked_in_by, DROP COLUMN checked_in_at;
+ALTER TABLE bookings DROP CONSTRAINT bookings_status_check;
+ALTER TABLE bookings ADD CONSTRAINT bookings_status_check
+    CHECK (status IN ('pending', 'confirmed', 'cancelled', 'completed'));
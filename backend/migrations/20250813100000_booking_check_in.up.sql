@@ -0,0 +1,17 @@
+-- Registro de llegada con el código QR del comprobante. checked_in_at impide usar el código dos veces.
+
+ALTER TABLE bookings DROP CONSTRAINT bookings_status_check;
+ALTER TABLE bookings ADD CONSTRAINT bookings_status_check
+    CHECK (status IN ('pending', 'confirmed', 'checked_in', 'cancelled', 'completed'));
+
+ALTER TABLE bookings
+    ADD COLUMN checked_in_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN checked_in_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE transport_bookings DROP CONSTRAINT transport_bookings_status_check;
+ALTER TABLE transport_bookings ADD CONSTRAINT transport_bookings_status_check
+    CHECK (status IN ('pending', 'confirmed', 'checked_in', 'cancelled', 'completed'));
+
+ALTER TABLE transport_bookings
+    ADD COLUMN checked_in_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN checked_in_by INTEGER REFERENCES users(id) ON DELETE SET NULL;
@@ -0,0 +1,3 @@
+ALTER TABLE users
+    DROP COLUMN reset_password_expires_at,
+    DROP COLUMN verification_token_expires_at;
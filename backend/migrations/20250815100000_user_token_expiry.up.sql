@@ -0,0 +1,6 @@
+-- Vigencia de los tokens de verificación y de restablecimiento de contraseña.
+-- maya-admin purge-tokens elimina los vencidos; los tokens sin fecha se consideran vencidos.
+
+ALTER TABLE users
+    ADD COLUMN verification_token_expires_at TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN reset_password_expires_at TIMESTAMP WITH TIME ZONE;
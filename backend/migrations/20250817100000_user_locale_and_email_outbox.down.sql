@@ -0,0 +1,4 @@
+DROP TABLE email_outbox;
+
+ALTER TABLE users DROP CONSTRAINT users_preferred_locale_check;
+ALTER TABLE users DROP COLUMN preferred_locale;
@@ -0,0 +1,20 @@
+-- Idioma preferido de cada usuario y bandeja de salida de correos ya traducidos.
+-- El envío lo hace un proceso aparte: sent_at queda en NULL hasta entonces.
+
+ALTER TABLE users ADD COLUMN preferred_locale VARCHAR(10);
+ALTER TABLE users ADD CONSTRAINT users_preferred_locale_check
+    CHECK (preferred_locale IN ('es', 'en'));
+
+CREATE TABLE email_outbox (
+    id SERIAL PRIMARY KEY,
+    user_id INTEGER REFERENCES users(id) ON DELETE SET NULL,
+    to_email VARCHAR(255) NOT NULL,
+    locale VARCHAR(10) NOT NULL,
+    kind VARCHAR(50) NOT NULL,
+    subject TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX idx_email_outbox_pending ON email_outbox(created_at) WHERE sent_at IS NULL;
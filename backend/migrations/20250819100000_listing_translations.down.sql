@@ -0,0 +1,8 @@
+DROP TABLE listing_translations;
+
+ALTER TABLE businesses DROP CONSTRAINT businesses_content_locale_check;
+ALTER TABLE businesses DROP COLUMN content_locale;
+
+ALTER TABLE hotels DROP CONSTRAINT hotels_content_locale_check;
+ALTER TABLE hotels DROP COLUMN content_locale;
+ALTER TABLE hotels DROP COLUMN amenities;
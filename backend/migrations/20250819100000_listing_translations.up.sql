@@ -0,0 +1,36 @@
+-- Contenido traducido de hoteles, negocios y platillos del menú.
+-- Las columnas propias de cada publicación están en su content_locale; listing_translations guarda
+-- el mismo contenido en otros idiomas. Un campo NULL en la traducción se toma de la cadena de respaldo.
+
+ALTER TABLE hotels ADD COLUMN amenities TEXT[] NOT NULL DEFAULT '{}';
+ALTER TABLE hotels ADD COLUMN content_locale VARCHAR(10) NOT NULL DEFAULT 'es';
+ALTER TABLE hotels ADD CONSTRAINT hotels_content_locale_check
+    CHECK (content_locale IN ('es', 'en'));
+
+ALTER TABLE businesses ADD COLUMN content_locale VARCHAR(10) NOT NULL DEFAULT 'es';
+ALTER TABLE businesses ADD CONSTRAINT businesses_content_locale_check
+    CHECK (content_locale IN ('es', 'en'));
+
+CREATE TABLE listing_translations (
+    id SERIAL PRIMARY KEY,
+    hotel_id INTEGER REFERENCES hotels(id) ON DELETE CASCADE,
+    business_id INTEGER REFERENCES businesses(id) ON DELETE CASCADE,
+    menu_item_id INTEGER REFERENCES menu_items(id) ON DELETE CASCADE,
+    locale VARCHAR(10) NOT NULL,
+    name VARCHAR(255),
+    description TEXT,
+    amenities TEXT[],
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT listing_translations_target_check CHECK (num_nonnulls(hotel_id, business_id, menu_item_id) = 1),
+    CONSTRAINT listing_translations_locale_check CHECK (locale IN ('es', 'en'))
+);
+
+-- Una traducción por publicación e idioma
+CREATE UNIQUE INDEX idx_listing_translations_hotel
+    ON listing_translations(hotel_id, locale) WHERE hotel_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_listing_translations_business
+    ON listing_translations(business_id, locale) WHERE business_id IS NOT NULL;
+CREATE UNIQUE INDEX idx_listing_translations_menu_item
+    ON listing_translations(menu_item_id, locale) WHERE menu_item_id IS NOT NULL;
@@ -1,72 +0,0 @@
--- migrations/20250715150000_create_bookings_table.sql
-
-CREATE TABLE bookings (
-    id SERIAL PRIMARY KEY,
-    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    hotel_id INTEGER NOT NULL REFERENCES hotels(id) ON DELETE CASCADE,
-    check_in DATE NOT NULL,
-    check_out DATE NOT NULL,
-    guests INTEGER NOT NULL DEFAULT 1,
-    rooms INTEGER NOT NULL DEFAULT 1,
-    total_price DECIMAL(10,2) NOT NULL,
-    status VARCHAR(20) NOT NULL DEFAULT 'pending',
-    payment_status VARCHAR(20) NOT NULL DEFAULT 'pending',
-    special_requests TEXT,
-    addon_services JSONB, -- Para almacenar extras como spa, tours, etc.
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
-    updated_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP,
-    cancelled_at TIMESTAMP WITH TIME ZONE,
-    cancellation_reason TEXT,
-    booking_reference VARCHAR(20) UNIQUE NOT NULL
-);
-
--- Índices para performance
-CREATE INDEX idx_bookings_user ON bookings(user_id);
-CREATE INDEX idx_bookings_hotel ON bookings(hotel_id);
-CREATE INDEX idx_bookings_dates ON bookings(check_in, check_out);
-CREATE INDEX idx_bookings_status ON bookings(status);
-CREATE INDEX idx_bookings_reference ON bookings(booking_reference);
-
--- Restricciones
-ALTER TABLE bookings ADD CONSTRAINT bookings_status_check 
-    CHECK (status IN ('pending', 'confirmed', 'cancelled', 'completed'));
-
-ALTER TABLE bookings ADD CONSTRAINT bookings_payment_status_check 
-    CHECK (payment_status IN ('pending', 'paid', 'refunded', 'failed'));
-
-ALTER TABLE bookings ADD CONSTRAINT bookings_dates_check 
-    CHECK (check_out > check_in);
-
-ALTER TABLE bookings ADD CONSTRAINT bookings_guests_check 
-    CHECK (guests >= 1 AND guests <= 10);
-
-ALTER TABLE bookings ADD CONSTRAINT bookings_rooms_check 
-    CHECK (rooms >= 1 AND rooms <= 5);
-
--- Función para generar referencia única
-CREATE OR REPLACE FUNCTION generate_booking_reference()
-RETURNS TEXT AS $$
-BEGIN
-    RETURN 'MY' || LPAD(FLOOR(RANDOM() * 1000000)::TEXT, 6, '0');
-END;
-$$ LANGUAGE plpgsql;
-
--- Trigger para generar referencia automáticamente
-CREATE OR REPLACE FUNCTION set_booking_reference()
-RETURNS TRIGGER AS $$
-BEGIN
-    IF NEW.booking_reference IS NULL OR NEW.booking_reference = '' THEN
-        NEW.booking_reference := generate_booking_reference();
-        -- Verificar que no exista ya
-        WHILE EXISTS (SELECT 1 FROM bookings WHERE booking_reference = NEW.booking_reference) LOOP
-            NEW.booking_reference := generate_booking_reference();
-        END LOOP;
-    END IF;
-    RETURN NEW;
-END;
-$$ LANGUAGE plpgsql;
-
-CREATE TRIGGER trigger_set_booking_reference
-    BEFORE INSERT ON bookings
-    FOR EACH ROW
-    EXECUTE FUNCTION set_booking_reference();
\ No newline at end of file
@@ -0,0 +1,90 @@
+// maya-admin export-bookings: reservas de hotel en CSV
+
+use std::{fs::File, io::{self, BufWriter, Write}, path::PathBuf};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Row};
+
+use maya_digital_backend::utils::errors::AppError;
+
+const HEADER: &str = "id,booking_reference,hotel_id,hotel_name,customer_email,check_in,check_out,guests,rooms,total_price,status,payment_status,created_at";
+
+// Comillas solo cuando el valor lo requiere (RFC 4180)
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Filtra por fecha de llegada (inclusiva) y estado; sin --output escribe en la salida estándar
+pub async fn export_bookings(
+    pool: &PgPool,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    status: Option<String>,
+    output: Option<PathBuf>,
+) -> Result<(), AppError> {
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(AppError::BadRequest("La fecha inicial no puede ser posterior a la final".to_string()));
+        }
+    }
+
+    let rows = sqlx::query(
+        r#"
+        SELECT b.id, b.booking_reference, b.hotel_id, h.name AS hotel_name, u.email AS customer_email,
+               b.check_in, b.check_out, b.guests, b.rooms, b.total_price::float8 AS total_price,
+               b.status, b.payment_status, b.created_at
+        FROM bookings b
+        JOIN hotels h ON h.id = b.hotel_id
+        JOIN users u ON u.id = b.user_id
+        WHERE ($1::date IS NULL OR b.check_in >= $1)
+          AND ($2::date IS NULL OR b.check_in <= $2)
+          AND ($3::text IS NULL OR b.status = $3)
+        ORDER BY b.check_in, b.id
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(status)
+    .fetch_all(pool)
+    .await?;
+
+    let write_error = |e: io::Error| AppError::InternalServerError(format!("No se pudo escribir el CSV: {}", e));
+    let mut out: Box<dyn Write> = match &output {
+        Some(path) => Box::new(BufWriter::new(File::create(path).map_err(write_error)?)),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+
+    writeln!(out, "{}", HEADER).map_err(write_error)?;
+    for r in &rows {
+        writeln!(
+            out,
+            "{},{},{},{},{},{},{},{},{},{:.2},{},{},{}",
+            r.get::<i32, _>("id"),
+            csv_field(&r.get::<String, _>("booking_reference")),
+            r.get::<i32, _>("hotel_id"),
+            csv_field(&r.get::<String, _>("hotel_name")),
+            csv_field(&r.get::<String, _>("customer_email")),
+            r.get::<NaiveDate, _>("check_in"),
+            r.get::<NaiveDate, _>("check_out"),
+            r.get::<i32, _>("guests"),
+            r.get::<i32, _>("rooms"),
+            r.get::<f64, _>("total_price"),
+            r.get::<String, _>("status"),
+            r.get::<String, _>("payment_status"),
+            r.try_get::<Option<DateTime<Utc>>, _>("created_at").unwrap_or(None).map(|d| d.to_rfc3339()).unwrap_or_default(),
+        )
+        .map_err(write_error)?;
+    }
+    out.flush().map_err(write_error)?;
+
+    // El resumen va a stderr para no mezclarse con el CSV
+    if let Some(path) = output {
+        eprintln!("✅ {} reservas exportadas a {}", rows.len(), path.display());
+    } else {
+        eprintln!("✅ {} reservas exportadas", rows.len());
+    }
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// maya-admin approve/reject: misma moderación que el panel, firmada por un administrador existente
+
+use clap::ValueEnum;
+use sqlx::PgPool;
+
+use maya_digital_backend::{
+    handlers::admin::{decide_business, decide_hotel},
+    utils::errors::AppError,
+};
+
+use crate::users::admin_id;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ListingKind {
+    Hotel,
+    Business,
+}
+
+impl ListingKind {
+    fn label(self) -> &'static str {
+        match self {
+            ListingKind::Hotel => "Hotel",
+            ListingKind::Business => "Negocio",
+        }
+    }
+}
+
+pub async fn decide(
+    pool: &PgPool,
+    kind: ListingKind,
+    id: i32,
+    admin_email: &str,
+    status: &str,
+    notes: Option<String>,
+) -> Result<(), AppError> {
+    let admin_id = admin_id(pool, admin_email).await?;
+    let notes = notes.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+
+    if status == "rejected" && notes.is_none() {
+        return Err(AppError::BadRequest("Indica el motivo del rechazo".to_string()));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let decided = match kind {
+        ListingKind::Hotel => decide_hotel(&mut tx, id, status, admin_id, notes.as_deref()).await?,
+        ListingKind::Business => decide_business(&mut tx, id, status, admin_id, notes.as_deref()).await?,
+    };
+    if !decided {
+        return Err(AppError::NotFound(format!(
+            "{} {} no encontrado o no está pendiente de revisión",
+            kind.label(),
+            id
+        )));
+    }
+
+    tx.commit().await?;
+
+    println!("✅ {} {} marcado como {}", kind.label(), id, status);
+    Ok(())
+}
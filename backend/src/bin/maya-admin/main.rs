@@ -0,0 +1,152 @@
+// maya-admin: herramienta de operación que comparte la configuración, los modelos y el código
+// de base de datos de la API. `maya-admin --help` lista los subcomandos.
+
+mod export;
+mod listings;
+mod maintenance;
+mod migrate;
+mod users;
+
+use std::path::PathBuf;
+use chrono::NaiveDate;
+use clap::{Parser, Subcommand};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+
+use maya_digital_backend::config::Settings;
+
+#[derive(Parser)]
+#[command(name = "maya-admin", about = "Tareas administrativas de Maya Digital")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Consulta, aplica o revierte las migraciones de la base de datos
+    Migrate {
+        #[command(subcommand)]
+        action: migrate::MigrateAction,
+    },
+    /// Crea una cuenta de administrador (p. ej. el primero de una instalación nueva)
+    CreateAdmin {
+        #[arg(long)]
+        email: String,
+        #[arg(long)]
+        first_name: String,
+        #[arg(long)]
+        last_name: String,
+        #[arg(long)]
+        phone: Option<String>,
+        /// Lee la contraseña de la entrada estándar en lugar de pedirla
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// Convierte a un usuario existente en administrador
+    Promote {
+        #[arg(long)]
+        email: String,
+    },
+    /// Cambia la contraseña de un usuario
+    ResetPassword {
+        #[arg(long)]
+        email: String,
+        /// Lee la contraseña de la entrada estándar en lugar de pedirla
+        #[arg(long)]
+        password_stdin: bool,
+    },
+    /// Aprueba un hotel o negocio pendiente
+    Approve {
+        kind: listings::ListingKind,
+        id: i32,
+        /// Email del administrador que firma la decisión
+        #[arg(long = "as")]
+        admin: String,
+        #[arg(long)]
+        notes: Option<String>,
+    },
+    /// Rechaza un hotel o negocio pendiente
+    Reject {
+        kind: listings::ListingKind,
+        id: i32,
+        /// Email del administrador que firma la decisión
+        #[arg(long = "as")]
+        admin: String,
+        #[arg(long)]
+        reason: String,
+    },
+    /// Recalcula la calificación de todos los hoteles y negocios
+    RecomputeRatings,
+    /// Elimina los tokens de verificación y de restablecimiento vencidos
+    PurgeTokens,
+    /// Exporta las reservas de hotel en CSV
+    ExportBookings {
+        /// Primera fecha de llegada (AAAA-MM-DD)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+        /// Última fecha de llegada (AAAA-MM-DD)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        #[arg(long)]
+        status: Option<String>,
+        /// Archivo de salida; por defecto la salida estándar
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    dotenvy::dotenv().ok();
+    let cli = Cli::parse();
+
+    let settings = match Settings::load() {
+        Ok(settings) => settings,
+        Err(e) => fail(e),
+    };
+    let pool = match connect(&settings).await {
+        Ok(pool) => pool,
+        Err(e) => fail(format!("Error al conectar con la base de datos: {}", e)),
+    };
+
+    let result = match cli.command {
+        Command::Migrate { action } => migrate::run(&pool, action).await,
+        Command::CreateAdmin { email, first_name, last_name, phone, password_stdin } => {
+            users::create_admin(&pool, email, first_name, last_name, phone, password_stdin)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        Command::Promote { email } => users::promote(&pool, email).await.map_err(|e| e.to_string()),
+        Command::ResetPassword { email, password_stdin } => {
+            users::reset_password(&pool, email, password_stdin).await.map_err(|e| e.to_string())
+        }
+        Command::Approve { kind, id, admin, notes } => {
+            listings::decide(&pool, kind, id, &admin, "approved", notes).await.map_err(|e| e.to_string())
+        }
+        Command::Reject { kind, id, admin, reason } => {
+            listings::decide(&pool, kind, id, &admin, "rejected", Some(reason)).await.map_err(|e| e.to_string())
+        }
+        Command::RecomputeRatings => maintenance::recompute_ratings(&pool).await.map_err(|e| e.to_string()),
+        Command::PurgeTokens => maintenance::purge_tokens(&pool).await.map_err(|e| e.to_string()),
+        Command::ExportBookings { from, to, status, output } => {
+            export::export_bookings(&pool, from, to, status, output).await.map_err(|e| e.to_string())
+        }
+    };
+
+    if let Err(e) = result {
+        fail(e);
+    }
+}
+
+async fn connect(settings: &Settings) -> Result<PgPool, sqlx::Error> {
+    PgPoolOptions::new()
+        .max_connections(2)
+        .acquire_timeout(settings.acquire_timeout())
+        .connect(&settings.database.url)
+        .await
+}
+
+fn fail(message: impl std::fmt::Display) -> ! {
+    eprintln!("❌ {}", message);
+    std::process::exit(1);
+}
@@ -0,0 +1,48 @@
+// maya-admin: tareas de mantenimiento de datos
+
+use sqlx::PgPool;
+
+use maya_digital_backend::{handlers::recompute_all_ratings, utils::errors::AppError};
+
+pub async fn recompute_ratings(pool: &PgPool) -> Result<(), AppError> {
+    let updated = recompute_all_ratings(pool).await?;
+
+    println!("✅ Calificaciones recalculadas ({} hoteles y negocios)", updated);
+    Ok(())
+}
+
+// Borra los tokens de verificación y de restablecimiento vencidos o sin fecha de vencimiento
+pub async fn purge_tokens(pool: &PgPool) -> Result<(), AppError> {
+    let mut tx = pool.begin().await?;
+
+    let reset = sqlx::query(
+        r#"
+        UPDATE users
+        SET reset_password_token = NULL, reset_password_expires_at = NULL
+        WHERE reset_password_token IS NOT NULL
+          AND (reset_password_expires_at IS NULL OR reset_password_expires_at < NOW())
+        "#
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    let verification = sqlx::query(
+        r#"
+        UPDATE users
+        SET verification_token = NULL, verification_token_expires_at = NULL
+        WHERE verification_token IS NOT NULL
+          AND (verification_token_expires_at IS NULL OR verification_token_expires_at < NOW())
+        "#
+    )
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    println!(
+        "✅ Tokens eliminados: {} de restablecimiento, {} de verificación",
+        reset.rows_affected(),
+        verification.rows_affected()
+    );
+    Ok(())
+}
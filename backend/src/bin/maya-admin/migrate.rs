@@ -0,0 +1,124 @@
+// maya-admin migrate: estado, aplicación y reversión del esquema embebido (MIGRATOR)
+
+use std::collections::HashMap;
+use clap::Subcommand;
+use sqlx::{migrate::Migrate, PgPool};
+
+use maya_digital_backend::MIGRATOR;
+
+#[derive(Subcommand)]
+pub enum MigrateAction {
+    /// Lista las migraciones y si están aplicadas, pendientes o modificadas
+    Status,
+    /// Aplica todas las migraciones pendientes
+    Up,
+    /// Revierte la última migración aplicada, o todas las posteriores a --target
+    Down {
+        /// Versión que debe quedar como la última aplicada (0 revierte todo)
+        #[arg(long)]
+        target: Option<i64>,
+    },
+}
+
+pub async fn run(pool: &PgPool, action: MigrateAction) -> Result<(), String> {
+    match action {
+        MigrateAction::Status => status(pool).await,
+        MigrateAction::Up => up(pool).await,
+        MigrateAction::Down { target } => down(pool, target).await,
+    }
+}
+
+// Versiones aplicadas con su checksum, en orden ascendente
+async fn applied_versions(pool: &PgPool) -> Result<Vec<(i64, Vec<u8>)>, String> {
+    let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+    conn.ensure_migrations_table().await.map_err(|e| e.to_string())?;
+    let mut applied: Vec<(i64, Vec<u8>)> = conn
+        .list_applied_migrations()
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|m| (m.version, m.checksum.into_owned()))
+        .collect();
+    applied.sort_by_key(|(version, _)| *version);
+    Ok(applied)
+}
+
+async fn status(pool: &PgPool) -> Result<(), String> {
+    let applied = applied_versions(pool).await?;
+    let checksums: HashMap<i64, &Vec<u8>> = applied.iter().map(|(v, c)| (*v, c)).collect();
+
+    let dirty = {
+        let mut conn = pool.acquire().await.map_err(|e| e.to_string())?;
+        conn.dirty_version().await.map_err(|e| e.to_string())?
+    };
+
+    let mut pending = 0;
+    for migration in MIGRATOR.iter().filter(|m| !m.migration_type.is_down_migration()) {
+        let state = match checksums.get(&migration.version) {
+            _ if dirty == Some(migration.version) => "incompleta",
+            Some(checksum) if checksum.as_slice() != migration.checksum.as_ref() => "modificada",
+            Some(_) => "aplicada",
+            None => {
+                pending += 1;
+                "pendiente"
+            }
+        };
+        println!("{:<14} {:<11} {}", migration.version, state, migration.description);
+    }
+
+    // Versiones registradas en la base que ya no existen en el binario
+    for (version, _) in &applied {
+        if !MIGRATOR.iter().any(|m| m.version == *version) {
+            println!("{:<14} {:<11} (no existe en esta versión)", version, "desconocida");
+        }
+    }
+
+    println!("\n{} aplicadas, {} pendientes", applied.len(), pending);
+    Ok(())
+}
+
+async fn up(pool: &PgPool) -> Result<(), String> {
+    let before = applied_versions(pool).await?.len();
+    MIGRATOR.run(pool).await.map_err(|e| format!("Error al aplicar las migraciones: {}", e))?;
+    let after = applied_versions(pool).await?.len();
+
+    println!("✅ {} migraciones aplicadas ({} en total)", after - before, after);
+    Ok(())
+}
+
+async fn down(pool: &PgPool, target: Option<i64>) -> Result<(), String> {
+    let applied = applied_versions(pool).await?;
+    let Some((latest, _)) = applied.last() else {
+        println!("No hay migraciones aplicadas");
+        return Ok(());
+    };
+
+    // Sin --target se revierte solo la última
+    let target = match target {
+        Some(target) => target,
+        None => applied.iter().rev().nth(1).map(|(v, _)| *v).unwrap_or(0),
+    };
+    if target >= *latest {
+        println!("Nada que revertir: la última migración aplicada es {}", latest);
+        return Ok(());
+    }
+
+    // undo solo revierte migraciones con archivo .down.sql; las demás se informan antes de tocar nada
+    for (version, _) in applied.iter().filter(|(v, _)| *v > target) {
+        let reversible = MIGRATOR
+            .iter()
+            .any(|m| m.version == *version && m.migration_type.is_down_migration());
+        if !reversible {
+            return Err(format!("La migración {} no tiene archivo .down.sql y no se puede revertir", version));
+        }
+    }
+
+    let count = applied.iter().filter(|(v, _)| *v > target).count();
+    MIGRATOR
+        .undo(pool, target)
+        .await
+        .map_err(|e| format!("Error al revertir las migraciones: {}", e))?;
+
+    println!("✅ {} migraciones revertidas; versión actual: {}", count, target);
+    Ok(())
+}
@@ -0,0 +1,159 @@
+// maya-admin: alta y mantenimiento de cuentas de administrador
+
+use std::io::BufRead;
+use bcrypt::{hash, DEFAULT_COST};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+
+use maya_digital_backend::{models::RegisterRequest, utils::errors::AppError};
+
+// Misma regla que el registro público
+const MIN_PASSWORD_LEN: usize = 6;
+
+// Con --password-stdin la contraseña se lee de la primera línea de la entrada (útil en scripts);
+// si no, se pide dos veces sin mostrarla
+fn read_password(from_stdin: bool) -> Result<String, AppError> {
+    let password = if from_stdin {
+        let mut line = String::new();
+        std::io::stdin()
+            .lock()
+            .read_line(&mut line)
+            .map_err(|e| AppError::BadRequest(format!("No se pudo leer la contraseña: {}", e)))?;
+        line.trim_end_matches(['\r', '\n']).to_string()
+    } else {
+        let read = |prompt: &str| {
+            rpassword::prompt_password(prompt)
+                .map_err(|e| AppError::BadRequest(format!("No se pudo leer la contraseña: {}", e)))
+        };
+        let password = read("Contraseña: ")?;
+        if read("Repite la contraseña: ")? != password {
+            return Err(AppError::BadRequest("Las contraseñas no coinciden".to_string()));
+        }
+        password
+    };
+
+    if password.chars().count() < MIN_PASSWORD_LEN {
+        return Err(AppError::BadRequest(format!(
+            "La contraseña debe tener al menos {} caracteres",
+            MIN_PASSWORD_LEN
+        )));
+    }
+    Ok(password)
+}
+
+pub async fn create_admin(
+    pool: &PgPool,
+    email: String,
+    first_name: String,
+    last_name: String,
+    phone: Option<String>,
+    password_stdin: bool,
+) -> Result<(), AppError> {
+    let email = email.trim().to_string();
+    let password = read_password(password_stdin)?;
+
+    // Se validan los mismos campos que en el registro público
+    let req = RegisterRequest { first_name, last_name, email, password, phone };
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let existing = sqlx::query("SELECT role FROM users WHERE email = $1")
+        .bind(&req.email)
+        .fetch_optional(pool)
+        .await?;
+    if let Some(row) = existing {
+        return Err(AppError::Conflict(format!(
+            "{} ya está registrado como {}; usa maya-admin promote",
+            req.email,
+            row.get::<String, _>("role")
+        )));
+    }
+
+    let password_hash = hash(&req.password, DEFAULT_COST)?;
+    let id: i32 = sqlx::query(
+        r#"
+        INSERT INTO users (first_name, last_name, email, password_hash, phone, role, verified)
+        VALUES ($1, $2, $3, $4, $5, 'admin', TRUE)
+        RETURNING id
+        "#
+    )
+    .bind(&req.first_name)
+    .bind(&req.last_name)
+    .bind(&req.email)
+    .bind(&password_hash)
+    .bind(&req.phone)
+    .fetch_one(pool)
+    .await?
+    .get("id");
+
+    println!("✅ Administrador {} creado con ID {}", req.email, id);
+    Ok(())
+}
+
+pub async fn promote(pool: &PgPool, email: String) -> Result<(), AppError> {
+    let email = email.trim().to_string();
+
+    let user = sqlx::query("SELECT id, role FROM users WHERE email = $1")
+        .bind(&email)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No existe un usuario con el email {}", email)))?;
+
+    let previous: String = user.get("role");
+    if previous == "admin" {
+        println!("{} ya es administrador", email);
+        return Ok(());
+    }
+
+    sqlx::query("UPDATE users SET role = 'admin', updated_at = NOW() WHERE id = $1")
+        .bind(user.get::<i32, _>("id"))
+        .execute(pool)
+        .await?;
+
+    // Los tokens emitidos conservan el rol anterior hasta que el usuario vuelva a iniciar sesión
+    println!("✅ {} promovido de {} a admin", email, previous);
+    Ok(())
+}
+
+pub async fn reset_password(pool: &PgPool, email: String, password_stdin: bool) -> Result<(), AppError> {
+    let email = email.trim().to_string();
+    let password = read_password(password_stdin)?;
+    let password_hash = hash(&password, DEFAULT_COST)?;
+
+    // Cualquier enlace de restablecimiento pendiente deja de valer
+    let result = sqlx::query(
+        r#"
+        UPDATE users
+        SET password_hash = $2, reset_password_token = NULL, reset_password_expires_at = NULL,
+            updated_at = NOW()
+        WHERE email = $1
+        "#
+    )
+    .bind(&email)
+    .bind(&password_hash)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::NotFound(format!("No existe un usuario con el email {}", email)));
+    }
+
+    println!("✅ Contraseña de {} actualizada", email);
+    Ok(())
+}
+
+// Usuario administrador que firma las decisiones tomadas desde la línea de comandos
+pub async fn admin_id(pool: &PgPool, email: &str) -> Result<i32, AppError> {
+    let email = email.trim().to_string();
+    let row = sqlx::query("SELECT id, role FROM users WHERE email = $1")
+        .bind(&email)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("No existe un usuario con el email {}", email)))?;
+
+    if row.get::<String, _>("role") != "admin" {
+        return Err(AppError::Forbidden(format!("{} no es administrador", email)));
+    }
+    Ok(row.get("id"))
+}
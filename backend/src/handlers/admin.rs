@@ -1,127 +1,368 @@
-use actix_web::{web, HttpResponse};
-use sqlx::{PgPool, Row};
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgConnection, PgPool, Row};
 use crate::{
-    models::UserInfo,
-    utils::errors::AppError,
+    models::{
+        MessageResponse, UserInfo, UserRole,
+        business::PendingBusiness,
+        hotel::{AdminHotel, ApproveNotes, HotelReviewEntry, HotelReviewHistory, PendingHotel, RejectReason},
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
 };
 
-pub async fn get_pending_hotels(pool: web::Data<PgPool>, _user: UserInfo) -> Result<HttpResponse, AppError> {
-    let hotels = sqlx::query("SELECT * FROM hotels WHERE status = $1")
-        .bind("pending")
-        .fetch_all(pool.get_ref())
-        .await?;
+// Todos los hoteles para el panel de administración (GET /admin/hotels)
+pub struct AllHotels;
 
-    let hotel_list: Vec<serde_json::Value> = hotels.into_iter().map(|h| serde_json::json!({
-        "id": h.get::<i32, _>("id"),
-        "name": h.get::<String, _>("name"),
-        "status": h.get::<String, _>("status")
-    })).collect();
+impl Listing for AllHotels {
+    const KEY: &'static str = "id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "name", Kind::Text),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "status", Kind::Text),
+        Filter::any_of("owner_id", "owner_id", Kind::Integer),
+        Filter::any_of("location", "location", Kind::Text),
+    ];
+}
+
+// Hoteles pendientes de revisión (GET /admin/hotels/pending); primero los que más esperan
+pub struct PendingHotels;
+
+impl Listing for PendingHotels {
+    const KEY: &'static str = "h.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("updated_at", "COALESCE(h.updated_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "h.name", Kind::Text),
+    ];
+    const DEFAULT_SORT: &'static str = "updated_at";
+    const FILTERS: &'static [Filter] = &[Filter::any_of("location", "h.location", Kind::Text)];
+}
+
+// Negocios pendientes de revisión (GET /admin/businesses/pending)
+pub struct PendingBusinesses;
+
+impl Listing for PendingBusinesses {
+    const KEY: &'static str = "id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "name", Kind::Text),
+    ];
+    const DEFAULT_SORT: &'static str = "created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("business_type", "business_type", Kind::Text),
+        Filter::any_of("location", "location", Kind::Text),
+    ];
+}
+
+pub(crate) fn require_admin(user: &UserInfo) -> Result<(), AppError> {
+    if !matches!(user.role, UserRole::Admin) {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "admin.forbidden"));
+    }
+    Ok(())
+}
+
+pub async fn get_pending_hotels(
+    pool: web::Data<PgPool>,
+    page: Page<PendingHotels>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    // Los reenvíos muestran la nota del último rechazo para que el revisor compare
+    let sql = format!(
+        r#"
+        SELECT h.id, h.name, h.status, h.admin_notes,
+               EXISTS (
+                   SELECT 1 FROM hotel_review_history r
+                   WHERE r.hotel_id = h.id AND r.action = 'resubmitted'
+               ) AS resubmitted,
+               {}
+        FROM hotels h
+        WHERE h.status = 'pending'{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(1),
+        page.order_and_limit()
+    );
+    let hotels = page.bind(sqlx::query(&sql)).fetch_all(pool.get_ref()).await?;
+    let (hotels, next_cursor) = page.finish(hotels);
+
+    let hotel_list: Vec<PendingHotel> = hotels.into_iter().map(|h| PendingHotel {
+        id: h.get::<i32, _>("id"),
+        name: h.get::<String, _>("name"),
+        status: h.get::<String, _>("status"),
+        admin_notes: h.try_get::<Option<String>, _>("admin_notes").unwrap_or(None),
+        resubmitted: h.try_get::<Option<bool>, _>("resubmitted").unwrap_or(None).unwrap_or(false),
+    }).collect();
 
-    Ok(HttpResponse::Ok().json(hotel_list))
+    Ok(HttpResponse::Ok().json(page.response(hotel_list, next_cursor)))
 }
 
-pub async fn approve_hotel(pool: web::Data<PgPool>, path: web::Path<i32>, _user: UserInfo) -> Result<HttpResponse, AppError> {
+pub async fn approve_hotel(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<ApproveNotes>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
     let hotel_id = path.into_inner();
-    let result = sqlx::query("UPDATE hotels SET status = $1 WHERE id = $2")
-        .bind("approved")
-        .bind(hotel_id)
-        .execute(pool.get_ref())
-        .await?;
-    
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Recurso no encontrado".to_string()));
+    let notes = req
+        .and_then(|r| r.into_inner().admin_notes)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    let mut tx = pool.begin().await?;
+
+    if !decide_hotel(&mut tx, hotel_id, "approved", user.id, notes.as_deref()).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "admin.hotel_not_pending"));
     }
-    Ok(HttpResponse::Ok().json(serde_json::json!({"message": "Hotel approved"})))
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("admin.hotel_approved") }))
 }
 
 pub async fn reject_hotel(
     pool: web::Data<PgPool>,
     path: web::Path<i32>,
-    _user: UserInfo,
+    req: web::Json<RejectReason>,
+    user: UserInfo,
 ) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
     let hotel_id = path.into_inner();
-    let result = sqlx::query("UPDATE hotels SET status = $1 WHERE id = $2")
-        .bind("rejected")
-        .bind(hotel_id)
-        .execute(pool.get_ref())
-        .await?;
-    
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Recurso no encontrado".to_string()));
+    let reason = req.reason.trim();
+
+    if reason.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "admin.reason_required"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    if !decide_hotel(&mut tx, hotel_id, "rejected", user.id, Some(reason)).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "admin.hotel_not_pending"));
     }
 
-    Ok(HttpResponse::Ok().json(serde_json::json!({"message": "Hotel rejected"})))
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("admin.hotel_rejected") }))
 }
 
-pub async fn get_all_hotels(pool: web::Data<PgPool>, _user: UserInfo) -> Result<HttpResponse, AppError> {
-    let hotels = sqlx::query("SELECT * FROM hotels")
-        .fetch_all(pool.get_ref())
-        .await?;
+// Aplica la decisión de moderación a un hotel pendiente. Devuelve false si no estaba pendiente.
+// approved_by/approved_at guardan quién y cuándo se tomó la decisión
+pub async fn decide_hotel(
+    conn: &mut PgConnection,
+    hotel_id: i32,
+    status: &str,
+    admin_id: i32,
+    notes: Option<&str>,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE hotels
+        SET status = $2, approved_by = $3, approved_at = NOW(), admin_notes = $4,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id
+        "#
+    )
+    .bind(hotel_id)
+    .bind(status)
+    .bind(admin_id)
+    .bind(notes)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if result.is_none() {
+        return Ok(false);
+    }
+
+    record_hotel_review(conn, hotel_id, status, admin_id, notes).await?;
+    Ok(true)
+}
 
-    let hotel_list: Vec<serde_json::Value> = hotels.into_iter().map(|h| serde_json::json!({
-        "id": h.get::<i32, _>("id"),
-        "name": h.get::<String, _>("name"),
-        "status": h.get::<String, _>("status")
-    })).collect();
+pub async fn decide_business(
+    conn: &mut PgConnection,
+    business_id: i32,
+    status: &str,
+    admin_id: i32,
+    notes: Option<&str>,
+) -> Result<bool, AppError> {
+    let result = sqlx::query(
+        r#"
+        UPDATE businesses
+        SET status = $2, approved_by = $3, approved_at = NOW(), admin_notes = $4,
+            updated_at = NOW()
+        WHERE id = $1 AND status = 'pending'
+        RETURNING id
+        "#
+    )
+    .bind(business_id)
+    .bind(status)
+    .bind(admin_id)
+    .bind(notes)
+    .fetch_optional(conn)
+    .await?;
 
-    Ok(HttpResponse::Ok().json(hotel_list))
+    Ok(result.is_some())
 }
 
-pub async fn get_pending_businesses(pool: web::Data<PgPool>, _user: UserInfo) -> Result<HttpResponse, AppError> {
-    let businesses = sqlx::query("SELECT * FROM businesses WHERE status = $1")
-        .bind("pending")
-        .fetch_all(pool.get_ref())
-        .await?;
+pub(crate) async fn record_hotel_review(
+    conn: &mut PgConnection,
+    hotel_id: i32,
+    action: &str,
+    actor_id: i32,
+    notes: Option<&str>,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO hotel_review_history (hotel_id, action, actor_id, notes) VALUES ($1, $2, $3, $4)"
+    )
+    .bind(hotel_id)
+    .bind(action)
+    .bind(actor_id)
+    .bind(notes)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
 
-    let business_list: Vec<serde_json::Value> = businesses.into_iter().map(|b| serde_json::json!({
-        "id": b.get::<i32, _>("id"),
-        "name": b.get::<String, _>("name"),
-        "status": b.get::<String, _>("status")
-    })).collect();
+pub(crate) async fn load_hotel_review_history(
+    pool: &PgPool,
+    hotel_id: i32,
+) -> Result<Vec<HotelReviewEntry>, AppError> {
+    let entries = sqlx::query(
+        r#"
+        SELECT h.id, h.action, h.notes, h.created_at, h.actor_id,
+               u.first_name || ' ' || u.last_name AS actor_name
+        FROM hotel_review_history h
+        LEFT JOIN users u ON h.actor_id = u.id
+        WHERE h.hotel_id = $1
+        ORDER BY h.created_at DESC, h.id DESC
+        "#
+    )
+    .bind(hotel_id)
+    .fetch_all(pool)
+    .await?;
 
-    Ok(HttpResponse::Ok().json(business_list))
+    Ok(entries.into_iter().map(|e| HotelReviewEntry {
+        id: e.get::<i32, _>("id"),
+        action: e.get::<String, _>("action"),
+        notes: e.try_get::<Option<String>, _>("notes").unwrap_or(None),
+        actor_id: e.try_get::<Option<i32>, _>("actor_id").unwrap_or(None),
+        actor_name: e.try_get::<Option<String>, _>("actor_name").unwrap_or(None),
+        created_at: e.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+    }).collect())
 }
 
-pub async fn approve_business(pool: web::Data<PgPool>, path: web::Path<i32>, _user: UserInfo) -> Result<HttpResponse, AppError> {
+pub async fn get_hotel_review_history(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    let hotel_id = path.into_inner();
+
+    let history = load_hotel_review_history(pool.get_ref(), hotel_id).await?;
+    if history.is_empty() {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "hotel.not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(HotelReviewHistory { history }))
+}
+
+pub async fn get_all_hotels(
+    pool: web::Data<PgPool>,
+    page: Page<AllHotels>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    let sql = format!(
+        "SELECT id, owner_id, name, location, status, created_at, {} FROM hotels WHERE TRUE{} {}",
+        page.columns(),
+        page.conditions(1),
+        page.order_and_limit()
+    );
+    let hotels = page.bind(sqlx::query(&sql)).fetch_all(pool.get_ref()).await?;
+    let (hotels, next_cursor) = page.finish(hotels);
+
+    let hotel_list: Vec<AdminHotel> = hotels.into_iter().map(|h| AdminHotel {
+        id: h.get::<i32, _>("id"),
+        owner_id: h.get::<i32, _>("owner_id"),
+        name: h.get::<String, _>("name"),
+        location: h.get::<String, _>("location"),
+        status: h.get::<String, _>("status"),
+        created_at: h.get::<Option<chrono::DateTime<chrono::Utc>>, _>("created_at"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(hotel_list, next_cursor)))
+}
+
+pub async fn get_pending_businesses(
+    pool: web::Data<PgPool>,
+    page: Page<PendingBusinesses>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    let sql = format!(
+        "SELECT id, name, status, {} FROM businesses WHERE status = 'pending'{} {}",
+        page.columns(),
+        page.conditions(1),
+        page.order_and_limit()
+    );
+    let businesses = page.bind(sqlx::query(&sql)).fetch_all(pool.get_ref()).await?;
+    let (businesses, next_cursor) = page.finish(businesses);
+
+    let business_list: Vec<PendingBusiness> = businesses.into_iter().map(|b| PendingBusiness {
+        id: b.get::<i32, _>("id"),
+        name: b.get::<String, _>("name"),
+        status: b.get::<String, _>("status"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(business_list, next_cursor)))
+}
+
+pub async fn approve_business(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<ApproveNotes>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
     let business_id = path.into_inner();
-    let result = sqlx::query("UPDATE businesses SET status = $1 WHERE id = $2")
-        .bind("approved")
-        .bind(business_id)
-        .execute(pool.get_ref())
-        .await?;
-    
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Recurso no encontrado".to_string()));
+    let notes = req
+        .and_then(|r| r.into_inner().admin_notes)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    let mut conn = pool.acquire().await?;
+    if !decide_business(&mut conn, business_id, "approved", user.id, notes.as_deref()).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "admin.business_not_pending"));
     }
-    Ok(HttpResponse::Ok().json(serde_json::json!({"message": "Business approved"})))
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("admin.business_approved") }))
 }
 
-pub async fn reject_business(pool: web::Data<PgPool>, path: web::Path<i32>, _user: UserInfo) -> Result<HttpResponse, AppError> {
+pub async fn reject_business(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<RejectReason>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
     let business_id = path.into_inner();
-    let result = sqlx::query("UPDATE businesses SET status = $1 WHERE id = $2")
-        .bind("rejected")
-        .bind(business_id)
-        .execute(pool.get_ref())
-        .await?;
-    
-    if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Recurso no encontrado".to_string()));
+    let reason = req
+        .map(|r| r.into_inner().reason.trim().to_string())
+        .filter(|r| !r.is_empty());
+
+    let mut conn = pool.acquire().await?;
+    if !decide_business(&mut conn, business_id, "rejected", user.id, reason.as_deref()).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "admin.business_not_pending"));
     }
-    Ok(HttpResponse::Ok().json(serde_json::json!({"message": "Business rejected"})))
-}
-
-pub async fn get_dashboard_stats(pool: web::Data<PgPool>, _user: UserInfo) -> Result<HttpResponse, AppError> {
-    let user_count_row = sqlx::query("SELECT COUNT(*) as count FROM users").fetch_one(pool.get_ref()).await?;
-    let hotel_count_row = sqlx::query("SELECT COUNT(*) as count FROM hotels").fetch_one(pool.get_ref()).await?;
-    let business_count_row = sqlx::query("SELECT COUNT(*) as count FROM businesses").fetch_one(pool.get_ref()).await?;
-    
-    let user_count: i64 = user_count_row.get("count");
-    let hotel_count: i64 = hotel_count_row.get("count");
-    let business_count: i64 = business_count_row.get("count");
-
-    Ok(HttpResponse::Ok().json(serde_json::json!({
-        "users": user_count,
-        "hotels": hotel_count,
-        "businesses": business_count
-    })))
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("admin.business_rejected") }))
 }
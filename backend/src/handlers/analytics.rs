@@ -0,0 +1,555 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{NaiveDate, Utc};
+use sqlx::{PgPool, Row};
+use crate::{
+    handlers::{admin::require_admin, business::BUSINESS_TIMEZONE, hotel::verify_hotel_ownership},
+    models::{
+        UserInfo,
+        analytics::{
+            AnalyticsHotel, CancellationStats, DashboardBookings, DashboardPeriod, DashboardQuery, DashboardRange,
+            DashboardStats, DateRange, HotelAnalytics, HotelAnalyticsQuery, HotelOccupancy, LeadTime,
+            LeadTimeDistribution, LocationStats, MonthlyStats, NightOccupancy, PendingModeration, PeriodStats,
+        },
+    },
+    utils::errors::AppError,
+};
+
+const DEFAULT_RANGE_DAYS: i64 = 30;
+const HOTEL_REPORT_DEFAULT_DAYS: i64 = 90;
+const MAX_RANGE_DAYS: i64 = 731;
+const TOP_LOCATIONS_LIMIT: i64 = 10;
+
+// Las métricas del panel se recalculan como máximo una vez por minuto por rango
+const DASHBOARD_CACHE_TTL: Duration = Duration::from_secs(60);
+static DASHBOARD_CACHE: OnceLock<Mutex<HashMap<String, (Instant, DashboardStats)>>> = OnceLock::new();
+
+// Rango de fechas inclusivo; por defecto los últimos `default_days` días
+pub(crate) fn resolve_date_range(
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    default_days: i64,
+) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let to = to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = from.unwrap_or_else(|| to - chrono::Duration::days(default_days - 1));
+
+    if from > to {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "invalid_date_range"));
+    }
+    if (to - from).num_days() + 1 > MAX_RANGE_DAYS {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "analytics.range_too_long").arg("days", MAX_RANGE_DAYS));
+    }
+
+    Ok((from, to))
+}
+
+fn cached_dashboard(key: &str) -> Option<DashboardStats> {
+    let cache = DASHBOARD_CACHE.get_or_init(|| Mutex::new(HashMap::new())).lock().ok()?;
+    cache
+        .get(key)
+        .filter(|(stored_at, _)| stored_at.elapsed() < DASHBOARD_CACHE_TTL)
+        .map(|(_, value)| value.clone())
+}
+
+fn store_dashboard(key: String, value: &DashboardStats) {
+    if let Ok(mut cache) = DASHBOARD_CACHE.get_or_init(|| Mutex::new(HashMap::new())).lock() {
+        cache.retain(|_, (stored_at, _)| stored_at.elapsed() < DASHBOARD_CACHE_TTL);
+        cache.insert(key, (Instant::now(), value.clone()));
+    }
+}
+
+pub async fn get_dashboard_stats(
+    pool: web::Data<PgPool>,
+    query: web::Query<DashboardQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+
+    let granularity = match query.granularity.as_deref() {
+        None | Some("day") => "day",
+        Some("week") => "week",
+        Some("month") => "month",
+        Some(other) => return Err(AppError::localized(StatusCode::BAD_REQUEST, "analytics.unknown_granularity").arg("granularity", other)),
+    };
+    let (from, to) = resolve_date_range(query.from, query.to, DEFAULT_RANGE_DAYS)?;
+
+    let cache_key = format!("{}|{}|{}", from, to, granularity);
+    if let Some(stats) = cached_dashboard(&cache_key) {
+        return Ok(HttpResponse::Ok().json(stats));
+    }
+
+    let pool = pool.get_ref();
+
+    let totals = sqlx::query(
+        r#"
+        SELECT (SELECT COUNT(*) FROM users) AS users,
+               (SELECT COUNT(*) FROM hotels) AS hotels,
+               (SELECT COUNT(*) FROM businesses) AS businesses,
+               (SELECT COUNT(*) FROM hotels WHERE status = 'pending') AS pending_hotels,
+               (SELECT COUNT(*) FROM businesses WHERE status = 'pending') AS pending_businesses,
+               (SELECT COUNT(*) FROM reviews WHERE status = 'pending') AS pending_reviews
+        "#
+    )
+    .fetch_one(pool)
+    .await?;
+
+    // Reservas por fecha de creación; los ingresos excluyen las canceladas
+    let booking_totals = sqlx::query(
+        r#"
+        SELECT COUNT(*) AS bookings,
+               COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled,
+               COALESCE(SUM(total_price) FILTER (WHERE status != 'cancelled'), 0)::float8 AS revenue
+        FROM bookings
+        WHERE created_at >= ($1::date::timestamp AT TIME ZONE $3)
+          AND created_at < (($2::date + 1)::timestamp AT TIME ZONE $3)
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_one(pool)
+    .await?;
+
+    let series = sqlx::query(
+        r#"
+        WITH buckets AS (
+            SELECT generate_series(
+                date_trunc($3, $1::date::timestamp),
+                date_trunc($3, $2::date::timestamp),
+                ('1 ' || $3)::interval
+            )::date AS bucket
+        ),
+        booking_stats AS (
+            SELECT date_trunc($3, created_at AT TIME ZONE $4)::date AS bucket,
+                   COUNT(*) AS bookings,
+                   COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled,
+                   COALESCE(SUM(total_price) FILTER (WHERE status != 'cancelled'), 0) AS revenue
+            FROM bookings
+            WHERE created_at >= ($1::date::timestamp AT TIME ZONE $4)
+              AND created_at < (($2::date + 1)::timestamp AT TIME ZONE $4)
+            GROUP BY 1
+        ),
+        user_stats AS (
+            SELECT date_trunc($3, created_at AT TIME ZONE $4)::date AS bucket, COUNT(*) AS new_users
+            FROM users
+            WHERE created_at >= ($1::date::timestamp AT TIME ZONE $4)
+              AND created_at < (($2::date + 1)::timestamp AT TIME ZONE $4)
+            GROUP BY 1
+        ),
+        hotel_stats AS (
+            SELECT date_trunc($3, created_at AT TIME ZONE $4)::date AS bucket, COUNT(*) AS new_hotels
+            FROM hotels
+            WHERE created_at >= ($1::date::timestamp AT TIME ZONE $4)
+              AND created_at < (($2::date + 1)::timestamp AT TIME ZONE $4)
+            GROUP BY 1
+        ),
+        business_stats AS (
+            SELECT date_trunc($3, created_at AT TIME ZONE $4)::date AS bucket, COUNT(*) AS new_businesses
+            FROM businesses
+            WHERE created_at >= ($1::date::timestamp AT TIME ZONE $4)
+              AND created_at < (($2::date + 1)::timestamp AT TIME ZONE $4)
+            GROUP BY 1
+        )
+        SELECT b.bucket,
+               COALESCE(bs.bookings, 0) AS bookings,
+               COALESCE(bs.cancelled, 0) AS cancelled,
+               COALESCE(bs.revenue, 0)::float8 AS revenue,
+               COALESCE(us.new_users, 0) AS new_users,
+               COALESCE(hs.new_hotels, 0) AS new_hotels,
+               COALESCE(bz.new_businesses, 0) AS new_businesses
+        FROM buckets b
+        LEFT JOIN booking_stats bs ON bs.bucket = b.bucket
+        LEFT JOIN user_stats us ON us.bucket = b.bucket
+        LEFT JOIN hotel_stats hs ON hs.bucket = b.bucket
+        LEFT JOIN business_stats bz ON bz.bucket = b.bucket
+        ORDER BY b.bucket
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(granularity)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_all(pool)
+    .await?;
+
+    // Noches-habitación ocupadas dentro del rango frente a las disponibles
+    let occupancy = sqlx::query(
+        r#"
+        SELECT h.id, h.name, h.location, h.rooms_available,
+               COALESCE(SUM(
+                   bk.rooms * (LEAST(bk.check_out, $2::date + 1) - GREATEST(bk.check_in, $1::date))
+               ), 0) AS room_nights
+        FROM hotels h
+        LEFT JOIN bookings bk ON bk.hotel_id = h.id
+                             AND bk.status != 'cancelled'
+                             AND bk.check_in <= $2
+                             AND bk.check_out > $1
+        WHERE h.status = 'approved'
+        GROUP BY h.id
+        ORDER BY h.name
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    let top_locations = sqlx::query(
+        r#"
+        SELECT h.location,
+               COUNT(*) AS bookings,
+               COALESCE(SUM(bk.total_price) FILTER (WHERE bk.status != 'cancelled'), 0)::float8 AS revenue
+        FROM bookings bk
+        JOIN hotels h ON bk.hotel_id = h.id
+        WHERE bk.created_at >= ($1::date::timestamp AT TIME ZONE $3)
+          AND bk.created_at < (($2::date + 1)::timestamp AT TIME ZONE $3)
+        GROUP BY h.location
+        ORDER BY bookings DESC, revenue DESC
+        LIMIT $4
+        "#
+    )
+    .bind(from)
+    .bind(to)
+    .bind(BUSINESS_TIMEZONE)
+    .bind(TOP_LOCATIONS_LIMIT)
+    .fetch_all(pool)
+    .await?;
+
+    let days_in_range = (to - from).num_days() + 1;
+    let bookings = booking_totals.get::<i64, _>("bookings");
+    let cancelled = booking_totals.get::<i64, _>("cancelled");
+    let cancellation_rate = if bookings > 0 { cancelled as f64 / bookings as f64 } else { 0.0 };
+
+    let series_list: Vec<DashboardPeriod> = series.into_iter().map(|s| DashboardPeriod {
+        period: s.get::<NaiveDate, _>("bucket"),
+        bookings: s.get::<i64, _>("bookings"),
+        cancelled: s.get::<i64, _>("cancelled"),
+        revenue: s.get::<f64, _>("revenue"),
+        new_users: s.get::<i64, _>("new_users"),
+        new_hotels: s.get::<i64, _>("new_hotels"),
+        new_businesses: s.get::<i64, _>("new_businesses"),
+    }).collect();
+
+    let occupancy_list: Vec<HotelOccupancy> = occupancy.into_iter().map(|h| {
+        let rooms = h.get::<i32, _>("rooms_available") as i64;
+        let room_nights = h.try_get::<Option<i64>, _>("room_nights").unwrap_or(None).unwrap_or(0);
+        let capacity = rooms * days_in_range;
+        HotelOccupancy {
+            hotel_id: h.get::<i32, _>("id"),
+            name: h.get::<String, _>("name"),
+            location: h.get::<String, _>("location"),
+            room_nights_sold: room_nights,
+            room_nights_available: capacity,
+            occupancy_rate: if capacity > 0 { room_nights as f64 / capacity as f64 } else { 0.0 },
+        }
+    }).collect();
+
+    let location_list: Vec<LocationStats> = top_locations.into_iter().map(|l| LocationStats {
+        location: l.get::<String, _>("location"),
+        bookings: l.get::<i64, _>("bookings"),
+        revenue: l.get::<f64, _>("revenue"),
+    }).collect();
+
+    let stats = DashboardStats {
+        users: totals.get::<i64, _>("users"),
+        hotels: totals.get::<i64, _>("hotels"),
+        businesses: totals.get::<i64, _>("businesses"),
+        range: DashboardRange {
+            from,
+            to,
+            granularity: granularity.to_string(),
+        },
+        bookings: DashboardBookings {
+            total: bookings,
+            cancelled,
+            cancellation_rate,
+            revenue: booking_totals.get::<f64, _>("revenue"),
+        },
+        pending_moderation: PendingModeration {
+            hotels: totals.get::<i64, _>("pending_hotels"),
+            businesses: totals.get::<i64, _>("pending_businesses"),
+            reviews: totals.get::<i64, _>("pending_reviews"),
+        },
+        series: series_list,
+        occupancy: occupancy_list,
+        top_locations: location_list,
+        generated_at: Utc::now(),
+    };
+
+    store_dashboard(cache_key, &stats);
+
+    Ok(HttpResponse::Ok().json(stats))
+}
+
+struct NightStat {
+    night: NaiveDate,
+    rooms_sold: i64,
+    revenue: f64,
+}
+
+#[derive(Default)]
+struct PeriodStat {
+    nights: i64,
+    rooms_sold: i64,
+    revenue: f64,
+}
+
+impl PeriodStat {
+    fn add(&mut self, night: &NightStat) {
+        self.nights += 1;
+        self.rooms_sold += night.rooms_sold;
+        self.revenue += night.revenue;
+    }
+
+    fn stats(&self, rooms_available: i64) -> PeriodStats {
+        let capacity = self.nights * rooms_available;
+        PeriodStats {
+            nights: self.nights,
+            room_nights_sold: self.rooms_sold,
+            room_nights_available: capacity,
+            occupancy_rate: ratio(self.rooms_sold as f64, capacity as f64),
+            revenue: round2(self.revenue),
+            adr: round2(ratio(self.revenue, self.rooms_sold as f64)),
+            revpar: round2(ratio(self.revenue, capacity as f64)),
+        }
+    }
+}
+
+fn ratio(numerator: f64, denominator: f64) -> f64 {
+    if denominator > 0.0 { numerator / denominator } else { 0.0 }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+async fn authorize_hotel_report(
+    pool: &PgPool,
+    hotel_id: i32,
+    user: &UserInfo,
+) -> Result<(String, i64), AppError> {
+    if !verify_hotel_ownership(pool, hotel_id, user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "analytics.hotel_forbidden"));
+    }
+
+    let hotel = sqlx::query("SELECT name, rooms_available FROM hotels WHERE id = $1")
+        .bind(hotel_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "hotel.not_found"))?;
+
+    Ok((hotel.get::<String, _>("name"), hotel.get::<i32, _>("rooms_available") as i64))
+}
+
+// Ocupación e ingreso por noche; el precio de cada reserva se reparte entre sus noches
+async fn load_night_stats(
+    pool: &PgPool,
+    hotel_id: i32,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<NightStat>, AppError> {
+    let rows = sqlx::query(
+        r#"
+        WITH nights AS (
+            SELECT generate_series($2::date, $3::date, INTERVAL '1 day')::date AS night
+        )
+        SELECT n.night,
+               COALESCE(SUM(b.rooms), 0) AS rooms_sold,
+               COALESCE(SUM(b.total_price / (b.check_out - b.check_in)), 0)::float8 AS revenue
+        FROM nights n
+        LEFT JOIN bookings b ON b.hotel_id = $1
+                            AND b.status != 'cancelled'
+                            AND b.check_in <= n.night
+                            AND b.check_out > n.night
+        GROUP BY n.night
+        ORDER BY n.night
+        "#
+    )
+    .bind(hotel_id)
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|r| NightStat {
+        night: r.get::<NaiveDate, _>("night"),
+        rooms_sold: r.try_get::<Option<i64>, _>("rooms_sold").unwrap_or(None).unwrap_or(0),
+        revenue: r.get::<f64, _>("revenue"),
+    }).collect())
+}
+
+fn group_by_month(nights: &[NightStat]) -> BTreeMap<String, PeriodStat> {
+    let mut months: BTreeMap<String, PeriodStat> = BTreeMap::new();
+    for night in nights {
+        months.entry(night.night.format("%Y-%m").to_string()).or_default().add(night);
+    }
+    months
+}
+
+pub async fn get_hotel_analytics(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    query: web::Query<HotelAnalyticsQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    let pool = pool.get_ref();
+    let (name, rooms_available) = authorize_hotel_report(pool, hotel_id, &user).await?;
+    let (from, to) = resolve_date_range(query.from, query.to, HOTEL_REPORT_DEFAULT_DAYS)?;
+
+    let nights = load_night_stats(pool, hotel_id, from, to).await?;
+
+    // Anticipación: días entre la creación de la reserva y el check-in
+    let lead_time = sqlx::query(
+        r#"
+        SELECT COUNT(*) AS bookings,
+               AVG(lead_days)::float8 AS avg_days,
+               percentile_cont(0.5) WITHIN GROUP (ORDER BY lead_days)::float8 AS median_days,
+               COUNT(*) FILTER (WHERE lead_days <= 7) AS within_week,
+               COUNT(*) FILTER (WHERE lead_days BETWEEN 8 AND 30) AS within_month,
+               COUNT(*) FILTER (WHERE lead_days BETWEEN 31 AND 90) AS within_quarter,
+               COUNT(*) FILTER (WHERE lead_days > 90) AS beyond_quarter
+        FROM (
+            SELECT GREATEST(b.check_in - (b.created_at AT TIME ZONE $4)::date, 0) AS lead_days
+            FROM bookings b
+            WHERE b.hotel_id = $1 AND b.status != 'cancelled'
+              AND b.check_in BETWEEN $2 AND $3
+        ) l
+        "#
+    )
+    .bind(hotel_id)
+    .bind(from)
+    .bind(to)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_one(pool)
+    .await?;
+
+    let cancellations = sqlx::query(
+        r#"
+        SELECT COUNT(*) AS bookings,
+               COUNT(*) FILTER (WHERE status = 'cancelled') AS cancelled,
+               COALESCE(SUM(total_price) FILTER (WHERE status = 'cancelled'), 0)::float8 AS lost_revenue,
+               AVG(check_in - (cancelled_at AT TIME ZONE $4)::date)
+                   FILTER (WHERE status = 'cancelled' AND cancelled_at IS NOT NULL)::float8 AS avg_days_before_check_in
+        FROM bookings
+        WHERE hotel_id = $1 AND check_in BETWEEN $2 AND $3
+        "#
+    )
+    .bind(hotel_id)
+    .bind(from)
+    .bind(to)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_one(pool)
+    .await?;
+
+    let mut summary = PeriodStat::default();
+    for night in &nights {
+        summary.add(night);
+    }
+
+    let night_list: Vec<NightOccupancy> = nights.iter().map(|n| NightOccupancy {
+        date: n.night,
+        rooms_sold: n.rooms_sold,
+        occupancy_rate: ratio(n.rooms_sold as f64, rooms_available as f64),
+        revenue: round2(n.revenue),
+    }).collect();
+
+    let month_list: Vec<MonthlyStats> = group_by_month(&nights).into_iter().map(|(month, stat)| MonthlyStats {
+        month,
+        stats: stat.stats(rooms_available),
+    }).collect();
+
+    let total_bookings = cancellations.get::<i64, _>("bookings");
+    let cancelled = cancellations.get::<i64, _>("cancelled");
+
+    Ok(HttpResponse::Ok().json(HotelAnalytics {
+        hotel: AnalyticsHotel {
+            id: hotel_id,
+            name,
+            rooms_available,
+        },
+        range: DateRange { from, to },
+        summary: summary.stats(rooms_available),
+        occupancy_by_night: night_list,
+        revenue_by_month: month_list,
+        lead_time: LeadTime {
+            bookings: lead_time.get::<i64, _>("bookings"),
+            avg_days: lead_time.try_get::<Option<f64>, _>("avg_days").unwrap_or(None),
+            median_days: lead_time.try_get::<Option<f64>, _>("median_days").unwrap_or(None),
+            distribution: LeadTimeDistribution {
+                within_week: lead_time.get::<i64, _>("within_week"),
+                within_month: lead_time.get::<i64, _>("within_month"),
+                within_quarter: lead_time.get::<i64, _>("within_quarter"),
+                beyond_quarter: lead_time.get::<i64, _>("beyond_quarter"),
+            },
+        },
+        cancellations: CancellationStats {
+            bookings: total_bookings,
+            cancelled,
+            cancellation_rate: ratio(cancelled as f64, total_bookings as f64),
+            lost_revenue: round2(cancellations.get::<f64, _>("lost_revenue")),
+            avg_days_before_check_in: cancellations.try_get::<Option<f64>, _>("avg_days_before_check_in").unwrap_or(None),
+        },
+    }))
+}
+
+pub async fn export_hotel_analytics_csv(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    query: web::Query<HotelAnalyticsQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    let pool = pool.get_ref();
+    let (_, rooms_available) = authorize_hotel_report(pool, hotel_id, &user).await?;
+    let (from, to) = resolve_date_range(query.from, query.to, HOTEL_REPORT_DEFAULT_DAYS)?;
+
+    let nights = load_night_stats(pool, hotel_id, from, to).await?;
+
+    let (report, csv) = match query.report.as_deref() {
+        None | Some("nightly") => {
+            let mut csv = String::from("date,rooms_sold,rooms_available,occupancy_rate,revenue\n");
+            for n in &nights {
+                csv.push_str(&format!(
+                    "{},{},{},{:.4},{:.2}\n",
+                    n.night,
+                    n.rooms_sold,
+                    rooms_available,
+                    ratio(n.rooms_sold as f64, rooms_available as f64),
+                    n.revenue
+                ));
+            }
+            ("nightly", csv)
+        }
+        Some("monthly") => {
+            let mut csv = String::from("month,room_nights_sold,room_nights_available,occupancy_rate,revenue,adr,revpar\n");
+            for (month, stat) in group_by_month(&nights) {
+                let capacity = stat.nights * rooms_available;
+                csv.push_str(&format!(
+                    "{},{},{},{:.4},{:.2},{:.2},{:.2}\n",
+                    month,
+                    stat.rooms_sold,
+                    capacity,
+                    ratio(stat.rooms_sold as f64, capacity as f64),
+                    stat.revenue,
+                    ratio(stat.revenue, stat.rooms_sold as f64),
+                    ratio(stat.revenue, capacity as f64)
+                ));
+            }
+            ("monthly", csv)
+        }
+        Some(other) => return Err(AppError::localized(StatusCode::BAD_REQUEST, "analytics.unknown_report").arg("report", other)),
+    };
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/csv; charset=utf-8")
+        .insert_header((
+            "Content-Disposition",
+            format!("attachment; filename=\"hotel-{}-{}-{}-{}.csv\"", hotel_id, report, from, to),
+        ))
+        .body(csv))
+}
@@ -1,45 +1,20 @@
 // backend/src/handlers/auth.rs - VERSIÓN CORREGIDA
 use actix_web::{web, HttpResponse, Result};
 use sqlx::PgPool;
-use serde_json::json;
 use bcrypt::{hash, verify, DEFAULT_COST}; // ✅ USAR BCRYPT REAL
-use jsonwebtoken::{encode, Header, EncodingKey};
-use chrono::{Utc, Duration};
 use validator::Validate;
 
-use crate::models::{RegisterRequest, LoginRequest, AuthResponse, UserInfo, JwtClaims, User};
-use crate::utils::errors::AppError; // ✅ IMPORTAR AppError
-
-// ✅ FUNCIÓN MEJORADA para crear JWT
-fn create_jwt_simple(
-    user_id: i32, 
-    email: &str, 
-    user_type: &str,
-    first_name: Option<&str>,
-    last_name: Option<&str>,
-    phone: Option<&str>
-) -> Result<String, jsonwebtoken::errors::Error> {
-    let expiration = Utc::now()
-        .checked_add_signed(Duration::days(7))
-        .expect("valid timestamp")
-        .timestamp() as usize;
-
-    let claims = JwtClaims {
-        user_id,
-        email: email.to_string(),
-        user_type: user_type.to_string(),
-        exp: expiration,
-    };
+use actix_web::http::StatusCode;
 
-    encode(
-        &Header::default(),
-        &claims,
-        &EncodingKey::from_secret("esta_es_una_clave_diferente_para_la_copia".as_ref()),
-    )
-}
+use crate::models::{RegisterRequest, LoginRequest, AuthResponse, MeResponse, UpdateLocaleRequest, UserInfo, UserRole};
+use crate::utils::errors::AppError; // ✅ IMPORTAR AppError
+use crate::config::Settings;
+use crate::middleware::locale::current_locale;
+use crate::utils::{i18n::{t, translate, Locale}, jwt::create_jwt};
 
 pub async fn register(
     pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
     register_req: web::Json<RegisterRequest>,
 ) -> Result<HttpResponse, AppError> { // ✅ USAR AppError
     println!("🔐 [AUTH] Procesando registro para: {}", register_req.email);
@@ -47,7 +22,7 @@ pub async fn register(
     // Validar datos de entrada
     if let Err(errors) = register_req.validate() {
         println!("❌ [AUTH] Errores de validación: {:?}", errors);
-        return Err(AppError::BadRequest(format!("Datos inválidos: {}", errors)));
+        return Err(AppError::Validation(errors));
     }
 
     // Verificar si el usuario ya existe
@@ -60,23 +35,25 @@ pub async fn register(
 
     if existing_user.is_some() {
         println!("⚠️ [AUTH] Email ya registrado: {}", register_req.email);
-        return Err(AppError::Conflict("Este email ya está registrado".to_string()));
+        return Err(AppError::localized(StatusCode::CONFLICT, "auth.email_taken"));
     }
 
     // ✅ HASHEAR CONTRASEÑA CON BCRYPT REAL
     let password_hash = hash(&register_req.password, DEFAULT_COST)?;
 
-    // Crear usuario en la base de datos
+    // Crear usuario en la base de datos; el idioma con el que se registró queda como preferido
     let user_record = sqlx::query!(
         r#"
-        INSERT INTO users (first_name, last_name, email, password_hash, user_type) 
-        VALUES ($1, $2, $3, $4, 'customer') 
-        RETURNING id, first_name, last_name, email, user_type, created_at
+        INSERT INTO users (first_name, last_name, email, password_hash, phone, role, preferred_locale) 
+        VALUES ($1, $2, $3, $4, $5, 'customer', $6) 
+        RETURNING id, first_name, last_name, email, phone, role, preferred_locale
         "#,
         register_req.first_name,
         register_req.last_name,
         register_req.email,
-        password_hash
+        password_hash,
+        register_req.phone,
+        current_locale().code()
     )
     .fetch_one(pool.get_ref())
     .await?; // ✅ USAR ? operator
@@ -85,26 +62,21 @@ pub async fn register(
 
     let user_info = UserInfo {
         id: user_record.id,
-        first_name: user_record.first_name,
-        last_name: user_record.last_name,
         email: user_record.email,
-        user_type: user_record.user_type,
+        role: UserRole::from_string(&user_record.role),
+        first_name: Some(user_record.first_name),
+        last_name: Some(user_record.last_name),
+        phone: user_record.phone,
+        locale: user_record.preferred_locale,
     };
 
     // ✅ GENERAR JWT CON MANEJO DE ERRORES CORRECTO
-    let token = create_jwt_simple(
-        user_record.id, 
-        &user_info.email, 
-        &user_info.user_type,
-        Some(&user_info.first_name),
-        Some(&user_info.last_name),
-        None
-    )?;
+    let token = create_jwt(&settings.auth, &user_info)?;
 
     println!("🎫 [AUTH] JWT generado exitosamente");
 
     let response = AuthResponse {
-        message: "Usuario registrado exitosamente".to_string(),
+        message: t("auth.registered"),
         token,
         user: user_info,
     };
@@ -114,6 +86,7 @@ pub async fn register(
 
 pub async fn login(
     pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
     login_req: web::Json<LoginRequest>,
 ) -> Result<HttpResponse, AppError> { // ✅ USAR AppError
     println!("🔐 [AUTH] Procesando login para: {}", login_req.email);
@@ -121,13 +94,12 @@ pub async fn login(
     // Validar datos de entrada
     if let Err(errors) = login_req.validate() {
         println!("❌ [AUTH] Errores de validación: {:?}", errors);
-        return Err(AppError::BadRequest(format!("Datos inválidos: {}", errors)));
+        return Err(AppError::Validation(errors));
     }
 
     // Buscar usuario en la base de datos
-    let user = sqlx::query_as!(
-        User,
-        "SELECT id, first_name, last_name, email, password_hash, user_type, created_at, updated_at FROM users WHERE email = $1",
+    let user = sqlx::query!(
+        "SELECT id, first_name, last_name, email, phone, password_hash, role, preferred_locale FROM users WHERE email = $1",
         login_req.email
     )
     .fetch_optional(pool.get_ref())
@@ -137,7 +109,7 @@ pub async fn login(
         Some(u) => u,
         None => {
             println!("❌ [AUTH] Usuario no encontrado: {}", login_req.email);
-            return Err(AppError::Unauthorized("Email o contraseña incorrectos".to_string()));
+            return Err(AppError::localized(StatusCode::UNAUTHORIZED, "auth.invalid_credentials"));
         }
     };
 
@@ -146,33 +118,28 @@ pub async fn login(
     // ✅ VERIFICAR CONTRASEÑA CON BCRYPT REAL
     if !verify(&login_req.password, &user.password_hash)? {
         println!("❌ [AUTH] Contraseña incorrecta");
-        return Err(AppError::Unauthorized("Email o contraseña incorrectos".to_string()));
+        return Err(AppError::localized(StatusCode::UNAUTHORIZED, "auth.invalid_credentials"));
     }
 
     println!("✅ [AUTH] Contraseña correcta");
 
     let user_info = UserInfo {
         id: user.id,
-        first_name: user.first_name,
-        last_name: user.last_name,
         email: user.email,
-        user_type: user.user_type,
+        role: UserRole::from_string(&user.role),
+        first_name: Some(user.first_name),
+        last_name: Some(user.last_name),
+        phone: user.phone,
+        locale: user.preferred_locale,
     };
 
     // ✅ GENERAR JWT CON MANEJO DE ERRORES CORRECTO
-    let token = create_jwt_simple(
-        user_info.id, 
-        &user_info.email, 
-        &user_info.user_type,
-        Some(&user_info.first_name),
-        Some(&user_info.last_name),
-        None
-    )?;
+    let token = create_jwt(&settings.auth, &user_info)?;
 
     println!("🎫 [AUTH] Login exitoso, JWT generado");
 
     let response = AuthResponse {
-        message: "Login exitoso".to_string(),
+        message: t("auth.logged_in"),
         token,
         user: user_info,
     };
@@ -183,7 +150,36 @@ pub async fn login(
 pub async fn me(user: UserInfo) -> Result<HttpResponse, AppError> {
     println!("👤 [AUTH] Obteniendo información del usuario: {}", user.email);
     
-    Ok(HttpResponse::Ok().json(json!({
-        "user": user
-    })))
-}
\ No newline at end of file
+    Ok(HttpResponse::Ok().json(MeResponse { user }))
+}
+// Guarda el idioma preferido y devuelve un token nuevo que ya lo incluye
+pub async fn update_locale(
+    pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
+    req: web::Json<UpdateLocaleRequest>,
+    mut user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    // Se guarda el idioma con catálogo: "es-MX" o "yua" quedan como "es"
+    let locale = Locale::parse(&req.locale)
+        .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "unsupported_locale").arg("locale", req.locale.trim()))?;
+
+    sqlx::query("UPDATE users SET preferred_locale = $2, updated_at = NOW() WHERE id = $1")
+        .bind(user.id)
+        .bind(locale.code())
+        .execute(pool.get_ref())
+        .await?;
+
+    user.locale = Some(locale.code().to_string());
+    let token = create_jwt(&settings.auth, &user)?;
+
+    // El mensaje ya sale en el idioma recién elegido
+    Ok(HttpResponse::Ok().json(AuthResponse {
+        message: translate(locale, "auth.locale_updated", &[]),
+        token,
+        user,
+    }))
+}
@@ -1,26 +1,60 @@
 // backend/src/handlers/booking.rs - CÓDIGO COMPLETO CORREGIDO
 use actix_web::{web, HttpResponse, Result};
-use sqlx::PgPool;
+use sqlx::{FromRow, PgPool, Row};
 use serde_json::json;
 use bigdecimal::BigDecimal;
 use std::str::FromStr;
 use validator::Validate;
 
+use actix_web::http::StatusCode;
+
 use crate::models::*;
-use crate::utils::errors::AppError;
-use crate::handlers::hotel::verify_hotel_ownership;
-
-#[derive(serde::Serialize)]
-pub struct HotelBookingDetail {
-    id: i32,
-    check_in: chrono::NaiveDate,
-    check_out: chrono::NaiveDate,
-    guests: i32,
-    total_price: f64,
-    status: String,
-    created_at: chrono::DateTime<chrono::Utc>,
-    customer_name: String,
-    customer_email: String,
+use crate::middleware::locale::current_locale;
+use crate::utils::{
+    errors::AppError,
+    i18n::{t, translate},
+    pagination::{Filter, Kind, Listing, Page, SortField},
+};
+use crate::handlers::{
+    document::money,
+    hotel::verify_hotel_ownership,
+    notification::{notify_user, queue_email, recipient_locale},
+};
+
+// Reservas del cliente (GET /bookings/my)
+pub struct MyBookings;
+
+impl Listing for MyBookings {
+    const KEY: &'static str = "b.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(b.created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("check_in", "b.check_in", Kind::Date),
+        SortField::new("total_price", "b.total_price", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "b.status", Kind::Text),
+        Filter::any_of("payment_status", "b.payment_status", Kind::Text),
+        Filter::at_least("check_in_from", "b.check_in", Kind::Date),
+        Filter::at_most("check_in_to", "b.check_in", Kind::Date),
+    ];
+}
+
+// Reservas de un hotel para su propietario (GET /hotels/{id}/bookings)
+pub struct HotelBookings;
+
+impl Listing for HotelBookings {
+    const KEY: &'static str = "b.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("check_in", "b.check_in", Kind::Date),
+        SortField::new("created_at", "COALESCE(b.created_at, 'epoch')", Kind::Timestamp),
+    ];
+    const DEFAULT_SORT: &'static str = "-check_in";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "b.status", Kind::Text),
+        Filter::at_least("check_in_from", "b.check_in", Kind::Date),
+        Filter::at_most("check_in_to", "b.check_in", Kind::Date),
+    ];
 }
 
 pub async fn create_booking(
@@ -34,13 +68,13 @@ pub async fn create_booking(
     // Validar datos de entrada
     if let Err(errors) = booking_req.validate() {
         println!("❌ [BOOKING] Error de validación: {:?}", errors);
-        return Err(AppError::BadRequest(format!("Datos inválidos: {}", errors)));
+        return Err(AppError::Validation(errors));
     }
 
     // Validar fechas
     if booking_req.check_out <= booking_req.check_in {
         println!("❌ [BOOKING] Error: fecha checkout <= checkin");
-        return Err(AppError::BadRequest("La fecha de check-out debe ser posterior a la de check-in".to_string()));
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "booking.invalid_dates"));
     }
 
     // Verificar que el hotel existe y está aprobado
@@ -59,7 +93,7 @@ pub async fn create_booking(
         },
         None => {
             println!("❌ [BOOKING] Hotel no encontrado o no aprobado");
-            return Err(AppError::NotFound("Hotel no encontrado o no disponible".to_string()));
+            return Err(AppError::localized(StatusCode::NOT_FOUND, "booking.hotel_unavailable"));
         }
     };
 
@@ -117,7 +151,31 @@ pub async fn create_booking(
     
     println!("🎫 [BOOKING] Referencia generada: {}", booking_reference);
 
-    // Crear la reserva en la base de datos
+    let mut tx = pool.begin().await?;
+
+    // Se bloquea el hotel para que dos reservas simultáneas no vendan la misma habitación; la
+    // disponibilidad (reservas activas y noches bloqueadas por calendarios externos) se comprueba
+    // dentro de la misma transacción que inserta la reserva
+    sqlx::query("SELECT id FROM hotels WHERE id = $1 FOR UPDATE")
+        .bind(booking_req.hotel_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let availability = sqlx::query!(
+        "SELECT hotel_rooms_free($1, $2, $3) AS rooms_free",
+        booking_req.hotel_id,
+        booking_req.check_in,
+        booking_req.check_out
+    )
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if availability.rooms_free.unwrap_or(0) < booking_req.rooms {
+        println!("❌ [BOOKING] Sin habitaciones disponibles para las fechas solicitadas");
+        return Err(AppError::localized(StatusCode::CONFLICT, "booking.no_rooms"));
+    }
+
+    // Crear la reserva y encolar el correo de confirmación en la misma transacción
     println!("💾 [BOOKING] Insertando en BD...");
     let booking = sqlx::query!(
         r#"
@@ -138,81 +196,105 @@ pub async fn create_booking(
         booking_req.addon_services.as_ref(),
         booking_reference
     )
-    .fetch_one(pool.get_ref())
+    .fetch_one(&mut *tx)
     .await?;
 
+    // El correo sale en el idioma de la petición (la preferencia del usuario si la tiene)
+    let locale = current_locale();
+    let email_args = [
+        ("name", user.first_name.clone().filter(|n| !n.is_empty()).unwrap_or_else(|| user.email.clone())),
+        ("reference", booking_reference.clone()),
+        ("hotel", hotel.name.clone()),
+        ("check_in", booking_req.check_in.format("%d/%m/%Y").to_string()),
+        ("check_out", booking_req.check_out.format("%d/%m/%Y").to_string()),
+        ("guests", booking_req.guests.to_string()),
+        ("total", money(total_price)),
+    ];
+    queue_email(
+        &mut tx,
+        user.id,
+        &user.email,
+        locale,
+        "booking_confirmation",
+        &translate(locale, "email.booking_confirmation.subject", &email_args),
+        &translate(locale, "email.booking_confirmation.body", &email_args),
+    )
+    .await?;
+
+    tx.commit().await?;
+
     println!("✅ [BOOKING] Reserva creada exitosamente! ID: {}", booking.id);
     
-    let response_data = json!({
-        "message": "Reserva creada exitosamente",
-        "booking": {
-            "id": booking.id,
-            "reference": booking_reference,
-            "hotel_name": hotel.name,
-            "hotel_location": hotel.location,
-            "check_in": booking_req.check_in,
-            "check_out": booking_req.check_out,
-            "guests": booking_req.guests,
-            "rooms": booking_req.rooms,
-            "total_price": total_price,
-            "status": "pending",
-            "created_at": booking.created_at
-        }
-    });
+    let response_data = BookingCreatedResponse {
+        message: t("booking.created"),
+        booking: BookingSummary {
+            id: booking.id,
+            reference: booking_reference,
+            hotel_name: hotel.name,
+            hotel_location: hotel.location,
+            check_in: booking_req.check_in,
+            check_out: booking_req.check_out,
+            guests: booking_req.guests,
+            rooms: booking_req.rooms,
+            total_price,
+            status: "pending".to_string(),
+            created_at: booking.created_at,
+        },
+    };
     
-    println!("📤 [BOOKING] Enviando respuesta: {}", response_data);
+    println!("📤 [BOOKING] Enviando respuesta: {:?}", response_data);
     Ok(HttpResponse::Created().json(response_data))
 }
 
 pub async fn get_my_bookings(
     pool: web::Data<PgPool>,
+    page: Page<MyBookings>,
     user: UserInfo,
 ) -> Result<HttpResponse, AppError> {
-    println!("📋 [BOOKINGS] Obteniendo reservas para usuario {}", user.id);
-    
-    let bookings = sqlx::query!(
+    let sql = format!(
         r#"
         SELECT b.id, b.check_in, b.check_out, b.guests, b.rooms, 
                b.total_price::text as total_price_text, b.status, b.payment_status,
                b.special_requests, b.addon_services, b.created_at, b.booking_reference,
-               h.name as hotel_name, h.location as hotel_location, h.address as hotel_address
+               h.name as hotel_name, h.location as hotel_location, h.address as hotel_address,
+               {}
         FROM bookings b
         JOIN hotels h ON b.hotel_id = h.id
-        WHERE b.user_id = $1
-        ORDER BY b.created_at DESC
+        WHERE b.user_id = $1{}
+        {}
         "#,
-        user.id
-    )
-    .fetch_all(pool.get_ref())
-    .await?;
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let bookings = page
+        .bind(sqlx::query(&sql).bind(user.id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (bookings, next_cursor) = page.finish(bookings);
 
-    println!("✅ [BOOKINGS] Encontradas {} reservas", bookings.len());
-    
-    let booking_list: Vec<serde_json::Value> = bookings
+    let booking_list: Vec<CustomerBooking> = bookings
         .into_iter()
-        .map(|b| json!({
-            "id": b.id, 
-            "hotel_name": b.hotel_name, 
-            "hotel_location": b.hotel_location, 
-            "hotel_address": b.hotel_address,
-            "check_in": b.check_in, 
-            "check_out": b.check_out, 
-            "guests": b.guests, 
-            "rooms": b.rooms,
-            "total_price": b.total_price_text.unwrap_or("0.0".to_string()).parse::<f64>().unwrap_or(0.0),
-            "status": b.status, 
-            "payment_status": b.payment_status, 
-            "special_requests": b.special_requests,
-            "addon_services": b.addon_services, 
-            "created_at": b.created_at, 
-            "booking_reference": b.booking_reference
-        }))
+        .map(|b| CustomerBooking {
+            id: b.get::<i32, _>("id"), 
+            hotel_name: b.get::<String, _>("hotel_name"), 
+            hotel_location: b.get::<String, _>("hotel_location"), 
+            hotel_address: b.get::<Option<String>, _>("hotel_address"),
+            check_in: b.get::<chrono::NaiveDate, _>("check_in"), 
+            check_out: b.get::<chrono::NaiveDate, _>("check_out"), 
+            guests: b.get::<i32, _>("guests"), 
+            rooms: b.get::<i32, _>("rooms"),
+            total_price: b.get::<Option<String>, _>("total_price_text").and_then(|p| p.parse::<f64>().ok()).unwrap_or(0.0),
+            status: b.get::<String, _>("status"), 
+            payment_status: b.get::<String, _>("payment_status"), 
+            special_requests: b.get::<Option<String>, _>("special_requests"),
+            addon_services: b.get::<Option<serde_json::Value>, _>("addon_services"), 
+            created_at: b.get::<Option<chrono::DateTime<chrono::Utc>>, _>("created_at"), 
+            booking_reference: b.get::<String, _>("booking_reference"),
+        })
         .collect();
         
-    Ok(HttpResponse::Ok().json(json!({ 
-        "bookings": booking_list, 
-        "total": booking_list.len() 
-    })))
+    Ok(HttpResponse::Ok().json(page.response(booking_list, next_cursor)))
 }
 
 pub async fn cancel_booking(
@@ -231,7 +313,7 @@ pub async fn cancel_booking(
             cancelled_at = CURRENT_TIMESTAMP,
             cancellation_reason = $3, 
             updated_at = CURRENT_TIMESTAMP
-        WHERE id = $1 AND user_id = $2 AND status != 'cancelled'
+        WHERE id = $1 AND user_id = $2 AND status NOT IN ('cancelled', 'checked_in', 'completed')
         RETURNING id
         "#,
         booking_id, 
@@ -244,13 +326,11 @@ pub async fn cancel_booking(
     match result {
         Some(_) => {
             println!("✅ [BOOKING] Reserva {} cancelada", booking_id);
-            Ok(HttpResponse::Ok().json(json!({ 
-                "message": "Reserva cancelada exitosamente" 
-            })))
+            Ok(HttpResponse::Ok().json(MessageResponse { message: t("booking.cancelled") }))
         },
         None => {
             println!("❌ [BOOKING] Reserva {} no encontrada o ya cancelada", booking_id);
-            Err(AppError::NotFound("Reserva no encontrada o ya cancelada".to_string()))
+            Err(AppError::localized(StatusCode::NOT_FOUND, "booking.not_cancellable"))
         }
     }
 }
@@ -258,36 +338,223 @@ pub async fn cancel_booking(
 pub async fn get_hotel_bookings(
     pool: web::Data<PgPool>,
     path: web::Path<i32>,
+    page: Page<HotelBookings>,
     user: UserInfo,
 ) -> Result<HttpResponse, AppError> {
     let hotel_id = path.into_inner();
     
     // Verificar ownership del hotel
     if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await.unwrap_or(false) {
-        return Err(AppError::Forbidden("No tienes permiso para ver las reservas de este hotel".to_string()));
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "booking.hotel_forbidden"));
     }
     
-    let bookings = sqlx::query_as!(
-        HotelBookingDetail,
+    let sql = format!(
         r#"
         SELECT b.id, b.check_in, b.check_out, b.guests,
-               b.total_price::numeric::float8 as "total_price!",
-               b.status as "status!", b.created_at as "created_at!",
-               u.first_name || ' ' || u.last_name as "customer_name!",
-               u.email as "customer_email!"
+               b.total_price::numeric::float8 as total_price,
+               b.status, COALESCE(b.created_at, 'epoch') as created_at,
+               u.first_name || ' ' || u.last_name as customer_name,
+               u.email as customer_email,
+               {}
         FROM bookings b 
         JOIN users u ON b.user_id = u.id
-        WHERE b.hotel_id = $1 
-        ORDER BY b.check_in DESC
+        WHERE b.hotel_id = $1{}
+        {}
         "#,
-        hotel_id
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let rows = page
+        .bind(sqlx::query(&sql).bind(hotel_id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (rows, next_cursor) = page.finish(rows);
+    let bookings = rows
+        .iter()
+        .map(HotelBookingDetail::from_row)
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    Ok(HttpResponse::Ok().json(page.response(bookings, next_cursor)))
+}
+
+// El propietario confirma una reserva pendiente; solo las confirmadas admiten el check-in
+pub async fn confirm_booking(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, booking_id) = path.into_inner();
+
+    if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "booking.hotel_forbidden"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let booking = sqlx::query(
+        r#"
+        UPDATE bookings b
+        SET status = 'confirmed', updated_at = NOW()
+        FROM hotels h
+        WHERE b.id = $1 AND b.hotel_id = $2 AND b.status = 'pending' AND h.id = b.hotel_id
+        RETURNING b.user_id, b.booking_reference, b.payment_status, h.name AS hotel_name
+        "#
+    )
+    .bind(booking_id)
+    .bind(hotel_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::CONFLICT, "booking.not_confirmable"))?;
+
+    let reference = booking.get::<String, _>("booking_reference");
+    let customer_id = booking.get::<i32, _>("user_id");
+    let locale = recipient_locale(&mut tx, customer_id).await?;
+    let args = [
+        ("hotel", booking.get::<String, _>("hotel_name")),
+        ("reference", reference.clone()),
+    ];
+    notify_user(
+        &mut tx,
+        customer_id,
+        "booking_confirmed",
+        &translate(locale, "notification.booking_confirmed.title", &args),
+        &translate(locale, "notification.booking_confirmed.message", &args),
+        json!({ "booking_id": booking_id, "hotel_id": hotel_id }),
     )
-    .fetch_all(pool.get_ref())
     .await?;
-    
-    Ok(HttpResponse::Ok().json(json!({ 
-        "bookings": bookings 
-    })))
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(BookingStateResponse {
+        message: t("booking.confirmed"),
+        booking: BookingState {
+            id: booking_id,
+            reference,
+            status: "confirmed".to_string(),
+            payment_status: booking.get::<String, _>("payment_status"),
+        },
+    }))
+}
+
+// El hotel registra el pago recibido (transferencia, efectivo o terminal propia), antes o después
+// de cerrar la estancia; es requisito para facturarla
+pub async fn mark_booking_paid(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, booking_id) = path.into_inner();
+
+    if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "booking.hotel_forbidden"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let booking = sqlx::query(
+        r#"
+        UPDATE bookings b
+        SET payment_status = 'paid', updated_at = NOW()
+        FROM hotels h
+        WHERE b.id = $1 AND b.hotel_id = $2 AND h.id = b.hotel_id
+          AND b.payment_status = 'pending' AND b.status != 'cancelled'
+        RETURNING b.user_id, b.booking_reference, b.status, h.name AS hotel_name
+        "#
+    )
+    .bind(booking_id)
+    .bind(hotel_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::CONFLICT, "booking.not_payable"))?;
+
+    let reference = booking.get::<String, _>("booking_reference");
+    let customer_id = booking.get::<i32, _>("user_id");
+    let locale = recipient_locale(&mut tx, customer_id).await?;
+    let args = [
+        ("hotel", booking.get::<String, _>("hotel_name")),
+        ("reference", reference.clone()),
+    ];
+    notify_user(
+        &mut tx,
+        customer_id,
+        "booking_paid",
+        &translate(locale, "notification.booking_paid.title", &args),
+        &translate(locale, "notification.booking_paid.message", &args),
+        json!({ "booking_id": booking_id, "hotel_id": hotel_id }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(BookingStateResponse {
+        message: t("booking.paid"),
+        booking: BookingState {
+            id: booking_id,
+            reference,
+            status: booking.get::<String, _>("status"),
+            payment_status: "paid".to_string(),
+        },
+    }))
+}
+
+// El hotel cierra la estancia de un huésped que ya registró su llegada
+pub async fn complete_booking(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, booking_id) = path.into_inner();
+
+    if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "booking.hotel_forbidden"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let booking = sqlx::query(
+        r#"
+        UPDATE bookings b
+        SET status = 'completed', updated_at = NOW()
+        FROM hotels h
+        WHERE b.id = $1 AND b.hotel_id = $2 AND b.status = 'checked_in' AND h.id = b.hotel_id
+        RETURNING b.user_id, b.booking_reference, b.payment_status, h.name AS hotel_name
+        "#
+    )
+    .bind(booking_id)
+    .bind(hotel_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::CONFLICT, "booking.not_completable"))?;
+
+    let reference = booking.get::<String, _>("booking_reference");
+    let customer_id = booking.get::<i32, _>("user_id");
+    let locale = recipient_locale(&mut tx, customer_id).await?;
+    let args = [
+        ("hotel", booking.get::<String, _>("hotel_name")),
+        ("reference", reference.clone()),
+    ];
+    notify_user(
+        &mut tx,
+        customer_id,
+        "booking_completed",
+        &translate(locale, "notification.booking_completed.title", &args),
+        &translate(locale, "notification.booking_completed.message", &args),
+        json!({ "booking_id": booking_id, "hotel_id": hotel_id }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(BookingStateResponse {
+        message: t("booking.completed"),
+        booking: BookingState {
+            id: booking_id,
+            reference,
+            status: "completed".to_string(),
+            payment_status: booking.get::<String, _>("payment_status"),
+        },
+    }))
 }
 
 // ✅ FUNCIÓN CORREGIDA para calcular precio de addons
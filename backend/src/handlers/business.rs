@@ -1,13 +1,91 @@
-use actix_web::{web, HttpResponse};
+use actix_web::{http::StatusCode, web, HttpResponse};
 use sqlx::{PgPool, Row};
 use crate::{
+    handlers::translation::{self, ListingKind},
+    middleware::locale::current_locale,
     models::{
-        UserInfo,
-        business::{CreateBusinessRequest, BusinessResponse, BusinessImage}
+        MessageResponse, UserInfo, UserRole,
+        business::{
+            BusinessCreatedResponse, BusinessResponse, CreateBusinessRequest, OwnerBusiness, PublicBusiness,
+            PublicBusinessDetail, PublicBusinessImage, PublicBusinessQuery,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        pagination::{Filter, Kind, Listing, Page, SortField},
     },
-    utils::errors::AppError,
 };
 
+// Zona horaria usada para evaluar el filtro `open_now` del directorio público
+pub(crate) const BUSINESS_TIMEZONE: &str = "America/Merida";
+
+// Directorio público de negocios (GET /businesses); primero los aprobados más recientemente
+pub struct PublicBusinesses;
+
+impl Listing for PublicBusinesses {
+    const KEY: &'static str = "b.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("approved_at", "COALESCE(b.approved_at, b.created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "b.name", Kind::Text),
+        SortField::new("rating", "COALESCE(b.rating, 0)", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "-approved_at";
+    const FILTERS: &'static [Filter] = &[];
+    const MAX_LIMIT: i64 = 50;
+}
+
+// Negocios del propietario (GET /businesses/my)
+pub struct MyBusinesses;
+
+impl Listing for MyBusinesses {
+    const KEY: &'static str = "id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "name", Kind::Text),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "status", Kind::Text),
+        Filter::any_of("business_type", "business_type", Kind::Text),
+    ];
+}
+
+// Filtros del listado público ($1..$5); los de la página se numeran a partir de $6
+const PUBLIC_BUSINESS_FILTER: &str = r#"
+    WHERE b.status = 'approved'
+      AND ($1::text IS NULL OR b.business_type = $1)
+      AND ($2::text IS NULL OR b.location ILIKE '%' || $2 || '%')
+      AND ($3::text IS NULL OR b.name ILIKE '%' || $3 || '%'
+                            OR b.description ILIKE '%' || $3 || '%'
+                            OR b.location ILIKE '%' || $3 || '%'
+                            OR EXISTS (SELECT 1 FROM listing_translations lt
+                                       WHERE lt.business_id = b.id
+                                         AND (lt.name ILIKE '%' || $3 || '%'
+                                              OR lt.description ILIKE '%' || $3 || '%')))
+      AND (NOT $4::boolean OR business_is_open(b.operating_hours, NOW() AT TIME ZONE $5::text))
+"#;
+
+pub async fn verify_business_ownership(
+    pool: &PgPool,
+    business_id: i32,
+    user: &UserInfo,
+) -> Result<bool, AppError> {
+    if matches!(user.role, UserRole::Admin) {
+        return Ok(true);
+    }
+
+    let result = sqlx::query("SELECT owner_id FROM businesses WHERE id = $1")
+        .bind(business_id)
+        .fetch_optional(pool)
+        .await?;
+
+    match result {
+        Some(business) => Ok(business.get::<i32, _>("owner_id") == user.id),
+        None => Ok(false),
+    }
+}
+
 pub async fn create_business(
     pool: web::Data<PgPool>,
     user: UserInfo,
@@ -16,17 +94,21 @@ pub async fn create_business(
     let req = req.into_inner();
     
     let business_data_json = serde_json::to_value(&req.business_data)
-        .map_err(|_| AppError::BadRequest("Error en datos del negocio".to_string()))?;
+        .map_err(|_| AppError::localized(StatusCode::BAD_REQUEST, "business.invalid_data"))?;
     
     let operating_hours_json = serde_json::to_value(&req.operating_hours)
-        .map_err(|_| AppError::BadRequest("Error en horarios".to_string()))?;
+        .map_err(|_| AppError::localized(StatusCode::BAD_REQUEST, "business.invalid_hours"))?;
+
+    // Sin idioma explícito se asume el de la petición
+    let content_locale = translation::requested_content_locale(req.content_locale.as_deref())?
+        .unwrap_or_else(current_locale);
 
     let result = sqlx::query(
         r#"
         INSERT INTO businesses (
             owner_id, business_type, name, description, location, address,
-            phone, email, website, status, business_data, operating_hours
-        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            phone, email, website, status, business_data, operating_hours, content_locale
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
         RETURNING id, business_type, name, location, status, created_at
         "#
     )
@@ -42,50 +124,55 @@ pub async fn create_business(
     .bind("pending")
     .bind(&business_data_json)
     .bind(&operating_hours_json)
+    .bind(content_locale.code())
     .fetch_one(pool.get_ref())
-    .await
-    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    Ok(HttpResponse::Created().json(serde_json::json!({
-        "message": "Negocio creado exitosamente",
-        "business": {
-            "id": result.get::<i32, _>("id"),
-            "name": result.get::<String, _>("name"),
-            "business_type": result.get::<String, _>("business_type"),
-            "location": result.get::<String, _>("location"),
-            "status": result.get::<String, _>("status"),
-            "created_at": result.get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-        }
-    })))
+    .await?;
+
+    Ok(HttpResponse::Created().json(BusinessCreatedResponse {
+        message: t("business.created"),
+        business: OwnerBusiness {
+            id: result.get::<i32, _>("id"),
+            name: result.get::<String, _>("name"),
+            business_type: result.get::<String, _>("business_type"),
+            location: result.get::<String, _>("location"),
+            status: result.get::<String, _>("status"),
+            created_at: result.get::<Option<chrono::DateTime<chrono::Utc>>, _>("created_at"),
+        },
+    }))
 }
 
 pub async fn get_my_businesses(
     pool: web::Data<PgPool>,
+    page: Page<MyBusinesses>,
     user: UserInfo,
 ) -> Result<HttpResponse, AppError> {
-    let businesses = sqlx::query(
+    let sql = format!(
         r#"
-        SELECT id, business_type, name, location, status, created_at
+        SELECT id, business_type, name, location, status, created_at, {}
         FROM businesses 
-        WHERE owner_id = $1
-        ORDER BY created_at DESC
-        "#
-    )
-    .bind(user.id)
-    .fetch_all(pool.get_ref())
-    .await
-    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    let business_list: Vec<serde_json::Value> = businesses.into_iter().map(|b| serde_json::json!({
-        "id": b.get::<i32, _>("id"),
-        "name": b.get::<String, _>("name"),
-        "business_type": b.get::<String, _>("business_type"),
-        "location": b.get::<String, _>("location"),
-        "status": b.get::<String, _>("status"),
-        "created_at": b.get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-    })).collect();
-
-    Ok(HttpResponse::Ok().json(business_list))
+        WHERE owner_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let businesses = page
+        .bind(sqlx::query(&sql).bind(user.id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (businesses, next_cursor) = page.finish(businesses);
+
+    let business_list: Vec<OwnerBusiness> = businesses.into_iter().map(|b| OwnerBusiness {
+        id: b.get::<i32, _>("id"),
+        name: b.get::<String, _>("name"),
+        business_type: b.get::<String, _>("business_type"),
+        location: b.get::<String, _>("location"),
+        status: b.get::<String, _>("status"),
+        created_at: b.get::<Option<chrono::DateTime<chrono::Utc>>, _>("created_at"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(business_list, next_cursor)))
 }
 
 pub async fn get_business_detail(
@@ -98,7 +185,7 @@ pub async fn get_business_detail(
     let business = sqlx::query(
         r#"
         SELECT id, business_type, name, description, location, address,
-               phone, email, website, status, business_data, operating_hours, created_at
+               phone, email, website, status, business_data, operating_hours, content_locale, created_at
         FROM businesses 
         WHERE id = $1 AND owner_id = $2
         "#
@@ -106,29 +193,29 @@ pub async fn get_business_detail(
     .bind(business_id)
     .bind(user.id)
     .fetch_optional(pool.get_ref())
-    .await
-    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    .await?;
 
     match business {
         Some(business) => {
-            Ok(HttpResponse::Ok().json(serde_json::json!({
-                "id": business.get::<i32, _>("id"),
-                "business_type": business.get::<String, _>("business_type"),
-                "name": business.get::<String, _>("name"),
-                "description": business.try_get::<Option<String>, _>("description").unwrap_or(None),
-                "location": business.get::<String, _>("location"),
-                "address": business.try_get::<Option<String>, _>("address").unwrap_or(None),
-                "phone": business.try_get::<Option<String>, _>("phone").unwrap_or(None),
-                "email": business.try_get::<Option<String>, _>("email").unwrap_or(None),
-                "website": business.try_get::<Option<String>, _>("website").unwrap_or(None),
-                "status": business.get::<String, _>("status"),
-                "business_data": business.get::<sqlx::types::JsonValue, _>("business_data"),
-                "operating_hours": business.get::<sqlx::types::JsonValue, _>("operating_hours"),
-                "images": Vec::<serde_json::Value>::new(),
-                "created_at": business.get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-            })))
+            Ok(HttpResponse::Ok().json(BusinessResponse {
+                id: business.get::<i32, _>("id"),
+                business_type: business.get::<String, _>("business_type"),
+                name: business.get::<String, _>("name"),
+                description: business.try_get::<Option<String>, _>("description").unwrap_or(None),
+                location: business.get::<String, _>("location"),
+                address: business.try_get::<Option<String>, _>("address").unwrap_or(None),
+                phone: business.try_get::<Option<String>, _>("phone").unwrap_or(None),
+                email: business.try_get::<Option<String>, _>("email").unwrap_or(None),
+                website: business.try_get::<Option<String>, _>("website").unwrap_or(None),
+                status: business.get::<String, _>("status"),
+                business_data: business.get::<sqlx::types::JsonValue, _>("business_data"),
+                operating_hours: business.get::<sqlx::types::JsonValue, _>("operating_hours"),
+                content_locale: business.get::<String, _>("content_locale"),
+                images: Vec::new(),
+                created_at: business.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+            }))
         }
-        None => Err(AppError::NotFound("Recurso no encontrado".to_string())),
+        None => Err(AppError::localized(StatusCode::NOT_FOUND, "not_found")),
     }
 }
 
@@ -140,12 +227,14 @@ pub async fn update_business(
 ) -> Result<HttpResponse, AppError> {
     let business_id = path.into_inner();
     let req = req.into_inner();
+    let content_locale = translation::requested_content_locale(req.content_locale.as_deref())?;
 
     let result = sqlx::query(
         r#"
         UPDATE businesses 
         SET name = $1, description = $2, location = $3, address = $4,
             phone = $5, email = $6, website = $7, business_type = $8,
+            content_locale = COALESCE($11, content_locale),
             updated_at = CURRENT_TIMESTAMP
         WHERE id = $9 AND owner_id = $10
         "#
@@ -160,15 +249,15 @@ pub async fn update_business(
     .bind(&req.business_type)
     .bind(business_id)
     .bind(user.id)
+    .bind(content_locale.map(|l| l.code()))
     .execute(pool.get_ref())
-    .await
-    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+    .await?;
 
     if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Recurso no encontrado".to_string()));
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "not_found"));
     }
 
-    Ok(HttpResponse::Ok().json(serde_json::json!({"message": "Negocio actualizado"})))
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("business.updated") }))
 }
 
 pub async fn delete_business(
@@ -182,12 +271,153 @@ pub async fn delete_business(
         .bind(business_id)
         .bind(user.id)
         .execute(pool.get_ref())
-        .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .await?;
 
     if result.rows_affected() == 0 {
-        return Err(AppError::NotFound("Recurso no encontrado".to_string()));
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "not_found"));
     }
 
-    Ok(HttpResponse::Ok().json(serde_json::json!({"message": "Negocio eliminado"})))
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("business.deleted") }))
+}
+
+
+pub async fn get_public_businesses(
+    pool: web::Data<PgPool>,
+    query: web::Query<PublicBusinessQuery>,
+    page: Page<PublicBusinesses>,
+) -> Result<HttpResponse, AppError> {
+    let query = query.into_inner();
+
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let business_type = non_empty(query.business_type);
+    let location = non_empty(query.location);
+    let text = non_empty(query.q);
+    let open_now = query.open_now.unwrap_or(false);
+
+    let sql = format!(
+        r#"
+        SELECT b.id, b.business_type, b.name, b.description, b.content_locale, b.location, b.address,
+               b.operating_hours, b.rating::float8 AS rating, b.review_count,
+               business_is_open(b.operating_hours, NOW() AT TIME ZONE $5::text) AS is_open_now,
+               (SELECT bi.image_url FROM business_images bi
+                WHERE bi.business_id = b.id
+                ORDER BY bi.display_order, bi.id
+                LIMIT 1) AS cover_image_url,
+               {}
+        FROM businesses b
+        {}{}
+        {}
+        "#,
+        page.columns(),
+        PUBLIC_BUSINESS_FILTER,
+        page.conditions(6),
+        page.order_and_limit()
+    );
+    let query = sqlx::query(&sql)
+        .bind(business_type.as_deref())
+        .bind(location.as_deref())
+        .bind(text.as_deref())
+        .bind(open_now)
+        .bind(BUSINESS_TIMEZONE);
+    let businesses = page.bind(query).fetch_all(pool.get_ref()).await?;
+    let (businesses, next_cursor) = page.finish(businesses);
+
+    let ids: Vec<i32> = businesses.iter().map(|b| b.get::<i32, _>("id")).collect();
+    let translations = translation::load_translations(pool.get_ref(), ListingKind::Business, &ids).await?;
+
+    let business_list: Vec<PublicBusiness> = businesses.into_iter().map(|b| {
+        let id = b.get::<i32, _>("id");
+        let content = translation::localize(
+            translation::source_locale(&b.get::<String, _>("content_locale")),
+            b.get::<String, _>("name"),
+            b.try_get::<Option<String>, _>("description").unwrap_or(None),
+            Vec::new(),
+            translations.get(&id).map(Vec::as_slice).unwrap_or_default(),
+        );
+        PublicBusiness {
+            id,
+            business_type: b.get::<String, _>("business_type"),
+            name: content.name,
+            description: content.description,
+            locale: content.locale.code().to_string(),
+            location: b.get::<String, _>("location"),
+            address: b.try_get::<Option<String>, _>("address").unwrap_or(None),
+            operating_hours: b.get::<sqlx::types::JsonValue, _>("operating_hours"),
+            is_open_now: b.try_get::<Option<bool>, _>("is_open_now").unwrap_or(None).unwrap_or(false),
+            rating: b.get::<f64, _>("rating"),
+            review_count: b.get::<i32, _>("review_count"),
+            image_url: b.try_get::<Option<String>, _>("cover_image_url").unwrap_or(None),
+        }
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(business_list, next_cursor)))
+}
+
+pub async fn get_public_business_detail(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    let business = sqlx::query(
+        r#"
+        SELECT id, business_type, name, description, content_locale, location, address,
+               phone, email, website, business_data, operating_hours,
+               rating::float8 AS rating, review_count,
+               business_is_open(operating_hours, NOW() AT TIME ZONE $2::text) AS is_open_now
+        FROM businesses
+        WHERE id = $1 AND status = 'approved'
+        "#
+    )
+    .bind(business_id)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "business.not_found"))?;
+
+    let images = sqlx::query(
+        r#"
+        SELECT image_url, image_type, display_order
+        FROM business_images
+        WHERE business_id = $1
+        ORDER BY display_order, id
+        "#
+    )
+    .bind(business_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let image_list: Vec<PublicBusinessImage> = images.into_iter().map(|i| PublicBusinessImage {
+        image_url: i.get::<String, _>("image_url"),
+        image_type: i.get::<String, _>("image_type"),
+        display_order: i.try_get::<Option<i32>, _>("display_order").unwrap_or(None),
+    }).collect();
+
+    let translations = translation::load_translations(pool.get_ref(), ListingKind::Business, &[business_id]).await?;
+    let content = translation::localize(
+        translation::source_locale(&business.get::<String, _>("content_locale")),
+        business.get::<String, _>("name"),
+        business.try_get::<Option<String>, _>("description").unwrap_or(None),
+        Vec::new(),
+        translations.get(&business_id).map(Vec::as_slice).unwrap_or_default(),
+    );
+
+    Ok(HttpResponse::Ok().json(PublicBusinessDetail {
+        id: business.get::<i32, _>("id"),
+        business_type: business.get::<String, _>("business_type"),
+        name: content.name,
+        description: content.description,
+        locale: content.locale.code().to_string(),
+        location: business.get::<String, _>("location"),
+        address: business.try_get::<Option<String>, _>("address").unwrap_or(None),
+        phone: business.try_get::<Option<String>, _>("phone").unwrap_or(None),
+        email: business.try_get::<Option<String>, _>("email").unwrap_or(None),
+        website: business.try_get::<Option<String>, _>("website").unwrap_or(None),
+        business_data: business.get::<sqlx::types::JsonValue, _>("business_data"),
+        operating_hours: business.get::<sqlx::types::JsonValue, _>("operating_hours"),
+        is_open_now: business.try_get::<Option<bool>, _>("is_open_now").unwrap_or(None).unwrap_or(false),
+        rating: business.get::<f64, _>("rating"),
+        review_count: business.get::<i32, _>("review_count"),
+        images: image_list,
+    }))
 }
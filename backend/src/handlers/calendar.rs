@@ -0,0 +1,756 @@
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use reqwest::{header::LOCATION, redirect::Policy, Url};
+use sqlx::{types::Json, PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{
+        business::BUSINESS_TIMEZONE,
+        hotel::verify_hotel_ownership,
+        notification::{notify_user, recipient_locale},
+    },
+    models::{
+        MessageResponse, UserInfo,
+        calendar::{
+            CalendarConflict, CalendarConflictBlock, CalendarConflictBooking, CalendarConflicts, CalendarExport,
+            CalendarExportResponse, CalendarFeed, CalendarFeedAddedResponse, CalendarFeedRequest, CalendarFeedResponse,
+            CalendarSync, CalendarUploadQuery,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::{t, t_args, translate},
+        ical::{self, Calendar, IcsEvent, IcsTime},
+    },
+};
+
+// Cada cuánto se vuelven a descargar los calendarios externos
+pub const CALENDAR_SYNC_INTERVAL_SECS: u64 = 900;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(20);
+const MAX_CALENDAR_BYTES: usize = 2 * 1024 * 1024;
+const MAX_REDIRECTS: usize = 3;
+const MAX_IMPORTED_EVENTS: usize = 2000;
+// El feed exportado incluye también las estancias recientes
+const EXPORT_PAST_DAYS: i64 = 30;
+
+// Noches en las que reservas y bloqueos importados superan las habitaciones del hotel
+const CALENDAR_CONFLICTS: &str = r#"
+    WITH nights AS (
+        SELECT DISTINCT generate_series(
+                   GREATEST(cb.start_date, CURRENT_DATE), cb.end_date - 1, INTERVAL '1 day'
+               )::date AS night
+        FROM hotel_calendar_blocks cb
+        JOIN hotel_calendar_feeds f ON cb.feed_id = f.id
+        WHERE cb.hotel_id = $1 AND f.is_active AND cb.end_date > CURRENT_DATE
+    ),
+    usage AS (
+        SELECT n.night,
+               (SELECT COALESCE(SUM(b.rooms), 0) FROM bookings b
+                WHERE b.hotel_id = $1 AND b.status != 'cancelled'
+                  AND b.check_in <= n.night AND b.check_out > n.night)::int AS booked_rooms,
+               (SELECT COUNT(*) FROM hotel_calendar_blocks cb
+                JOIN hotel_calendar_feeds f ON cb.feed_id = f.id
+                WHERE cb.hotel_id = $1 AND f.is_active
+                  AND cb.start_date <= n.night AND cb.end_date > n.night)::int AS blocked_rooms
+        FROM nights n
+    )
+    SELECT u.night, u.booked_rooms, u.blocked_rooms,
+           (SELECT COALESCE(rooms_available, 1) FROM hotels WHERE id = $1) AS rooms_available,
+           (SELECT jsonb_agg(jsonb_build_object(
+                       'id', b.id,
+                       'reference', b.booking_reference,
+                       'check_in', b.check_in,
+                       'check_out', b.check_out,
+                       'rooms', b.rooms
+                   ) ORDER BY b.check_in, b.id)
+            FROM bookings b
+            WHERE b.hotel_id = $1 AND b.status != 'cancelled'
+              AND b.check_in <= u.night AND b.check_out > u.night) AS bookings,
+           (SELECT jsonb_agg(jsonb_build_object(
+                       'id', cb.id,
+                       'feed', f.name,
+                       'summary', cb.summary,
+                       'start_date', cb.start_date,
+                       'end_date', cb.end_date
+                   ) ORDER BY cb.start_date, cb.id)
+            FROM hotel_calendar_blocks cb
+            JOIN hotel_calendar_feeds f ON cb.feed_id = f.id
+            WHERE cb.hotel_id = $1 AND f.is_active
+              AND cb.start_date <= u.night AND cb.end_date > u.night) AS blocks
+    FROM usage u
+    WHERE u.booked_rooms > 0
+      AND u.booked_rooms + u.blocked_rooms > (SELECT COALESCE(rooms_available, 1) FROM hotels WHERE id = $1)
+    ORDER BY u.night
+"#;
+
+async fn authorize_hotel_calendar(pool: &PgPool, hotel_id: i32, user: &UserInfo) -> Result<(), AppError> {
+    if !verify_hotel_ownership(pool, hotel_id, user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "calendar.hotel_forbidden"));
+    }
+
+    sqlx::query("SELECT id FROM hotels WHERE id = $1")
+        .bind(hotel_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "hotel.not_found"))?;
+
+    Ok(())
+}
+
+fn feed_path(token: &str) -> String {
+    format!("{}/calendar/hotels/{}.ics", crate::API_V1, token)
+}
+
+// webcal:// es el mismo recurso servido por https. Las direcciones IP escritas en la URL se
+// comprueban aquí; los nombres se resuelven y comprueban en cada descarga.
+fn normalize_feed_url(url: &str) -> Result<String, AppError> {
+    let url = url.trim();
+    let url = match url.strip_prefix("webcal://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    };
+
+    let parsed = Url::parse(&url)
+        .ok()
+        .filter(|u| matches!(u.scheme(), "http" | "https") && u.host_str().is_some())
+        .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "calendar.invalid_url"))?;
+    if host_ip(&parsed).is_some_and(|ip| !is_public_ip(ip)) {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.private_address"));
+    }
+    Ok(url)
+}
+
+// Dirección IP escrita directamente en la URL ("[::1]" en IPv6)
+fn host_ip(url: &Url) -> Option<IpAddr> {
+    url.host_str()?.trim_start_matches('[').trim_end_matches(']').parse().ok()
+}
+
+// Solo se descargan calendarios de internet: nada de loopback, redes privadas, link-local
+// (metadatos de la nube), CGNAT, multicast ni rangos reservados
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && ip.octets()[2] == 0)
+                || (a == 198 && (b == 18 || b == 19)))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(v4) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || first == 0x2001 && ip.segments()[1] == 0x0db8
+                || first == 0x0064 && ip.segments()[1] == 0xff9b)
+        }
+    }
+}
+
+// Resuelve el servidor del calendario y exige que todas sus direcciones sean públicas
+async fn resolve_public(url: &Url) -> Result<SocketAddr, AppError> {
+    let port = url.port_or_known_default().unwrap_or(443);
+    let addresses: Vec<SocketAddr> = match (host_ip(url), url.host_str()) {
+        (Some(ip), _) => vec![SocketAddr::new(ip, port)],
+        (None, Some(domain)) => tokio::net::lookup_host((domain, port))
+            .await
+            .map_err(|e| AppError::localized(StatusCode::BAD_REQUEST, "calendar.fetch_failed").arg("detail", e))?
+            .collect(),
+        (None, None) => Vec::new(),
+    };
+
+    match addresses.first() {
+        Some(address) if addresses.iter().all(|a| is_public_ip(a.ip())) => Ok(*address),
+        Some(_) => Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.private_address")),
+        None => Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.invalid_url")),
+    }
+}
+
+// Descarga el calendario sin seguir redirecciones a ciegas: cada salto se resuelve y comprueba de
+// nuevo, y la conexión usa la dirección ya comprobada para que una segunda resolución DNS no la
+// cambie. El cuerpo se lee por partes y se corta al pasar del límite.
+async fn fetch_calendar(url: &str) -> Result<String, AppError> {
+    let fetch_failed = |e: reqwest::Error| {
+        AppError::localized(StatusCode::BAD_REQUEST, "calendar.fetch_failed").arg("detail", e)
+    };
+    let too_large = || AppError::localized(StatusCode::BAD_REQUEST, "calendar.too_large");
+
+    let mut url = Url::parse(url).map_err(|_| AppError::localized(StatusCode::BAD_REQUEST, "calendar.invalid_url"))?;
+    for _ in 0..=MAX_REDIRECTS {
+        let address = resolve_public(&url).await?;
+        let mut client = reqwest::Client::builder().timeout(FETCH_TIMEOUT).redirect(Policy::none());
+        if let (None, Some(domain)) = (host_ip(&url), url.host_str()) {
+            client = client.resolve(domain, address);
+        }
+        let mut response = client.build().map_err(fetch_failed)?.get(url.clone()).send().await.map_err(fetch_failed)?;
+
+        if response.status().is_redirection() {
+            url = response
+                .headers()
+                .get(LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .and_then(|location| url.join(location).ok())
+                .filter(|next| matches!(next.scheme(), "http" | "https"))
+                .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "calendar.invalid_url"))?;
+            continue;
+        }
+        if !response.status().is_success() {
+            return Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.bad_status").arg("status", response.status()));
+        }
+        if matches!(response.content_length(), Some(len) if len as usize > MAX_CALENDAR_BYTES) {
+            return Err(too_large());
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(fetch_failed)? {
+            if body.len() + chunk.len() > MAX_CALENDAR_BYTES {
+                return Err(too_large());
+            }
+            body.extend_from_slice(&chunk);
+        }
+        return Ok(String::from_utf8_lossy(&body).into_owned());
+    }
+
+    Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.too_many_redirects"))
+}
+
+async fn record_sync_error(pool: &PgPool, feed_id: i32, error: &str) -> Result<(), AppError> {
+    sqlx::query(
+        "UPDATE hotel_calendar_feeds SET last_synced_at = NOW(), last_status = 'error', last_error = $2, updated_at = NOW() WHERE id = $1"
+    )
+    .bind(feed_id)
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+// Reemplaza los bloqueos del calendario por los eventos actuales (solo los que no han terminado)
+// y avisa al dueño si aparecen noches sobrevendidas. Devuelve cuántos bloqueos quedaron.
+async fn import_calendar(pool: &PgPool, feed_id: i32, content: &str) -> Result<usize, AppError> {
+    // Día y desfase actuales de la zona del negocio; las horas UTC de los eventos se pasan a esa
+    // zona antes de tomar la fecha
+    let clock = sqlx::query(
+        r#"
+        SELECT (NOW() AT TIME ZONE $1)::date AS today,
+               EXTRACT(EPOCH FROM (NOW() AT TIME ZONE $1) - (NOW() AT TIME ZONE 'UTC'))::int AS utc_offset
+        "#
+    )
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_one(pool)
+    .await?;
+    let offset = FixedOffset::east_opt(clock.get::<i32, _>("utc_offset"))
+        .ok_or_else(|| AppError::InternalServerError("Desfase horario inválido".to_string()))?;
+
+    let events = match ical::parse_events(content, offset) {
+        Ok(events) => events,
+        Err(_) => {
+            let error = AppError::localized(StatusCode::BAD_REQUEST, "calendar.not_icalendar");
+            record_sync_error(pool, feed_id, &error.public_message()).await?;
+            return Err(error);
+        }
+    };
+
+    let today = clock.get::<NaiveDate, _>("today");
+    let events: Vec<_> = events.into_iter().filter(|e| e.end > today).collect();
+    if events.len() > MAX_IMPORTED_EVENTS {
+        let error = AppError::localized(StatusCode::BAD_REQUEST, "calendar.too_many_events").arg("max", MAX_IMPORTED_EVENTS);
+        record_sync_error(pool, feed_id, &error.public_message()).await?;
+        return Err(error);
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let feed = sqlx::query(
+        r#"
+        SELECT f.hotel_id, f.name, f.last_conflict_nights, h.owner_id, h.name AS hotel_name
+        FROM hotel_calendar_feeds f
+        JOIN hotels h ON f.hotel_id = h.id
+        WHERE f.id = $1
+        FOR UPDATE OF f
+        "#
+    )
+    .bind(feed_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "calendar.not_found"))?;
+    let hotel_id = feed.get::<i32, _>("hotel_id");
+
+    sqlx::query("DELETE FROM hotel_calendar_blocks WHERE feed_id = $1")
+        .bind(feed_id)
+        .execute(&mut *tx)
+        .await?;
+
+    for event in &events {
+        sqlx::query(
+            r#"
+            INSERT INTO hotel_calendar_blocks (feed_id, hotel_id, external_uid, summary, start_date, end_date)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (feed_id, external_uid, start_date) DO NOTHING
+            "#
+        )
+        .bind(feed_id)
+        .bind(hotel_id)
+        .bind(&event.uid)
+        .bind(&event.summary)
+        .bind(event.start)
+        .bind(event.end)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let conflict_nights = sqlx::query(&format!("SELECT COUNT(*) AS total FROM ({}) c", CALENDAR_CONFLICTS))
+        .bind(hotel_id)
+        .fetch_one(&mut *tx)
+        .await?
+        .get::<i64, _>("total") as i32;
+
+    // Solo se avisa cuando el número de noches en conflicto aumenta, no en cada sincronización
+    if conflict_nights > feed.get::<i32, _>("last_conflict_nights") {
+        let owner_id = feed.get::<i32, _>("owner_id");
+        let locale = recipient_locale(&mut tx, owner_id).await?;
+        let args = [
+            ("hotel", feed.get::<String, _>("hotel_name")),
+            ("calendar", feed.get::<String, _>("name")),
+            ("nights", conflict_nights.to_string()),
+        ];
+        notify_user(
+            &mut tx,
+            owner_id,
+            "calendar_conflict",
+            &translate(locale, "notification.calendar_conflict.title", &args),
+            &translate(locale, "notification.calendar_conflict.message", &args),
+            serde_json::json!({
+                "hotel_id": hotel_id,
+                "feed_id": feed_id,
+                "conflict_nights": conflict_nights
+            }),
+        ).await?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE hotel_calendar_feeds
+        SET last_synced_at = NOW(), last_status = 'ok', last_error = NULL,
+            last_conflict_nights = $2, updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(feed_id)
+    .bind(conflict_nights)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(events.len())
+}
+
+async fn sync_feed(pool: &PgPool, feed_id: i32, url: &str) -> Result<usize, AppError> {
+    match fetch_calendar(url).await {
+        Ok(content) => import_calendar(pool, feed_id, &content).await,
+        Err(e) => {
+            record_sync_error(pool, feed_id, &e.public_message()).await?;
+            Err(e)
+        }
+    }
+}
+
+// Sincroniza los calendarios por URL que no se han actualizado en el último intervalo.
+// La ejecuta la tarea periódica de main; devuelve (sincronizados, con error).
+pub async fn sync_calendar_feeds(pool: &PgPool) -> Result<(u64, u64), AppError> {
+    let feeds = sqlx::query(
+        r#"
+        SELECT id, source_url
+        FROM hotel_calendar_feeds
+        WHERE is_active AND source_url IS NOT NULL
+          AND (last_synced_at IS NULL OR last_synced_at <= NOW() - make_interval(secs => $1))
+        ORDER BY last_synced_at NULLS FIRST
+        "#
+    )
+    .bind(CALENDAR_SYNC_INTERVAL_SECS as f64)
+    .fetch_all(pool)
+    .await?;
+
+    let (mut synced, mut failed) = (0, 0);
+    for feed in feeds {
+        let feed_id = feed.get::<i32, _>("id");
+        match sync_feed(pool, feed_id, &feed.get::<String, _>("source_url")).await {
+            Ok(_) => synced += 1,
+            Err(e) => {
+                log::warn!("Error al sincronizar el calendario {}: {}", feed_id, e);
+                failed += 1;
+            }
+        }
+    }
+
+    Ok((synced, failed))
+}
+
+// Feed público (protegido solo por el token) con las noches reservadas del hotel
+pub async fn get_hotel_ics_feed(
+    pool: web::Data<PgPool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let hotel = sqlx::query("SELECT id, name FROM hotels WHERE ical_token = $1")
+        .bind(path.into_inner())
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "calendar.not_found"))?;
+    let hotel_id = hotel.get::<i32, _>("id");
+
+    let bookings = sqlx::query(
+        r#"
+        SELECT id, check_in, check_out, rooms, status
+        FROM bookings
+        WHERE hotel_id = $1 AND status != 'cancelled' AND check_out >= $2
+        ORDER BY check_in, id
+        "#
+    )
+    .bind(hotel_id)
+    .bind(Utc::now().date_naive() - chrono::Duration::days(EXPORT_PAST_DAYS))
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    // Sin datos del huésped: el feed lo consumen plataformas externas
+    let mut calendar = Calendar::new(&hotel.get::<String, _>("name"));
+    for b in &bookings {
+        let rooms = b.get::<i32, _>("rooms");
+        calendar.add_event(&IcsEvent {
+            uid: format!("booking-{}@mayadigital", b.get::<i32, _>("id")),
+            summary: if rooms > 1 { t_args("calendar.booked_rooms", &[("rooms", rooms.to_string())]) } else { t("calendar.booked") },
+            description: None,
+            location: None,
+            start: IcsTime::Date(b.get::<NaiveDate, _>("check_in")),
+            end: IcsTime::Date(b.get::<NaiveDate, _>("check_out")),
+            confirmed: b.get::<String, _>("status") != "pending",
+        });
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .body(calendar.finish()))
+}
+
+fn feed_from_row(f: &sqlx::postgres::PgRow) -> CalendarFeed {
+    let url = f.try_get::<Option<String>, _>("source_url").unwrap_or(None);
+    CalendarFeed {
+        id: f.get::<i32, _>("id"),
+        name: f.get::<String, _>("name"),
+        source: if url.is_some() { "url" } else { "upload" }.to_string(),
+        url,
+        is_active: f.get::<bool, _>("is_active"),
+        blocked_events: f.get::<i64, _>("blocked_events"),
+        last_synced_at: f.try_get::<Option<DateTime<Utc>>, _>("last_synced_at").unwrap_or(None),
+        last_status: f.try_get::<Option<String>, _>("last_status").unwrap_or(None),
+        last_error: f.try_get::<Option<String>, _>("last_error").unwrap_or(None),
+        created_at: f.get::<DateTime<Utc>, _>("created_at"),
+    }
+}
+
+async fn load_feed(pool: &PgPool, hotel_id: i32, feed_id: i32) -> Result<sqlx::postgres::PgRow, AppError> {
+    sqlx::query(
+        r#"
+        SELECT f.id, f.name, f.source_url, f.is_active, f.last_synced_at, f.last_status, f.last_error, f.created_at,
+               (SELECT COUNT(*) FROM hotel_calendar_blocks cb WHERE cb.feed_id = f.id) AS blocked_events
+        FROM hotel_calendar_feeds f
+        WHERE f.id = $1 AND f.hotel_id = $2
+        "#
+    )
+    .bind(feed_id)
+    .bind(hotel_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "calendar.not_found"))
+}
+
+async fn load_conflicts(pool: &PgPool, hotel_id: i32) -> Result<Vec<CalendarConflict>, AppError> {
+    let conflicts = sqlx::query(CALENDAR_CONFLICTS)
+        .bind(hotel_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(conflicts.into_iter().map(|c| CalendarConflict {
+        night: c.get::<NaiveDate, _>("night"),
+        rooms_available: c.get::<i32, _>("rooms_available"),
+        booked_rooms: c.get::<i32, _>("booked_rooms"),
+        blocked_rooms: c.get::<i32, _>("blocked_rooms"),
+        bookings: c
+            .try_get::<Option<Json<Vec<CalendarConflictBooking>>>, _>("bookings")
+            .unwrap_or(None)
+            .map_or_else(Vec::new, |bookings| bookings.0),
+        blocks: c
+            .try_get::<Option<Json<Vec<CalendarConflictBlock>>>, _>("blocks")
+            .unwrap_or(None)
+            .map_or_else(Vec::new, |blocks| blocks.0),
+    }).collect())
+}
+
+// URL del feed de exportación (se genera la primera vez), calendarios importados y conflictos
+pub async fn get_hotel_calendar_sync(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    let pool = pool.get_ref();
+    authorize_hotel_calendar(pool, hotel_id, &user).await?;
+
+    let token = sqlx::query(
+        "UPDATE hotels SET ical_token = COALESCE(ical_token, $2) WHERE id = $1 RETURNING ical_token"
+    )
+    .bind(hotel_id)
+    .bind(uuid::Uuid::new_v4().simple().to_string())
+    .fetch_one(pool)
+    .await?
+    .get::<String, _>("ical_token");
+
+    let feeds = sqlx::query(
+        r#"
+        SELECT f.id, f.name, f.source_url, f.is_active, f.last_synced_at, f.last_status, f.last_error, f.created_at,
+               (SELECT COUNT(*) FROM hotel_calendar_blocks cb WHERE cb.feed_id = f.id) AS blocked_events
+        FROM hotel_calendar_feeds f
+        WHERE f.hotel_id = $1
+        ORDER BY f.created_at, f.id
+        "#
+    )
+    .bind(hotel_id)
+    .fetch_all(pool)
+    .await?;
+
+    let conflicts = load_conflicts(pool, hotel_id).await?;
+
+    Ok(HttpResponse::Ok().json(CalendarSync {
+        export: CalendarExport { path: feed_path(&token), token },
+        imports: feeds.iter().map(feed_from_row).collect(),
+        conflicts,
+    }))
+}
+
+// Genera un token nuevo; el enlace anterior deja de funcionar
+pub async fn rotate_hotel_calendar_token(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    authorize_hotel_calendar(pool.get_ref(), hotel_id, &user).await?;
+
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    sqlx::query("UPDATE hotels SET ical_token = $2 WHERE id = $1")
+        .bind(hotel_id)
+        .bind(&token)
+        .execute(pool.get_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(CalendarExportResponse {
+        message: t("calendar.export_regenerated"),
+        export: CalendarExport { path: feed_path(&token), token },
+    }))
+}
+
+pub async fn get_hotel_calendar_conflicts(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    authorize_hotel_calendar(pool.get_ref(), hotel_id, &user).await?;
+
+    let conflicts = load_conflicts(pool.get_ref(), hotel_id).await?;
+
+    Ok(HttpResponse::Ok().json(CalendarConflicts { hotel_id, conflicts }))
+}
+
+fn map_feed_name_conflict(e: sqlx::Error) -> AppError {
+    match e {
+        sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+            AppError::localized(StatusCode::CONFLICT, "calendar.name_taken")
+        }
+        other => AppError::from(other),
+    }
+}
+
+// Registra un calendario externo por URL y lo sincroniza de inmediato
+pub async fn add_hotel_calendar_feed(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<CalendarFeedRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    let pool = pool.get_ref();
+    authorize_hotel_calendar(pool, hotel_id, &user).await?;
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+    let url = normalize_feed_url(&req.url)?;
+
+    let feed_id = sqlx::query(
+        "INSERT INTO hotel_calendar_feeds (hotel_id, name, source_url) VALUES ($1, $2, $3) RETURNING id"
+    )
+    .bind(hotel_id)
+    .bind(req.name.trim())
+    .bind(&url)
+    .fetch_one(pool)
+    .await
+    .map_err(map_feed_name_conflict)?
+    .get::<i32, _>("id");
+
+    // Un fallo en la primera descarga no impide registrar el calendario; queda en last_error
+    let sync_error = sync_feed(pool, feed_id, &url).await.err().map(|e| e.public_message());
+
+    Ok(HttpResponse::Created().json(CalendarFeedAddedResponse {
+        message: t("calendar.added"),
+        sync_error,
+        feed: feed_from_row(&load_feed(pool, hotel_id, feed_id).await?),
+        conflicts: load_conflicts(pool, hotel_id).await?,
+    }))
+}
+
+// Importa un archivo .ics (cuerpo de la petición). Subir otra vez con el mismo nombre reemplaza los bloqueos.
+pub async fn upload_hotel_calendar(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    query: web::Query<CalendarUploadQuery>,
+    body: String,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    let pool = pool.get_ref();
+    authorize_hotel_calendar(pool, hotel_id, &user).await?;
+
+    if let Err(errors) = query.validate() {
+        return Err(AppError::Validation(errors));
+    }
+    if body.len() > MAX_CALENDAR_BYTES {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.too_large"));
+    }
+
+    let feed = sqlx::query(
+        r#"
+        INSERT INTO hotel_calendar_feeds (hotel_id, name) VALUES ($1, $2)
+        ON CONFLICT (hotel_id, name) DO UPDATE SET updated_at = NOW()
+        RETURNING id, source_url
+        "#
+    )
+    .bind(hotel_id)
+    .bind(query.name.trim())
+    .fetch_one(pool)
+    .await?;
+
+    if feed.try_get::<Option<String>, _>("source_url").unwrap_or(None).is_some() {
+        return Err(AppError::localized(StatusCode::CONFLICT, "calendar.name_is_url_feed"));
+    }
+
+    let feed_id = feed.get::<i32, _>("id");
+    let imported = import_calendar(pool, feed_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(CalendarFeedResponse {
+        message: t_args("calendar.imported", &[("count", imported.to_string())]),
+        feed: feed_from_row(&load_feed(pool, hotel_id, feed_id).await?),
+        conflicts: load_conflicts(pool, hotel_id).await?,
+    }))
+}
+
+pub async fn sync_hotel_calendar_feed(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, feed_id) = path.into_inner();
+    let pool = pool.get_ref();
+    authorize_hotel_calendar(pool, hotel_id, &user).await?;
+
+    let feed = load_feed(pool, hotel_id, feed_id).await?;
+    let Some(url) = feed.try_get::<Option<String>, _>("source_url").unwrap_or(None) else {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "calendar.upload_not_syncable"));
+    };
+
+    let imported = sync_feed(pool, feed_id, &url).await?;
+
+    Ok(HttpResponse::Ok().json(CalendarFeedResponse {
+        message: t_args("calendar.imported", &[("count", imported.to_string())]),
+        feed: feed_from_row(&load_feed(pool, hotel_id, feed_id).await?),
+        conflicts: load_conflicts(pool, hotel_id).await?,
+    }))
+}
+
+// Elimina el calendario y libera las noches que bloqueaba
+pub async fn delete_hotel_calendar_feed(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, feed_id) = path.into_inner();
+    authorize_hotel_calendar(pool.get_ref(), hotel_id, &user).await?;
+
+    let result = sqlx::query("DELETE FROM hotel_calendar_feeds WHERE id = $1 AND hotel_id = $2")
+        .bind(feed_id)
+        .bind(hotel_id)
+        .execute(pool.get_ref())
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "calendar.not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("calendar.deleted") }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solo_las_direcciones_publicas_se_pueden_descargar() {
+        for ip in ["93.184.216.34", "2606:4700::6810:84e5", "8.8.8.8"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1", "10.1.2.3", "172.16.0.1", "192.168.1.1", "169.254.169.254", "100.64.0.1",
+            "0.0.0.0", "255.255.255.255", "::1", "::", "fc00::1", "fe80::1", "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+    }
+
+    #[test]
+    fn la_url_del_calendario_se_normaliza_y_valida() {
+        assert_eq!(
+            normalize_feed_url(" webcal://calendar.example.com/a.ics ").unwrap(),
+            "https://calendar.example.com/a.ics"
+        );
+        for url in ["ftp://calendar.example.com/a.ics", "https://", "file:///etc/passwd"] {
+            assert_eq!(normalize_feed_url(url).unwrap_err().code(), "calendar.invalid_url", "{}", url);
+        }
+        for url in ["http://127.0.0.1:8080/a.ics", "http://169.254.169.254/latest", "https://[::1]/a.ics"] {
+            assert_eq!(normalize_feed_url(url).unwrap_err().code(), "calendar.private_address", "{}", url);
+        }
+    }
+
+    #[actix_web::test]
+    async fn un_nombre_que_resuelve_a_loopback_no_se_descarga() {
+        let error = fetch_calendar("http://localhost:9/a.ics").await.unwrap_err();
+        assert_eq!(error.code(), "calendar.private_address");
+    }
+}
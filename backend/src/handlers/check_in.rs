@@ -0,0 +1,304 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    config::{AuthSettings, Settings},
+    handlers::{
+        business::{verify_business_ownership, BUSINESS_TIMEZONE},
+        hotel::verify_hotel_ownership,
+    },
+    models::{
+        check_in::{CheckInRequest, CheckInResponse, CheckInTokenResponse, CheckedInBooking, HotelCheckIn, TransportCheckIn},
+        UserInfo,
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        jwt::{create_check_in_token, verify_check_in_token},
+    },
+};
+
+const KIND_HOTEL: &str = "hotel";
+const KIND_TRANSPORT: &str = "transport";
+
+// Margen para abordar un traslado antes de la hora de salida
+const TRANSPORT_BOARDING_HOURS: i64 = 12;
+
+// La estancia termina a la medianoche del día de salida
+pub(crate) fn hotel_check_in_token(auth: &AuthSettings, booking_id: i32, reference: &str, check_out: NaiveDate) -> Result<String, AppError> {
+    let ends_at = check_out.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    create_check_in_token(auth, KIND_HOTEL, booking_id, reference, ends_at)
+        .map_err(|e| AppError::InternalServerError(format!("No se pudo generar el código de check-in: {}", e)))
+}
+
+fn transport_check_in_token(auth: &AuthSettings, booking_id: i32, reference: &str, arrival_at: DateTime<Utc>) -> Result<String, AppError> {
+    create_check_in_token(auth, KIND_TRANSPORT, booking_id, reference, arrival_at)
+        .map_err(|e| AppError::InternalServerError(format!("No se pudo generar el código de check-in: {}", e)))
+}
+
+// Código que el cliente muestra como QR al llegar (también va impreso en el comprobante PDF)
+pub async fn get_booking_check_in_token(
+    pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+
+    let booking = sqlx::query(
+        "SELECT booking_reference, status, check_out FROM bookings WHERE id = $1 AND user_id = $2"
+    )
+    .bind(booking_id)
+    .bind(user.id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "booking.not_found"))?;
+
+    let status = booking.get::<String, _>("status");
+    if status == "cancelled" {
+        return Err(AppError::localized(StatusCode::CONFLICT, "check_in.cancelled"));
+    }
+
+    let booking_reference = booking.get::<String, _>("booking_reference");
+    let token = hotel_check_in_token(&settings.auth, booking_id, &booking_reference, booking.get::<NaiveDate, _>("check_out"))?;
+
+    Ok(HttpResponse::Ok().json(CheckInTokenResponse { token, booking_reference, status }))
+}
+
+pub async fn get_transport_check_in_token(
+    pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+
+    let booking = sqlx::query(
+        r#"
+        SELECT tb.booking_reference, tb.status, t.arrival_at
+        FROM transport_bookings tb
+        JOIN transport_trips t ON tb.trip_id = t.id
+        WHERE tb.id = $1 AND tb.user_id = $2
+        "#
+    )
+    .bind(booking_id)
+    .bind(user.id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "check_in.transport_not_found"))?;
+
+    let status = booking.get::<String, _>("status");
+    if status == "cancelled" {
+        return Err(AppError::localized(StatusCode::CONFLICT, "check_in.cancelled"));
+    }
+
+    let booking_reference = booking.get::<String, _>("booking_reference");
+    let token = transport_check_in_token(&settings.auth, booking_id, &booking_reference, booking.get::<DateTime<Utc>, _>("arrival_at"))?;
+
+    Ok(HttpResponse::Ok().json(CheckInTokenResponse { token, booking_reference, status }))
+}
+
+// Motivo por el que una reserva no admite el registro de llegada
+fn check_in_blocked(status: &str, checked_in_at: Option<DateTime<Utc>>) -> Option<AppError> {
+    if let Some(at) = checked_in_at {
+        return Some(AppError::localized(StatusCode::CONFLICT, "check_in.already_used_at")
+            .arg("date", at.format("%d/%m/%Y %H:%M UTC")));
+    }
+
+    match status {
+        "confirmed" => None,
+        "pending" => Some(AppError::localized(StatusCode::CONFLICT, "check_in.not_confirmed")),
+        "cancelled" => Some(AppError::localized(StatusCode::CONFLICT, "check_in.cancelled")),
+        _ => Some(AppError::localized(StatusCode::CONFLICT, "check_in.not_allowed")),
+    }
+}
+
+async fn check_in_hotel_booking(
+    pool: &PgPool,
+    booking_id: i32,
+    reference: &str,
+    user: &UserInfo,
+) -> Result<HotelCheckIn, AppError> {
+    let booking = sqlx::query(
+        r#"
+        SELECT b.hotel_id, b.booking_reference, b.status, b.checked_in_at,
+               b.check_in, b.check_out, b.guests, b.rooms, b.special_requests,
+               (NOW() AT TIME ZONE $2)::date AS today,
+               h.name AS hotel_name,
+               u.first_name || ' ' || u.last_name AS customer_name, u.phone AS customer_phone
+        FROM bookings b
+        JOIN hotels h ON b.hotel_id = h.id
+        JOIN users u ON b.user_id = u.id
+        WHERE b.id = $1
+        "#
+    )
+    .bind(booking_id)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "booking.not_found"))?;
+
+    // Un código firmado de otra reserva (p. ej. tras recrear los datos) no debe valer
+    if booking.get::<String, _>("booking_reference") != reference {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "check_in.invalid_code"));
+    }
+
+    if !verify_hotel_ownership(pool, booking.get::<i32, _>("hotel_id"), user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "check_in.hotel_forbidden"));
+    }
+
+    let status = booking.get::<String, _>("status");
+    let checked_in_at = booking.try_get::<Option<DateTime<Utc>>, _>("checked_in_at").unwrap_or(None);
+    if let Some(error) = check_in_blocked(&status, checked_in_at) {
+        return Err(error);
+    }
+
+    let today = booking.get::<NaiveDate, _>("today");
+    let check_in = booking.get::<NaiveDate, _>("check_in");
+    let check_out = booking.get::<NaiveDate, _>("check_out");
+    if today < check_in {
+        return Err(AppError::localized(StatusCode::CONFLICT, "check_in.too_early").arg("date", check_in.format("%d/%m/%Y")));
+    }
+    if today >= check_out {
+        return Err(AppError::localized(StatusCode::CONFLICT, "check_in.stay_ended"));
+    }
+
+    // La condición sobre checked_in_at evita que dos lecturas simultáneas registren la llegada
+    let updated = sqlx::query(
+        r#"
+        UPDATE bookings
+        SET status = 'checked_in', checked_in_at = NOW(), checked_in_by = $2, updated_at = NOW()
+        WHERE id = $1 AND status = 'confirmed' AND checked_in_at IS NULL
+        RETURNING checked_in_at
+        "#
+    )
+    .bind(booking_id)
+    .bind(user.id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::CONFLICT, "check_in.already_used"))?;
+
+    Ok(HotelCheckIn {
+        booking_id,
+        booking_reference: reference.to_string(),
+        hotel_id: booking.get::<i32, _>("hotel_id"),
+        hotel_name: booking.get::<String, _>("hotel_name"),
+        customer_name: booking.try_get::<Option<String>, _>("customer_name").unwrap_or(None),
+        customer_phone: booking.try_get::<Option<String>, _>("customer_phone").unwrap_or(None),
+        check_in,
+        check_out,
+        guests: booking.get::<i32, _>("guests"),
+        rooms: booking.get::<i32, _>("rooms"),
+        special_requests: booking.try_get::<Option<String>, _>("special_requests").unwrap_or(None),
+        checked_in_at: updated.get::<DateTime<Utc>, _>("checked_in_at"),
+    })
+}
+
+async fn check_in_transport_booking(
+    pool: &PgPool,
+    booking_id: i32,
+    reference: &str,
+    user: &UserInfo,
+) -> Result<TransportCheckIn, AppError> {
+    let booking = sqlx::query(
+        r#"
+        SELECT r.business_id, tb.booking_reference, tb.status, tb.checked_in_at,
+               tb.seats, tb.passenger_name, tb.flight_number, tb.special_requests,
+               t.id AS trip_id, t.departure_at, t.arrival_at,
+               r.origin, r.destination,
+               u.first_name || ' ' || u.last_name AS customer_name, u.phone AS customer_phone
+        FROM transport_bookings tb
+        JOIN transport_trips t ON tb.trip_id = t.id
+        JOIN transport_routes r ON t.route_id = r.id
+        JOIN users u ON tb.user_id = u.id
+        WHERE tb.id = $1
+        "#
+    )
+    .bind(booking_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "check_in.transport_not_found"))?;
+
+    if booking.get::<String, _>("booking_reference") != reference {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "check_in.invalid_code"));
+    }
+
+    if !verify_business_ownership(pool, booking.get::<i32, _>("business_id"), user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "check_in.business_forbidden"));
+    }
+
+    let status = booking.get::<String, _>("status");
+    let checked_in_at = booking.try_get::<Option<DateTime<Utc>>, _>("checked_in_at").unwrap_or(None);
+    if let Some(error) = check_in_blocked(&status, checked_in_at) {
+        return Err(error);
+    }
+
+    let departure_at = booking.get::<DateTime<Utc>, _>("departure_at");
+    let arrival_at = booking.get::<DateTime<Utc>, _>("arrival_at");
+    let now = Utc::now();
+    if now < departure_at - Duration::hours(TRANSPORT_BOARDING_HOURS) {
+        return Err(AppError::localized(StatusCode::CONFLICT, "check_in.boarding_not_open"));
+    }
+    if now > arrival_at {
+        return Err(AppError::localized(StatusCode::CONFLICT, "check_in.trip_ended"));
+    }
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE transport_bookings
+        SET status = 'checked_in', checked_in_at = NOW(), checked_in_by = $2, updated_at = NOW()
+        WHERE id = $1 AND status = 'confirmed' AND checked_in_at IS NULL
+        RETURNING checked_in_at
+        "#
+    )
+    .bind(booking_id)
+    .bind(user.id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::CONFLICT, "check_in.already_used"))?;
+
+    Ok(TransportCheckIn {
+        booking_id,
+        booking_reference: reference.to_string(),
+        business_id: booking.get::<i32, _>("business_id"),
+        trip_id: booking.get::<i32, _>("trip_id"),
+        origin: booking.get::<String, _>("origin"),
+        destination: booking.get::<String, _>("destination"),
+        departure_at,
+        passenger_name: booking.try_get::<Option<String>, _>("passenger_name").unwrap_or(None),
+        customer_name: booking.try_get::<Option<String>, _>("customer_name").unwrap_or(None),
+        customer_phone: booking.try_get::<Option<String>, _>("customer_phone").unwrap_or(None),
+        seats: booking.get::<i32, _>("seats"),
+        flight_number: booking.try_get::<Option<String>, _>("flight_number").unwrap_or(None),
+        special_requests: booking.try_get::<Option<String>, _>("special_requests").unwrap_or(None),
+        checked_in_at: updated.get::<DateTime<Utc>, _>("checked_in_at"),
+    })
+}
+
+// El dueño escanea el QR del cliente: se verifica la firma, que la reserva sea de uno de sus
+// negocios y que no se haya usado antes
+pub async fn check_in_booking(
+    pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
+    req: web::Json<CheckInRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let claims = verify_check_in_token(&settings.auth, req.token.trim())
+        .map_err(|_| AppError::localized(StatusCode::BAD_REQUEST, "check_in.invalid_or_expired"))?;
+
+    let booking = match claims.kind.as_str() {
+        KIND_HOTEL => CheckedInBooking::Hotel(check_in_hotel_booking(pool.get_ref(), claims.booking_id, &claims.reference, &user).await?),
+        KIND_TRANSPORT => CheckedInBooking::Transport(check_in_transport_booking(pool.get_ref(), claims.booking_id, &claims.reference, &user).await?),
+        _ => return Err(AppError::localized(StatusCode::BAD_REQUEST, "check_in.invalid_code")),
+    };
+
+    Ok(HttpResponse::Ok().json(CheckInResponse {
+        message: t("check_in.registered"),
+        booking,
+    }))
+}
@@ -0,0 +1,441 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use sqlx::{PgPool, Row};
+use crate::{
+    config::{AuthSettings, Settings},
+    handlers::{check_in::hotel_check_in_token, hotel::verify_hotel_ownership},
+    models::UserInfo,
+    utils::{
+        errors::AppError,
+        pdf::{Document, Font, Page, PAGE_HEIGHT, PAGE_WIDTH},
+    },
+};
+
+const DEFAULT_IVA_RATE: f64 = 0.16;
+const DEFAULT_LODGING_TAX_RATE: f64 = 0.05;
+
+const MARGIN: f32 = 50.0;
+const RIGHT: f32 = PAGE_WIDTH - MARGIN;
+
+struct BookingDocument {
+    id: i32,
+    reference: String,
+    status: String,
+    payment_status: String,
+    check_in: NaiveDate,
+    check_out: NaiveDate,
+    guests: i32,
+    rooms: i32,
+    total_price: f64,
+    addon_services: Option<serde_json::Value>,
+    special_requests: Option<String>,
+    created_at: DateTime<Utc>,
+    hotel_name: String,
+    hotel_address: String,
+    hotel_location: String,
+    hotel_phone: Option<String>,
+    hotel_email: Option<String>,
+    check_in_time: Option<NaiveTime>,
+    check_out_time: Option<NaiveTime>,
+    customer_name: String,
+    customer_email: String,
+}
+
+impl BookingDocument {
+    fn nights(&self) -> i64 {
+        (self.check_out - self.check_in).num_days().max(1)
+    }
+
+    // Desglose con impuestos incluidos: hospedaje (total menos servicios adicionales) y cada servicio
+    fn price_lines(&self) -> Vec<(String, f64)> {
+        let addons: Vec<(String, f64)> = self.addon_services
+            .as_ref()
+            .and_then(|a| a.as_array())
+            .map(|items| items.iter().filter_map(|addon| {
+                let price = addon.get("price").and_then(|p| p.as_f64())?;
+                let name = addon.get("name").and_then(|n| n.as_str()).unwrap_or("Servicio adicional");
+                Some((name.to_string(), price))
+            }).collect())
+            .unwrap_or_default();
+
+        let addon_total: f64 = addons.iter().map(|(_, price)| price).sum();
+        let lodging = self.total_price - addon_total;
+        let nightly = lodging / (self.nights() * self.rooms.max(1) as i64) as f64;
+
+        let mut lines = vec![(
+            format!(
+                "Hospedaje: {} noche(s) x {} habitación(es) x {}",
+                self.nights(), self.rooms, money(nightly)
+            ),
+            lodging,
+        )];
+        lines.extend(addons);
+        lines
+    }
+}
+
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+// $1,234.50 MXN
+pub(crate) fn money(value: f64) -> String {
+    let cents = (value.abs() * 100.0).round() as i64;
+    let digits = (cents / 100).to_string();
+    let grouped: Vec<&str> = digits
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    format!("{}${}.{:02} MXN", if value < 0.0 { "-" } else { "" }, grouped.join(","), cents % 100)
+}
+
+fn status_label(status: &str) -> &str {
+    match status {
+        "pending" => "Pendiente",
+        "confirmed" => "Confirmada",
+        "checked_in" => "Huésped registrado",
+        "completed" => "Completada",
+        "cancelled" => "Cancelada",
+        other => other,
+    }
+}
+
+fn payment_label(status: &str) -> &str {
+    match status {
+        "pending" => "Pendiente",
+        "paid" => "Pagado",
+        "refunded" => "Reembolsado",
+        "failed" => "Fallido",
+        other => other,
+    }
+}
+
+// El cliente de la reserva, el dueño del hotel o un administrador
+async fn load_booking_document(
+    pool: &PgPool,
+    booking_id: i32,
+    user: &UserInfo,
+) -> Result<BookingDocument, AppError> {
+    let b = sqlx::query(
+        r#"
+        SELECT b.id, b.user_id, b.hotel_id, b.booking_reference, b.status, b.payment_status,
+               b.check_in, b.check_out, b.guests, b.rooms, b.total_price::float8 AS total_price,
+               b.addon_services, b.special_requests, b.created_at,
+               h.name AS hotel_name, h.address AS hotel_address, h.location AS hotel_location,
+               h.phone AS hotel_phone, h.email AS hotel_email, h.check_in_time, h.check_out_time,
+               u.first_name || ' ' || u.last_name AS customer_name, u.email AS customer_email
+        FROM bookings b
+        JOIN hotels h ON b.hotel_id = h.id
+        JOIN users u ON b.user_id = u.id
+        WHERE b.id = $1
+        "#
+    )
+    .bind(booking_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "booking.not_found"))?;
+
+    if b.get::<i32, _>("user_id") != user.id && !verify_hotel_ownership(pool, b.get::<i32, _>("hotel_id"), user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "document.forbidden"));
+    }
+
+    Ok(BookingDocument {
+        id: b.get::<i32, _>("id"),
+        reference: b.get::<String, _>("booking_reference"),
+        status: b.get::<String, _>("status"),
+        payment_status: b.get::<String, _>("payment_status"),
+        check_in: b.get::<NaiveDate, _>("check_in"),
+        check_out: b.get::<NaiveDate, _>("check_out"),
+        guests: b.get::<i32, _>("guests"),
+        rooms: b.get::<i32, _>("rooms"),
+        total_price: b.get::<f64, _>("total_price"),
+        addon_services: b.try_get::<Option<serde_json::Value>, _>("addon_services").unwrap_or(None),
+        special_requests: b.try_get::<Option<String>, _>("special_requests").unwrap_or(None),
+        created_at: b.try_get::<Option<DateTime<Utc>>, _>("created_at").unwrap_or(None).unwrap_or_else(Utc::now),
+        hotel_name: b.get::<String, _>("hotel_name"),
+        hotel_address: b.get::<String, _>("hotel_address"),
+        hotel_location: b.get::<String, _>("hotel_location"),
+        hotel_phone: b.try_get::<Option<String>, _>("hotel_phone").unwrap_or(None),
+        hotel_email: b.try_get::<Option<String>, _>("hotel_email").unwrap_or(None),
+        check_in_time: b.try_get::<Option<NaiveTime>, _>("check_in_time").unwrap_or(None),
+        check_out_time: b.try_get::<Option<NaiveTime>, _>("check_out_time").unwrap_or(None),
+        customer_name: b.try_get::<Option<String>, _>("customer_name").unwrap_or(None).unwrap_or_default(),
+        customer_email: b.get::<String, _>("customer_email"),
+    })
+}
+
+fn header(page: &mut Page, title: &str, right_label: &str, right_value: &str) {
+    page.fill_rect(0.0, PAGE_HEIGHT - 90.0, PAGE_WIDTH, 90.0, 0.93);
+    page.text(MARGIN, PAGE_HEIGHT - 48.0, 20.0, Font::Bold, "MAYA DIGITAL");
+    page.text(MARGIN, PAGE_HEIGHT - 68.0, 11.0, Font::Regular, title);
+    page.text_right(RIGHT, PAGE_HEIGHT - 44.0, 9.0, Font::Regular, right_label);
+    page.text_right(RIGHT, PAGE_HEIGHT - 64.0, 16.0, Font::Bold, right_value);
+}
+
+fn section(page: &mut Page, y: f32, title: &str) -> f32 {
+    page.text(MARGIN, y, 11.0, Font::Bold, title);
+    page.line(MARGIN, y - 5.0, RIGHT, y - 5.0, 0.5);
+    y - 22.0
+}
+
+fn field(page: &mut Page, x: f32, y: f32, label: &str, value: &str) {
+    page.text(x, y, 9.0, Font::Regular, label);
+    page.text(x + 85.0, y, 10.0, Font::Bold, value);
+}
+
+// Tabla de conceptos con importes alineados a la derecha; devuelve la siguiente posición libre
+fn amount_rows(page: &mut Page, mut y: f32, rows: &[(String, f64)]) -> f32 {
+    for (label, amount) in rows {
+        page.text(MARGIN, y, 10.0, Font::Regular, label);
+        page.text_right(RIGHT, y, 10.0, Font::Regular, &money(*amount));
+        y -= 16.0;
+    }
+    y
+}
+
+fn footer(page: &mut Page, text: &str) {
+    page.line(MARGIN, 60.0, RIGHT, 60.0, 0.5);
+    page.text(MARGIN, 45.0, 8.0, Font::Regular, text);
+    page.text_right(RIGHT, 45.0, 8.0, Font::Regular, &format!("Generado el {}", Utc::now().format("%d/%m/%Y %H:%M UTC")));
+}
+
+fn voucher_pdf(auth: &AuthSettings, doc: &BookingDocument) -> Result<Vec<u8>, AppError> {
+    let mut page = Page::new();
+    header(&mut page, "Comprobante de reserva", "Referencia", &doc.reference);
+
+    let mut y = section(&mut page, PAGE_HEIGHT - 125.0, "Hotel");
+    page.text(MARGIN, y, 14.0, Font::Bold, &doc.hotel_name);
+    y -= 16.0;
+    page.text(MARGIN, y, 10.0, Font::Regular, &doc.hotel_address);
+    y -= 14.0;
+    page.text(MARGIN, y, 10.0, Font::Regular, &doc.hotel_location);
+    y -= 14.0;
+    let contact: Vec<&str> = [doc.hotel_phone.as_deref(), doc.hotel_email.as_deref()].into_iter().flatten().collect();
+    if !contact.is_empty() {
+        page.text(MARGIN, y, 10.0, Font::Regular, &contact.join("  •  "));
+        y -= 14.0;
+    }
+
+    // El QR con el código de check-in queda a la derecha de los datos de la estancia
+    let y_stay = section(&mut page, y - 14.0, "Estancia");
+    let token = hotel_check_in_token(auth, doc.id, &doc.reference, doc.check_out)?;
+    page.qr_code(RIGHT - 130.0, y_stay - 128.0, 130.0, &token)
+        .map_err(|e| AppError::InternalServerError(format!("No se pudo generar el código QR: {}", e)))?;
+    page.text(RIGHT - 130.0, y_stay - 142.0, 8.0, Font::Regular, "Presenta este código al llegar");
+
+    let time = |t: Option<NaiveTime>, default: &str| t.map(|t| t.format("%H:%M").to_string()).unwrap_or_else(|| default.to_string());
+    let mut y = y_stay;
+    field(&mut page, MARGIN, y, "Llegada", &format!("{}  desde las {}", doc.check_in.format("%d/%m/%Y"), time(doc.check_in_time, "15:00")));
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Salida", &format!("{}  hasta las {}", doc.check_out.format("%d/%m/%Y"), time(doc.check_out_time, "11:00")));
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Noches", &doc.nights().to_string());
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Huéspedes", &doc.guests.to_string());
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Habitaciones", &doc.rooms.to_string());
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Titular", &doc.customer_name);
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Correo", &doc.customer_email);
+    y -= 18.0;
+    field(&mut page, MARGIN, y, "Estado", &format!("{}  •  Pago: {}", status_label(&doc.status), payment_label(&doc.payment_status)));
+
+    let mut y = section(&mut page, (y - 14.0).min(y_stay - 170.0), "Desglose de precio");
+    y = amount_rows(&mut page, y, &doc.price_lines());
+    page.line(MARGIN, y + 8.0, RIGHT, y + 8.0, 0.5);
+    y -= 8.0;
+    page.text(MARGIN, y, 12.0, Font::Bold, "Total (impuestos incluidos)");
+    page.text_right(RIGHT, y, 12.0, Font::Bold, &money(doc.total_price));
+
+    if let Some(requests) = doc.special_requests.as_deref().filter(|r| !r.trim().is_empty()) {
+        let y = section(&mut page, y - 34.0, "Solicitudes especiales");
+        page.text(MARGIN, y, 10.0, Font::Regular, requests);
+    }
+
+    footer(&mut page, &format!("Reserva #{} creada el {}", doc.id, doc.created_at.format("%d/%m/%Y")));
+
+    let mut document = Document::new(&format!("Reserva {}", doc.reference));
+    document.add_page(page);
+    Ok(document.finish())
+}
+
+struct Invoice {
+    number: String,
+    subtotal: f64,
+    iva_rate: f64,
+    iva_amount: f64,
+    lodging_tax_rate: f64,
+    lodging_tax_amount: f64,
+    total: f64,
+    issued_at: DateTime<Utc>,
+}
+
+async fn tax_rate(pool: &PgPool, setting: &str, default: f64) -> Result<f64, AppError> {
+    let value = sqlx::query("SELECT setting_value FROM platform_settings WHERE setting_name = $1")
+        .bind(setting)
+        .fetch_optional(pool)
+        .await?
+        .and_then(|r| r.get::<String, _>("setting_value").trim().parse::<f64>().ok());
+
+    Ok(value.unwrap_or(default))
+}
+
+// Devuelve la factura de la reserva, emitiéndola la primera vez con las tasas vigentes
+async fn issue_invoice(pool: &PgPool, doc: &BookingDocument) -> Result<Invoice, AppError> {
+    let select = r#"
+        SELECT invoice_number, subtotal::float8 AS subtotal, iva_rate::float8 AS iva_rate,
+               iva_amount::float8 AS iva_amount, lodging_tax_rate::float8 AS lodging_tax_rate,
+               lodging_tax_amount::float8 AS lodging_tax_amount, total::float8 AS total, issued_at
+        FROM booking_invoices
+        WHERE booking_id = $1
+    "#;
+
+    let existing = sqlx::query(select).bind(doc.id).fetch_optional(pool).await?;
+
+    let invoice = match existing {
+        Some(invoice) => invoice,
+        None => {
+            let iva_rate = tax_rate(pool, "IVA_RATE", DEFAULT_IVA_RATE).await?;
+            let lodging_tax_rate = tax_rate(pool, "LODGING_TAX_RATE", DEFAULT_LODGING_TAX_RATE).await?;
+
+            // El precio incluye impuestos; el redondeo se absorbe en el impuesto de hospedaje
+            let total = round2(doc.total_price);
+            let subtotal = round2(total / (1.0 + iva_rate + lodging_tax_rate));
+            let iva_amount = round2(subtotal * iva_rate);
+            let lodging_tax_amount = round2(total - subtotal - iva_amount);
+
+            sqlx::query(
+                r#"
+                INSERT INTO booking_invoices
+                    (id, booking_id, invoice_number, subtotal, iva_rate, iva_amount,
+                     lodging_tax_rate, lodging_tax_amount, total)
+                SELECT n.id, $1, 'MD-' || to_char(NOW(), 'YYYY') || '-' || LPAD(n.id::text, 6, '0'),
+                       $2, $3, $4, $5, $6, $7
+                FROM (SELECT nextval('booking_invoices_id_seq')::int AS id) n
+                ON CONFLICT (booking_id) DO NOTHING
+                "#
+            )
+            .bind(doc.id)
+            .bind(subtotal)
+            .bind(iva_rate)
+            .bind(iva_amount)
+            .bind(lodging_tax_rate)
+            .bind(lodging_tax_amount)
+            .bind(total)
+            .execute(pool)
+            .await?;
+
+            sqlx::query(select).bind(doc.id).fetch_one(pool).await?
+        }
+    };
+
+    Ok(Invoice {
+        number: invoice.get::<String, _>("invoice_number"),
+        subtotal: invoice.get::<f64, _>("subtotal"),
+        iva_rate: invoice.get::<f64, _>("iva_rate"),
+        iva_amount: invoice.get::<f64, _>("iva_amount"),
+        lodging_tax_rate: invoice.get::<f64, _>("lodging_tax_rate"),
+        lodging_tax_amount: invoice.get::<f64, _>("lodging_tax_amount"),
+        total: invoice.get::<f64, _>("total"),
+        issued_at: invoice.get::<DateTime<Utc>, _>("issued_at"),
+    })
+}
+
+fn invoice_pdf(doc: &BookingDocument, invoice: &Invoice) -> Vec<u8> {
+    let mut page = Page::new();
+    header(&mut page, "Factura", "Folio", &invoice.number);
+
+    let y = section(&mut page, PAGE_HEIGHT - 125.0, "Datos");
+    field(&mut page, MARGIN, y, "Fecha", &invoice.issued_at.format("%d/%m/%Y").to_string());
+    field(&mut page, MARGIN, y - 18.0, "Reserva", &doc.reference);
+    field(&mut page, MARGIN, y - 36.0, "Cliente", &doc.customer_name);
+    field(&mut page, MARGIN, y - 54.0, "Correo", &doc.customer_email);
+    field(&mut page, 320.0, y, "Prestador", &doc.hotel_name);
+    page.text(320.0, y - 18.0, 9.0, Font::Regular, &doc.hotel_address);
+    page.text(320.0, y - 32.0, 9.0, Font::Regular, &doc.hotel_location);
+
+    let mut y = section(&mut page, y - 90.0, "Conceptos");
+    page.text(MARGIN, y, 9.0, Font::Regular, &format!(
+        "Estancia del {} al {}",
+        doc.check_in.format("%d/%m/%Y"),
+        doc.check_out.format("%d/%m/%Y")
+    ));
+    y -= 20.0;
+
+    // Conceptos sin impuestos, prorrateados sobre el subtotal; el último absorbe el redondeo
+    let lines = doc.price_lines();
+    let factor = if doc.total_price > 0.0 { invoice.subtotal / doc.total_price } else { 0.0 };
+    let mut remaining = invoice.subtotal;
+    let concept_rows: Vec<(String, f64)> = lines.iter().enumerate().map(|(i, (label, amount))| {
+        let value = if i + 1 == lines.len() { round2(remaining) } else { round2(amount * factor) };
+        remaining -= value;
+        (label.clone(), value)
+    }).collect();
+    y = amount_rows(&mut page, y, &concept_rows);
+
+    page.line(320.0, y + 8.0, RIGHT, y + 8.0, 0.5);
+    y -= 8.0;
+    let totals = [
+        ("Subtotal".to_string(), invoice.subtotal),
+        (format!("IVA ({:.0}%)", invoice.iva_rate * 100.0), invoice.iva_amount),
+        (format!("Impuesto sobre hospedaje ({:.0}%)", invoice.lodging_tax_rate * 100.0), invoice.lodging_tax_amount),
+    ];
+    for (label, amount) in &totals {
+        page.text(320.0, y, 10.0, Font::Regular, label);
+        page.text_right(RIGHT, y, 10.0, Font::Regular, &money(*amount));
+        y -= 16.0;
+    }
+    page.line(320.0, y + 8.0, RIGHT, y + 8.0, 0.5);
+    y -= 8.0;
+    page.text(320.0, y, 12.0, Font::Bold, "Total");
+    page.text_right(RIGHT, y, 12.0, Font::Bold, &money(invoice.total));
+
+    page.text(MARGIN, y - 40.0, 9.0, Font::Regular, &format!("Pago: {}", payment_label(&doc.payment_status)));
+
+    footer(&mut page, "Comprobante emitido por Maya Digital");
+
+    let mut document = Document::new(&format!("Factura {}", invoice.number));
+    document.add_page(page);
+    document.finish()
+}
+
+fn pdf_response(filename: &str, bytes: Vec<u8>) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/pdf")
+        .insert_header(("Content-Disposition", format!("inline; filename=\"{}\"", filename)))
+        .body(bytes)
+}
+
+pub async fn get_booking_voucher(
+    pool: web::Data<PgPool>,
+    settings: web::Data<Settings>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let doc = load_booking_document(pool.get_ref(), path.into_inner(), &user).await?;
+
+    if doc.status == "cancelled" {
+        return Err(AppError::localized(StatusCode::CONFLICT, "document.booking_cancelled"));
+    }
+
+    let bytes = voucher_pdf(&settings.auth, &doc)?;
+    Ok(pdf_response(&format!("reserva-{}.pdf", doc.reference), bytes))
+}
+
+pub async fn get_booking_invoice(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let doc = load_booking_document(pool.get_ref(), path.into_inner(), &user).await?;
+
+    if doc.status != "completed" || doc.payment_status != "paid" {
+        return Err(AppError::localized(StatusCode::CONFLICT, "document.invoice_not_ready"));
+    }
+
+    let invoice = issue_invoice(pool.get_ref(), &doc).await?;
+    Ok(pdf_response(&format!("factura-{}.pdf", invoice.number), invoice_pdf(&doc, &invoice)))
+}
@@ -0,0 +1,553 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    models::{
+        MessageResponse, UserInfo,
+        favorite::{
+            AddFavoriteRequest, FavoriteAvailabilityQuery, FavoriteHotelAvailability, FavoriteItem, FavoriteList,
+            FavoriteListAvailability, FavoriteListDetail, FavoriteListDetailResponse, FavoriteListRequest,
+            FavoriteListResponse, FavoriteListSummary, FavoriteLists, FavoriteSavedResponse, SavedFavoriteItem,
+            SharedFavoriteList, SharedFavoriteListResponse,
+        },
+    },
+    utils::{errors::AppError, i18n::t},
+};
+
+const DEFAULT_LIST_NAME: &str = "Favoritos";
+const MAX_AVAILABILITY_NIGHTS: i64 = 30;
+
+// Elementos de una lista con los datos públicos de cada hotel, negocio o producto.
+// `is_listed` indica si sigue visible en el directorio.
+const FAVORITE_ITEMS: &str = r#"
+    SELECT fi.id, fi.hotel_id, fi.business_id, fi.product_id, fi.notes, fi.created_at,
+           CASE WHEN fi.hotel_id IS NOT NULL THEN 'hotel'
+                WHEN fi.business_id IS NOT NULL THEN 'business'
+                ELSE 'product' END AS item_type,
+           COALESCE(h.name, b.name, p.name) AS name,
+           COALESCE(h.location, b.location, pb.location) AS location,
+           COALESCE(
+               h.image_url,
+               (SELECT bi.image_url FROM business_images bi
+                WHERE bi.business_id = b.id
+                ORDER BY bi.display_order, bi.id LIMIT 1),
+               (SELECT pi.image_url FROM product_images pi
+                WHERE pi.product_id = p.id
+                ORDER BY pi.display_order, pi.id LIMIT 1)
+           ) AS image_url,
+           COALESCE(
+               h.price::float8,
+               (SELECT MIN(v.price)::float8 FROM product_variants v
+                WHERE v.product_id = p.id AND v.is_active)
+           ) AS price,
+           COALESCE(h.rating, b.rating)::float8 AS rating,
+           b.business_type,
+           pb.name AS artisan,
+           CASE WHEN fi.hotel_id IS NOT NULL THEN h.status = 'approved'
+                WHEN fi.business_id IS NOT NULL THEN b.status = 'approved'
+                ELSE p.is_active AND pb.status = 'approved' END AS is_listed
+    FROM favorite_items fi
+    LEFT JOIN hotels h ON fi.hotel_id = h.id
+    LEFT JOIN businesses b ON fi.business_id = b.id
+    LEFT JOIN products p ON fi.product_id = p.id
+    LEFT JOIN businesses pb ON p.business_id = pb.id
+    WHERE fi.list_id = $1
+    ORDER BY fi.created_at DESC, fi.id DESC
+"#;
+
+fn favorite_item_from_row(i: &sqlx::postgres::PgRow) -> SavedFavoriteItem {
+    let item_type = i.get::<String, _>("item_type");
+    let target_id = match item_type.as_str() {
+        "hotel" => i.try_get::<Option<i32>, _>("hotel_id").unwrap_or(None),
+        "business" => i.try_get::<Option<i32>, _>("business_id").unwrap_or(None),
+        _ => i.try_get::<Option<i32>, _>("product_id").unwrap_or(None),
+    };
+
+    SavedFavoriteItem {
+        item: FavoriteItem {
+            id: i.get::<i32, _>("id"),
+            item_type,
+            target_id,
+            name: i.try_get::<Option<String>, _>("name").unwrap_or(None),
+            location: i.try_get::<Option<String>, _>("location").unwrap_or(None),
+            image_url: i.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+            price: i.try_get::<Option<f64>, _>("price").unwrap_or(None),
+            rating: i.try_get::<Option<f64>, _>("rating").unwrap_or(None),
+            business_type: i.try_get::<Option<String>, _>("business_type").unwrap_or(None),
+            artisan: i.try_get::<Option<String>, _>("artisan").unwrap_or(None),
+            is_listed: i.try_get::<Option<bool>, _>("is_listed").unwrap_or(None).unwrap_or(false),
+            added_at: i.get::<DateTime<Utc>, _>("created_at"),
+        },
+        notes: i.try_get::<Option<String>, _>("notes").unwrap_or(None),
+    }
+}
+
+async fn load_favorite_items(pool: &PgPool, list_id: i32) -> Result<Vec<SavedFavoriteItem>, AppError> {
+    let items = sqlx::query(FAVORITE_ITEMS)
+        .bind(list_id)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(items.iter().map(favorite_item_from_row).collect())
+}
+
+// Comprueba que la lista pertenece al usuario y devuelve su fila
+async fn find_my_list(pool: &PgPool, list_id: i32, user: &UserInfo) -> Result<sqlx::postgres::PgRow, AppError> {
+    sqlx::query(
+        "SELECT id, name, share_token, shared_at, created_at, updated_at FROM favorite_lists WHERE id = $1 AND user_id = $2"
+    )
+    .bind(list_id)
+    .bind(user.id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"))
+}
+
+fn list_from_row(l: &sqlx::postgres::PgRow) -> FavoriteList {
+    let share_token = l.try_get::<Option<String>, _>("share_token").unwrap_or(None);
+    FavoriteList {
+        id: l.get::<i32, _>("id"),
+        name: l.get::<String, _>("name"),
+        shared: share_token.is_some(),
+        share_path: share_token.as_ref().map(|t| format!("{}/favorites/shared/{}", crate::API_V1, t)),
+        share_token,
+        created_at: l.get::<DateTime<Utc>, _>("created_at"),
+        updated_at: l.get::<DateTime<Utc>, _>("updated_at"),
+    }
+}
+
+pub async fn get_my_favorite_lists(
+    pool: web::Data<PgPool>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let lists = sqlx::query(
+        r#"
+        SELECT l.id, l.name, l.share_token, l.shared_at, l.created_at, l.updated_at,
+               COUNT(fi.id) AS item_count
+        FROM favorite_lists l
+        LEFT JOIN favorite_items fi ON fi.list_id = l.id
+        WHERE l.user_id = $1
+        GROUP BY l.id
+        ORDER BY l.created_at, l.id
+        "#
+    )
+    .bind(user.id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let list_data: Vec<FavoriteListSummary> = lists.iter().map(|l| FavoriteListSummary {
+        list: list_from_row(l),
+        item_count: l.get::<i64, _>("item_count"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(FavoriteLists { lists: list_data }))
+}
+
+pub async fn create_favorite_list(
+    pool: web::Data<PgPool>,
+    req: web::Json<FavoriteListRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "favorite.name_required"));
+    }
+
+    let list = sqlx::query(
+        r#"
+        INSERT INTO favorite_lists (user_id, name)
+        VALUES ($1, $2)
+        RETURNING id, name, share_token, shared_at, created_at, updated_at
+        "#
+    )
+    .bind(user.id)
+    .bind(name)
+    .fetch_one(pool.get_ref())
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+            AppError::localized(StatusCode::CONFLICT, "favorite.name_taken")
+        }
+        other => AppError::from(other),
+    })?;
+
+    Ok(HttpResponse::Created().json(FavoriteListResponse {
+        message: t("favorite.list_created"),
+        list: list_from_row(&list),
+    }))
+}
+
+pub async fn get_favorite_list(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let list = find_my_list(pool.get_ref(), path.into_inner(), &user).await?;
+    let items = load_favorite_items(pool.get_ref(), list.get::<i32, _>("id")).await?;
+
+    Ok(HttpResponse::Ok().json(FavoriteListDetailResponse {
+        list: FavoriteListDetail { list: list_from_row(&list), items },
+    }))
+}
+
+pub async fn rename_favorite_list(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<FavoriteListRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "favorite.name_required"));
+    }
+
+    let list = sqlx::query(
+        r#"
+        UPDATE favorite_lists SET name = $3, updated_at = NOW()
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, name, share_token, shared_at, created_at, updated_at
+        "#
+    )
+    .bind(path.into_inner())
+    .bind(user.id)
+    .bind(name)
+    .fetch_optional(pool.get_ref())
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+            AppError::localized(StatusCode::CONFLICT, "favorite.name_taken")
+        }
+        other => AppError::from(other),
+    })?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"))?;
+
+    Ok(HttpResponse::Ok().json(FavoriteListResponse {
+        message: t("favorite.list_updated"),
+        list: list_from_row(&list),
+    }))
+}
+
+pub async fn delete_favorite_list(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let result = sqlx::query("DELETE FROM favorite_lists WHERE id = $1 AND user_id = $2")
+        .bind(path.into_inner())
+        .bind(user.id)
+        .execute(pool.get_ref())
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("favorite.list_deleted") }))
+}
+
+// Guarda un elemento en la lista indicada o en la predeterminada ("Favoritos"), que se crea al usarla
+async fn add_favorite(
+    pool: &PgPool,
+    list_id: Option<i32>,
+    req: &AddFavoriteRequest,
+    user: &UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    // Claves de error según el tipo de elemento: (no encontrado, ya en la lista)
+    let ((not_found, duplicate), exists_query, target_id) = match (req.hotel_id, req.business_id, req.product_id) {
+        (Some(id), None, None) => (
+            ("favorite.hotel_not_found", "favorite.hotel_already_listed"),
+            "SELECT 1 FROM hotels WHERE id = $1 AND status = 'approved'",
+            id,
+        ),
+        (None, Some(id), None) => (
+            ("favorite.business_not_found", "favorite.business_already_listed"),
+            "SELECT 1 FROM businesses WHERE id = $1 AND status = 'approved'",
+            id,
+        ),
+        (None, None, Some(id)) => (
+            ("favorite.product_not_found", "favorite.product_already_listed"),
+            "SELECT 1 FROM products p JOIN businesses b ON p.business_id = b.id WHERE p.id = $1 AND p.is_active AND b.status = 'approved'",
+            id,
+        ),
+        _ => return Err(AppError::localized(StatusCode::BAD_REQUEST, "favorite.target_required")),
+    };
+
+    if sqlx::query(exists_query).bind(target_id).fetch_optional(pool).await?.is_none() {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, not_found));
+    }
+
+    let notes = req.notes.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let mut tx = pool.begin().await?;
+
+    let list_id = match list_id {
+        Some(list_id) => sqlx::query("SELECT id FROM favorite_lists WHERE id = $1 AND user_id = $2")
+            .bind(list_id)
+            .bind(user.id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"))?
+            .get::<i32, _>("id"),
+        None => sqlx::query(
+            r#"
+            INSERT INTO favorite_lists (user_id, name) VALUES ($1, $2)
+            ON CONFLICT (user_id, name) DO UPDATE SET updated_at = NOW()
+            RETURNING id
+            "#
+        )
+        .bind(user.id)
+        .bind(DEFAULT_LIST_NAME)
+        .fetch_one(&mut *tx)
+        .await?
+        .get::<i32, _>("id"),
+    };
+
+    let item = sqlx::query(
+        r#"
+        INSERT INTO favorite_items (list_id, hotel_id, business_id, product_id, notes)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#
+    )
+    .bind(list_id)
+    .bind(req.hotel_id)
+    .bind(req.business_id)
+    .bind(req.product_id)
+    .bind(notes)
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db) if db.code().as_deref() == Some("23505") => {
+            AppError::localized(StatusCode::CONFLICT, duplicate)
+        }
+        other => AppError::from(other),
+    })?;
+
+    sqlx::query("UPDATE favorite_lists SET updated_at = NOW() WHERE id = $1")
+        .bind(list_id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(FavoriteSavedResponse {
+        message: t("favorite.saved"),
+        list_id,
+        item_id: item.get::<i32, _>("id"),
+    }))
+}
+
+pub async fn add_to_favorites(
+    pool: web::Data<PgPool>,
+    req: web::Json<AddFavoriteRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    add_favorite(pool.get_ref(), req.list_id, &req, &user).await
+}
+
+pub async fn add_favorite_list_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<AddFavoriteRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    add_favorite(pool.get_ref(), Some(path.into_inner()), &req, &user).await
+}
+
+pub async fn remove_favorite_list_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (list_id, item_id) = path.into_inner();
+
+    let result = sqlx::query(
+        r#"
+        DELETE FROM favorite_items fi
+        USING favorite_lists l
+        WHERE fi.id = $1 AND fi.list_id = $2 AND l.id = fi.list_id AND l.user_id = $3
+        "#
+    )
+    .bind(item_id)
+    .bind(list_id)
+    .bind(user.id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "favorite.item_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("favorite.removed") }))
+}
+
+// Genera el enlace público de la lista; si ya estaba compartida se conserva el mismo
+pub async fn share_favorite_list(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let token = uuid::Uuid::new_v4().simple().to_string();
+
+    let list = sqlx::query(
+        r#"
+        UPDATE favorite_lists
+        SET share_token = COALESCE(share_token, $3),
+            shared_at = COALESCE(shared_at, NOW())
+        WHERE id = $1 AND user_id = $2
+        RETURNING id, name, share_token, shared_at, created_at, updated_at
+        "#
+    )
+    .bind(path.into_inner())
+    .bind(user.id)
+    .bind(&token)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"))?;
+
+    Ok(HttpResponse::Ok().json(FavoriteListResponse {
+        message: t("favorite.shared"),
+        list: list_from_row(&list),
+    }))
+}
+
+// Revoca el enlace; compartir de nuevo genera uno distinto
+pub async fn unshare_favorite_list(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let result = sqlx::query(
+        "UPDATE favorite_lists SET share_token = NULL, shared_at = NULL WHERE id = $1 AND user_id = $2"
+    )
+    .bind(path.into_inner())
+    .bind(user.id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("favorite.unshared") }))
+}
+
+// Vista pública de una lista compartida; no requiere sesión
+pub async fn get_shared_favorite_list(
+    pool: web::Data<PgPool>,
+    path: web::Path<String>,
+) -> Result<HttpResponse, AppError> {
+    let list = sqlx::query(
+        r#"
+        SELECT l.id, l.name, l.updated_at, u.first_name AS owner_name
+        FROM favorite_lists l
+        JOIN users u ON l.user_id = u.id
+        WHERE l.share_token = $1
+        "#
+    )
+    .bind(path.into_inner())
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "favorite.list_not_found"))?;
+
+    // Los elementos retirados del directorio no se muestran a terceros
+    let items: Vec<FavoriteItem> = load_favorite_items(pool.get_ref(), list.get::<i32, _>("id"))
+        .await?
+        .into_iter()
+        .map(|saved| saved.item)
+        .filter(|i| i.is_listed)
+        .collect();
+
+    Ok(HttpResponse::Ok().json(SharedFavoriteListResponse {
+        list: SharedFavoriteList {
+            name: list.get::<String, _>("name"),
+            owner_name: list.try_get::<Option<String>, _>("owner_name").unwrap_or(None),
+            updated_at: list.get::<DateTime<Utc>, _>("updated_at"),
+            items,
+        },
+    }))
+}
+
+// Disponibilidad de los hoteles guardados en la lista para las fechas indicadas
+pub async fn get_favorite_list_availability(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    query: web::Query<FavoriteAvailabilityQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let list = find_my_list(pool.get_ref(), path.into_inner(), &user).await?;
+
+    let check_in: NaiveDate = query.check_in;
+    let check_out: NaiveDate = query.check_out;
+    let rooms = query.rooms.unwrap_or(1);
+
+    if check_out <= check_in {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "booking.invalid_dates"));
+    }
+    if check_in < Utc::now().date_naive() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "favorite.check_in_past"));
+    }
+    let nights = (check_out - check_in).num_days();
+    if nights > MAX_AVAILABILITY_NIGHTS {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "favorite.too_many_nights").arg("max", MAX_AVAILABILITY_NIGHTS));
+    }
+    if !(1..=5).contains(&rooms) {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "favorite.invalid_rooms"));
+    }
+
+    // Habitaciones libres en la noche más ocupada, incluidas las bloqueadas por calendarios externos
+    let hotels = sqlx::query(
+        r#"
+        SELECT h.id, h.name, h.location, h.image_url, h.price::float8 AS price,
+               h.status = 'approved' AS is_listed,
+               hotel_rooms_free(h.id, $2, $3) AS rooms_free
+        FROM favorite_items fi
+        JOIN hotels h ON fi.hotel_id = h.id
+        WHERE fi.list_id = $1
+        ORDER BY fi.created_at DESC, fi.id DESC
+        "#
+    )
+    .bind(list.get::<i32, _>("id"))
+    .bind(check_in)
+    .bind(check_out)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let hotel_list: Vec<FavoriteHotelAvailability> = hotels.into_iter().map(|h| {
+        let is_listed = h.try_get::<Option<bool>, _>("is_listed").unwrap_or(None).unwrap_or(false);
+        let rooms_left = h.try_get::<Option<i32>, _>("rooms_free").unwrap_or(None).unwrap_or(0).max(0);
+        let price = h.get::<f64, _>("price");
+
+        FavoriteHotelAvailability {
+            hotel_id: h.get::<i32, _>("id"),
+            name: h.get::<String, _>("name"),
+            location: h.get::<String, _>("location"),
+            image_url: h.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+            price_per_night: price,
+            estimated_total: price * nights as f64 * rooms as f64,
+            rooms_left: if is_listed { rooms_left } else { 0 },
+            available: is_listed && rooms_left >= rooms,
+        }
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(FavoriteListAvailability {
+        list_id: list.get::<i32, _>("id"),
+        check_in,
+        check_out,
+        nights,
+        rooms,
+        hotels: hotel_list,
+    }))
+}
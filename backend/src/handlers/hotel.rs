@@ -1,10 +1,61 @@
-use actix_web::{web, HttpResponse};
-use sqlx::{PgPool, Row};
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgConnection, PgPool, Row};
+use validator::Validate;
 use crate::{
-    models::{UserInfo, UserRole, hotel::CreateHotelRequest},
-    utils::errors::AppError,
+    handlers::{
+        admin::{record_hotel_review, load_hotel_review_history},
+        translation::{self, ListingKind},
+    },
+    middleware::locale::current_locale,
+    models::{
+        UserInfo, UserRole,
+        hotel::{
+            CreateHotelRequest, HotelCreatedResponse, HotelReviewHistory, HotelStatusResponse, HotelSummary,
+            LatestHotelReview, OwnerHotel, PublicHotel, ResubmitHotelRequest,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
 };
 
+// Directorio público de hoteles (GET /hotels/public)
+pub struct PublicHotels;
+
+impl Listing for PublicHotels {
+    const KEY: &'static str = "id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("price", "price", Kind::Numeric),
+        SortField::new("rating", "COALESCE(rating, 0)", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("location", "location", Kind::Text),
+        Filter::at_least("min_price", "price", Kind::Numeric),
+        Filter::at_most("max_price", "price", Kind::Numeric),
+    ];
+}
+
+// Hoteles del propietario (GET /hotels/my)
+pub struct MyHotels;
+
+impl Listing for MyHotels {
+    const KEY: &'static str = "h.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(h.created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "h.name", Kind::Text),
+        SortField::new("price", "h.price", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "h.status", Kind::Text),
+        Filter::any_of("location", "h.location", Kind::Text),
+    ];
+}
+
 pub async fn verify_hotel_ownership(
     pool: &PgPool,
     hotel_id: i32,
@@ -31,12 +82,18 @@ pub async fn create_hotel(
     user: UserInfo,
 ) -> Result<HttpResponse, AppError> {
     let req = req.into_inner();
+    // Sin idioma explícito se asume el de la petición
+    let content_locale = translation::requested_content_locale(req.content_locale.as_deref())?
+        .unwrap_or_else(current_locale);
+
+    let mut tx = pool.begin().await?;
 
     let result = sqlx::query(
         r#"
         INSERT INTO hotels (owner_id, name, description, location, address, price, 
-                           image_url, phone, email, website, rooms_available, status)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                           image_url, phone, email, website, rooms_available, status,
+                           amenities, content_locale)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
         RETURNING id, name, status, created_at
         "#
     )
@@ -52,81 +109,281 @@ pub async fn create_hotel(
     .bind(req.website.as_deref())
     .bind(req.rooms_available)
     .bind("pending")
-    .fetch_one(pool.get_ref())
+    .bind(translation::normalize_amenities(req.amenities))
+    .bind(content_locale.code())
+    .fetch_one(&mut *tx)
     .await?;
 
-    Ok(HttpResponse::Created().json(serde_json::json!({
-        "message": "Hotel creado exitosamente",
-        "hotel": {
-            "id": result.get::<i32, _>("id"),
-            "name": result.get::<String, _>("name"),
-            "status": result.get::<String, _>("status"),
-            "created_at": result.get::<chrono::DateTime<chrono::Utc>, _>("created_at")
-        }
-    })))
+    record_hotel_review(&mut tx, result.get::<i32, _>("id"), "submitted", user.id, None).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(HotelCreatedResponse {
+        message: t("hotel.created"),
+        hotel: HotelSummary {
+            id: result.get::<i32, _>("id"),
+            name: result.get::<String, _>("name"),
+            status: result.get::<String, _>("status"),
+            created_at: result.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        },
+    }))
 }
 
 pub async fn get_my_hotels(
     pool: web::Data<PgPool>,
+    page: Page<MyHotels>,
     user: UserInfo,
 ) -> Result<HttpResponse, AppError> {
-    let hotels = sqlx::query(
+    let sql = format!(
+        r#"
+        SELECT h.id, h.name, h.description, h.location, h.address, h.price::text as price_text,
+               h.image_url, h.status, h.created_at, h.approved_at, h.admin_notes,
+               h.phone, h.email, h.website, h.rooms_available, h.rating::text as rating_text,
+               h.amenities, h.content_locale,
+               lr.action AS review_action, lr.notes AS review_notes, lr.created_at AS reviewed_at,
+               {}
+        FROM hotels h
+        LEFT JOIN LATERAL (
+            SELECT r.action, r.notes, r.created_at
+            FROM hotel_review_history r
+            WHERE r.hotel_id = h.id AND r.action IN ('approved', 'rejected')
+            ORDER BY r.created_at DESC, r.id DESC
+            LIMIT 1
+        ) lr ON TRUE
+        WHERE h.owner_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let hotels = page
+        .bind(sqlx::query(&sql).bind(user.id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (hotels, next_cursor) = page.finish(hotels);
+
+    let hotel_list: Vec<OwnerHotel> = hotels.into_iter().map(|h| OwnerHotel {
+        id: h.get::<i32, _>("id"),
+        name: h.get::<String, _>("name"),
+        description: h.try_get::<Option<String>, _>("description").unwrap_or(None),
+        location: h.get::<String, _>("location"),
+        address: h.try_get::<Option<String>, _>("address").unwrap_or(None),
+        price: h.get::<String, _>("price_text"),
+        image_url: h.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+        status: h.get::<String, _>("status"),
+        created_at: h.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        approved_at: h.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("approved_at").unwrap_or(None),
+        admin_notes: h.try_get::<Option<String>, _>("admin_notes").unwrap_or(None),
+        phone: h.try_get::<Option<String>, _>("phone").unwrap_or(None),
+        email: h.try_get::<Option<String>, _>("email").unwrap_or(None),
+        website: h.try_get::<Option<String>, _>("website").unwrap_or(None),
+        rooms_available: h.get::<i32, _>("rooms_available"),
+        rating: h.try_get::<Option<String>, _>("rating_text").unwrap_or(None),
+        amenities: h.get::<Vec<String>, _>("amenities"),
+        content_locale: h.get::<String, _>("content_locale"),
+        latest_review: h.try_get::<Option<String>, _>("review_action").unwrap_or(None).map(|action| LatestHotelReview {
+            action,
+            notes: h.try_get::<Option<String>, _>("review_notes").unwrap_or(None),
+            reviewed_at: h.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("reviewed_at").unwrap_or(None),
+        }),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(hotel_list, next_cursor)))
+}
+
+pub async fn update_hotel(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<CreateHotelRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "hotel.edit_forbidden"));
+    }
+
+    let req = req.into_inner();
+    let content_locale = translation::requested_content_locale(req.content_locale.as_deref())?;
+
+    let mut tx = pool.begin().await?;
+
+    // Editar no saca a un hotel rechazado de ese estado: sigue así hasta que se reenvía
+    sqlx::query(
         r#"
-        SELECT id, name, description, location, address, price::text as price_text,
-               image_url, status, created_at, approved_at, admin_notes,
-               phone, email, website, rooms_available, rating::text as rating_text
-        FROM hotels 
-        WHERE owner_id = $1
-        ORDER BY created_at DESC
+        UPDATE hotels
+        SET name = $1, description = $2, location = $3, address = $4, price = $5,
+            image_url = $6, phone = $7, email = $8, website = $9, rooms_available = $10,
+            amenities = $12, content_locale = COALESCE($13, content_locale),
+            updated_at = NOW()
+        WHERE id = $11
+        RETURNING id
         "#
     )
-    .bind(user.id)
-    .fetch_all(pool.get_ref())
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(&req.location)
+    .bind(req.address.as_deref())
+    .bind(req.price)
+    .bind(req.image_url.as_deref())
+    .bind(req.phone.as_deref())
+    .bind(req.email.as_deref())
+    .bind(req.website.as_deref())
+    .bind(req.rooms_available)
+    .bind(hotel_id)
+    .bind(translation::normalize_amenities(req.amenities))
+    .bind(content_locale.map(|l| l.code()))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "hotel.not_found"))?;
+
+    let returned = return_to_review(&mut tx, hotel_id, &user).await?;
+    let status = sqlx::query("SELECT status FROM hotels WHERE id = $1")
+        .bind(hotel_id)
+        .fetch_one(&mut *tx)
+        .await?
+        .get::<String, _>("status");
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(HotelStatusResponse {
+        message: t(if returned { "hotel.updated_pending_review" } else { "hotel.updated" }),
+        status,
+    }))
+}
+
+// Un hotel aprobado que su propietario modifica vuelve a la cola de moderación antes de mostrar
+// el contenido nuevo; las ediciones del administrador no la necesitan
+pub(crate) async fn return_to_review(
+    conn: &mut PgConnection,
+    hotel_id: i32,
+    user: &UserInfo,
+) -> Result<bool, AppError> {
+    if matches!(user.role, UserRole::Admin) {
+        return Ok(false);
+    }
+
+    let returned = sqlx::query(
+        "UPDATE hotels SET status = 'pending', updated_at = NOW() WHERE id = $1 AND status = 'approved' RETURNING id"
+    )
+    .bind(hotel_id)
+    .fetch_optional(&mut *conn)
+    .await?
+    .is_some();
+
+    if returned {
+        record_hotel_review(conn, hotel_id, "resubmitted", user.id, None).await?;
+    }
+
+    Ok(returned)
+}
+
+pub async fn resubmit_hotel(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<ResubmitHotelRequest>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+
+    if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "hotel.resubmit_forbidden"));
+    }
+
+    let notes = req
+        .and_then(|r| r.into_inner().notes)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    let mut tx = pool.begin().await?;
+
+    // Se conserva admin_notes para que el revisor vea el motivo del rechazo anterior
+    let result = sqlx::query(
+        "UPDATE hotels SET status = 'pending', updated_at = NOW() WHERE id = $1 AND status = 'rejected' RETURNING id"
+    )
+    .bind(hotel_id)
+    .fetch_optional(&mut *tx)
     .await?;
 
-    let hotel_list: Vec<serde_json::Value> = hotels.into_iter().map(|h| serde_json::json!({
-        "id": h.get::<i32, _>("id"),
-        "name": h.get::<String, _>("name"),
-        "description": h.try_get::<Option<String>, _>("description").unwrap_or(None),
-        "location": h.get::<String, _>("location"),
-        "address": h.try_get::<Option<String>, _>("address").unwrap_or(None),
-        "price": h.get::<String, _>("price_text"),
-        "image_url": h.try_get::<Option<String>, _>("image_url").unwrap_or(None),
-        "status": h.get::<String, _>("status"),
-        "created_at": h.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
-        "approved_at": h.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("approved_at").unwrap_or(None),
-        "admin_notes": h.try_get::<Option<String>, _>("admin_notes").unwrap_or(None),
-        "phone": h.try_get::<Option<String>, _>("phone").unwrap_or(None),
-        "email": h.try_get::<Option<String>, _>("email").unwrap_or(None),
-        "website": h.try_get::<Option<String>, _>("website").unwrap_or(None),
-        "rooms_available": h.get::<i32, _>("rooms_available"),
-        "rating": h.try_get::<Option<String>, _>("rating_text").unwrap_or(None)
-    })).collect();
-
-    Ok(HttpResponse::Ok().json(hotel_list))
+    if result.is_none() {
+        return Err(AppError::localized(StatusCode::CONFLICT, "hotel.not_rejected"));
+    }
+
+    record_hotel_review(&mut tx, hotel_id, "resubmitted", user.id, notes.as_deref()).await?;
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(HotelStatusResponse {
+        message: t("hotel.resubmitted"),
+        status: "pending".to_string(),
+    }))
+}
+
+pub async fn get_my_hotel_review_history(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+
+    if !verify_hotel_ownership(pool.get_ref(), hotel_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "hotel.view_forbidden"));
+    }
+
+    let history = load_hotel_review_history(pool.get_ref(), hotel_id).await?;
+
+    Ok(HttpResponse::Ok().json(HotelReviewHistory { history }))
 }
 
 pub async fn get_public_hotels(
     pool: web::Data<PgPool>,
+    page: Page<PublicHotels>,
 ) -> Result<HttpResponse, AppError> {
-    let hotels = sqlx::query(
+    let sql = format!(
         r#"
-        SELECT id, name, location, price::numeric::float8 as price, image_url 
-        FROM hotels 
-        WHERE status = 'approved'
-        ORDER BY created_at DESC
-        "#
-    )
-    .fetch_all(pool.get_ref())
-    .await?;
+        SELECT id, name, description, amenities, content_locale, location,
+               price::numeric::float8 as price, image_url,
+               COALESCE(rating, 0)::float8 AS rating, review_count,
+               {}
+        FROM hotels
+        WHERE status = 'approved'{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(1),
+        page.order_and_limit()
+    );
+    let hotels = page.bind(sqlx::query(&sql)).fetch_all(pool.get_ref()).await?;
+    let (hotels, next_cursor) = page.finish(hotels);
 
-    let hotel_list: Vec<serde_json::Value> = hotels.into_iter().map(|h| serde_json::json!({
-        "id": h.get::<i32, _>("id"),
-        "name": h.get::<String, _>("name"),
-        "location": h.get::<String, _>("location"),
-        "price": h.get::<f64, _>("price"),
-        "image_url": h.try_get::<Option<String>, _>("image_url").unwrap_or(None)
-    })).collect();
+    let ids: Vec<i32> = hotels.iter().map(|h| h.get::<i32, _>("id")).collect();
+    let translations = translation::load_translations(pool.get_ref(), ListingKind::Hotel, &ids).await?;
+
+    let hotel_list: Vec<PublicHotel> = hotels.into_iter().map(|h| {
+        let id = h.get::<i32, _>("id");
+        let content = translation::localize(
+            translation::source_locale(&h.get::<String, _>("content_locale")),
+            h.get::<String, _>("name"),
+            h.try_get::<Option<String>, _>("description").unwrap_or(None),
+            h.get::<Vec<String>, _>("amenities"),
+            translations.get(&id).map(Vec::as_slice).unwrap_or_default(),
+        );
+        PublicHotel {
+            id,
+            name: content.name,
+            description: content.description,
+            amenities: content.amenities,
+            locale: content.locale.code().to_string(),
+            location: h.get::<String, _>("location"),
+            price: h.get::<f64, _>("price"),
+            image_url: h.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+            rating: h.get::<f64, _>("rating"),
+            review_count: h.get::<i32, _>("review_count"),
+        }
+    }).collect();
 
-    Ok(HttpResponse::Ok().json(hotel_list))
+    Ok(HttpResponse::Ok().json(page.response(hotel_list, next_cursor)))
 }
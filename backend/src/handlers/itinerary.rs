@@ -0,0 +1,437 @@
+use std::collections::BTreeMap;
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use sqlx::{PgPool, Row};
+use crate::{
+    handlers::business::BUSINESS_TIMEZONE,
+    models::{
+        UserInfo,
+        itinerary::{Itinerary, ItineraryBookingRef, ItineraryConflict, ItineraryDay, ItineraryEntry, ItineraryQuery, ItineraryTrip},
+    },
+    utils::{
+        errors::AppError,
+        i18n::{t, t_args},
+        ical::{Calendar, IcsEvent, IcsTime},
+    },
+};
+
+// Si entre una reserva y la siguiente pasan más días que esto, empieza otro viaje
+const TRIP_GAP_DAYS: i64 = 1;
+
+// Todas las reservas activas del usuario con hora local de inicio y fin.
+// Cada tipo de reserva aporta un bloque; `data` lleva los detalles propios de cada uno.
+const ITINERARY_ITEMS: &str = r#"
+    WITH items AS (
+        SELECT 'hotel'::text AS kind, b.id, b.booking_reference AS reference, b.status,
+               h.name AS title, h.location,
+               b.check_in + COALESCE(h.check_in_time, TIME '15:00') AS starts_at,
+               b.check_out + COALESCE(h.check_out_time, TIME '11:00') AS ends_at,
+               b.total_price::float8 AS total_price,
+               jsonb_build_object(
+                   'hotel_id', h.id,
+                   'address', h.address,
+                   'phone', h.phone,
+                   'check_in', b.check_in,
+                   'check_out', b.check_out,
+                   'guests', b.guests,
+                   'rooms', b.rooms
+               ) AS data
+        FROM bookings b
+        JOIN hotels h ON b.hotel_id = h.id
+        WHERE b.user_id = $1 AND b.status != 'cancelled'
+
+        UNION ALL
+
+        SELECT 'transfer'::text, tb.id, tb.booking_reference, tb.status,
+               r.origin || ' → ' || r.destination, r.origin,
+               t.departure_at AT TIME ZONE $2,
+               t.arrival_at AT TIME ZONE $2,
+               tb.total_price::float8,
+               jsonb_build_object(
+                   'origin', r.origin,
+                   'destination', r.destination,
+                   'trip_type', t.trip_type,
+                   'seats', tb.seats,
+                   'flight_number', tb.flight_number,
+                   'vehicle', v.name,
+                   'business_name', bz.name,
+                   'business_phone', bz.phone
+               )
+        FROM transport_bookings tb
+        JOIN transport_trips t ON tb.trip_id = t.id
+        JOIN transport_routes r ON t.route_id = r.id
+        JOIN transport_vehicles v ON t.vehicle_id = v.id
+        JOIN businesses bz ON r.business_id = bz.id
+        WHERE tb.user_id = $1 AND tb.status != 'cancelled' AND t.status != 'cancelled'
+    )
+    SELECT i.*, i.starts_at AT TIME ZONE $2 AS starts_at_utc, i.ends_at AT TIME ZONE $2 AS ends_at_utc
+    FROM items i
+    WHERE i.ends_at >= $3::date
+      AND ($4::date IS NULL OR i.starts_at < $4::date + 1)
+    ORDER BY i.starts_at, i.kind, i.id
+"#;
+
+struct ItineraryItem {
+    kind: String,
+    id: i32,
+    reference: String,
+    status: String,
+    title: String,
+    location: String,
+    starts_at: NaiveDateTime,
+    ends_at: NaiveDateTime,
+    starts_at_utc: DateTime<Utc>,
+    ends_at_utc: DateTime<Utc>,
+    total_price: f64,
+    data: serde_json::Value,
+}
+
+impl ItineraryItem {
+    // Las estancias solo chocan con otras estancias; el resto (traslados, actividades) entre sí
+    fn is_stay(&self) -> bool {
+        self.kind == "hotel"
+    }
+
+    fn entry(&self, moment: &str) -> ItineraryEntry {
+        ItineraryEntry {
+            item_type: self.kind.clone(),
+            id: self.id,
+            reference: self.reference.clone(),
+            status: self.status.clone(),
+            title: self.title.clone(),
+            location: self.location.clone(),
+            starts_at: self.starts_at,
+            ends_at: self.ends_at,
+            total_price: self.total_price,
+            details: self.data.clone(),
+            moment: moment.to_string(),
+        }
+    }
+
+    fn booking_ref(&self) -> ItineraryBookingRef {
+        ItineraryBookingRef {
+            item_type: self.kind.clone(),
+            id: self.id,
+            reference: self.reference.clone(),
+        }
+    }
+}
+
+struct Conflict {
+    first: usize,
+    second: usize,
+    from: NaiveDateTime,
+    to: NaiveDateTime,
+}
+
+async fn load_itinerary(
+    pool: &PgPool,
+    user_id: i32,
+    query: &ItineraryQuery,
+) -> Result<Vec<ItineraryItem>, AppError> {
+    let from = query.from.unwrap_or_else(|| Utc::now().date_naive());
+    if matches!(query.to, Some(to) if to < from) {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "invalid_date_range"));
+    }
+
+    let rows = sqlx::query(ITINERARY_ITEMS)
+        .bind(user_id)
+        .bind(BUSINESS_TIMEZONE)
+        .bind(from)
+        .bind(query.to)
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.into_iter().map(|r| ItineraryItem {
+        kind: r.get::<String, _>("kind"),
+        id: r.get::<i32, _>("id"),
+        reference: r.get::<String, _>("reference"),
+        status: r.get::<String, _>("status"),
+        title: r.get::<String, _>("title"),
+        location: r.get::<String, _>("location"),
+        starts_at: r.get::<NaiveDateTime, _>("starts_at"),
+        ends_at: r.get::<NaiveDateTime, _>("ends_at"),
+        starts_at_utc: r.get::<DateTime<Utc>, _>("starts_at_utc"),
+        ends_at_utc: r.get::<DateTime<Utc>, _>("ends_at_utc"),
+        total_price: r.get::<f64, _>("total_price"),
+        data: r.get::<serde_json::Value, _>("data"),
+    }).collect())
+}
+
+// Agrupa reservas consecutivas (ya ordenadas por inicio) en viajes
+fn split_trips(items: &[ItineraryItem]) -> Vec<std::ops::Range<usize>> {
+    let mut trips = Vec::new();
+    let mut start = 0;
+    let mut trip_end: Option<NaiveDateTime> = None;
+
+    for (index, item) in items.iter().enumerate() {
+        if let Some(end) = trip_end {
+            if item.starts_at > end + chrono::Duration::days(TRIP_GAP_DAYS) {
+                trips.push(start..index);
+                start = index;
+                trip_end = None;
+            }
+        }
+        trip_end = Some(trip_end.map_or(item.ends_at, |end| end.max(item.ends_at)));
+    }
+
+    if start < items.len() {
+        trips.push(start..items.len());
+    }
+    trips
+}
+
+fn find_conflicts(items: &[ItineraryItem]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for (i, a) in items.iter().enumerate() {
+        for (j, b) in items.iter().enumerate().skip(i + 1) {
+            // Orden por inicio: si b empieza después de que a termina, ninguna posterior choca con a
+            if b.starts_at >= a.ends_at {
+                break;
+            }
+            if a.is_stay() != b.is_stay() {
+                continue;
+            }
+            conflicts.push(Conflict {
+                first: i,
+                second: j,
+                from: b.starts_at,
+                to: a.ends_at.min(b.ends_at),
+            });
+        }
+    }
+    conflicts
+}
+
+fn conflict_from(items: &[ItineraryItem], conflict: &Conflict) -> ItineraryConflict {
+    let first = &items[conflict.first];
+    let second = &items[conflict.second];
+    let args = [("first", first.title.clone()), ("second", second.title.clone())];
+    let message = if first.is_stay() {
+        t_args("itinerary.stays_overlap", &args)
+    } else {
+        t_args("itinerary.schedule_overlap", &args)
+    };
+
+    ItineraryConflict {
+        items: vec![first.booking_ref(), second.booking_ref()],
+        from: conflict.from,
+        to: conflict.to,
+        message,
+    }
+}
+
+// Un viaje día por día: cada reserva aparece en todos los días que abarca
+fn trip_from(number: usize, items: &[ItineraryItem]) -> ItineraryTrip {
+    let starts_on = items.iter().map(|i| i.starts_at.date()).min().unwrap_or_default();
+    let ends_on = items.iter().map(|i| i.ends_at.date()).max().unwrap_or_default();
+
+    let mut days: BTreeMap<NaiveDate, Vec<(NaiveDateTime, ItineraryEntry)>> = BTreeMap::new();
+    for item in items {
+        let (first_day, last_day) = (item.starts_at.date(), item.ends_at.date());
+        for day in first_day.iter_days().take_while(|d| *d <= last_day) {
+            // "start", "end" u "ongoing" según lo que ocurre con la reserva ese día
+            let (moment, at) = if day == first_day {
+                ("start", item.starts_at)
+            } else if day == last_day {
+                ("end", item.ends_at)
+            } else {
+                ("ongoing", day.and_hms_opt(0, 0, 0).unwrap_or(item.starts_at))
+            };
+            days.entry(day).or_default().push((at, item.entry(moment)));
+        }
+    }
+
+    let day_list: Vec<ItineraryDay> = days.into_iter().map(|(date, mut entries)| {
+        entries.sort_by_key(|(at, _)| *at);
+        ItineraryDay {
+            date,
+            items: entries.into_iter().map(|(_, entry)| entry).collect(),
+        }
+    }).collect();
+
+    let mut locations: Vec<String> = Vec::new();
+    for item in items {
+        if !locations.contains(&item.location) {
+            locations.push(item.location.clone());
+        }
+    }
+
+    let conflicts: Vec<ItineraryConflict> = find_conflicts(items)
+        .iter()
+        .map(|c| conflict_from(items, c))
+        .collect();
+
+    ItineraryTrip {
+        trip: number,
+        starts_on,
+        ends_on,
+        locations,
+        bookings: items.len(),
+        total_price: items.iter().map(|i| i.total_price).sum::<f64>(),
+        days: day_list,
+        conflicts,
+    }
+}
+
+pub async fn get_my_itinerary(
+    pool: web::Data<PgPool>,
+    query: web::Query<ItineraryQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let items = load_itinerary(pool.get_ref(), user.id, &query).await?;
+
+    let trips: Vec<ItineraryTrip> = split_trips(&items)
+        .into_iter()
+        .enumerate()
+        .map(|(index, range)| trip_from(index + 1, &items[range]))
+        .collect();
+
+    let conflict_count: usize = trips.iter().map(|t| t.conflicts.len()).sum();
+
+    Ok(HttpResponse::Ok().json(Itinerary {
+        timezone: BUSINESS_TIMEZONE.to_string(),
+        trips,
+        total_bookings: items.len(),
+        total_conflicts: conflict_count,
+    }))
+}
+
+pub async fn export_my_itinerary_ics(
+    pool: web::Data<PgPool>,
+    query: web::Query<ItineraryQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let items = load_itinerary(pool.get_ref(), user.id, &query).await?;
+
+    let mut calendar = Calendar::new(&t("itinerary.calendar_name"));
+    for item in &items {
+        let detail = |key: &str| item.data.get(key).and_then(|v| v.as_str()).map(str::to_string);
+
+        // Las estancias se exportan como días completos (noches reservadas)
+        let (start, end, location, summary) = if item.is_stay() {
+            (
+                IcsTime::Date(item.starts_at.date()),
+                IcsTime::Date(item.ends_at.date()),
+                detail("address").unwrap_or_else(|| item.location.clone()),
+                t_args("itinerary.stay_summary", &[("title", item.title.clone())]),
+            )
+        } else {
+            (
+                IcsTime::Utc(item.starts_at_utc),
+                IcsTime::Utc(item.ends_at_utc),
+                item.location.clone(),
+                t_args("itinerary.transfer_summary", &[("title", item.title.clone())]),
+            )
+        };
+
+        let mut description = t_args(
+            "itinerary.event_description",
+            &[("reference", item.reference.clone()), ("status", item.status.clone())],
+        );
+        if let Some(flight) = detail("flight_number") {
+            description.push('\n');
+            description.push_str(&t_args("itinerary.flight", &[("flight", flight)]));
+        }
+        if let Some(phone) = detail("phone").or_else(|| detail("business_phone")) {
+            description.push('\n');
+            description.push_str(&t_args("itinerary.phone", &[("phone", phone)]));
+        }
+
+        calendar.add_event(&IcsEvent {
+            uid: format!("{}-{}@mayadigital", item.kind, item.id),
+            summary,
+            description: Some(description),
+            location: Some(location),
+            start,
+            end,
+            confirmed: item.status == "confirmed" || item.status == "completed",
+        });
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/calendar; charset=utf-8")
+        .insert_header(("Content-Disposition", "attachment; filename=\"itinerario.ics\""))
+        .body(calendar.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 3, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn item(kind: &str, id: i32, starts_at: NaiveDateTime, ends_at: NaiveDateTime) -> ItineraryItem {
+        ItineraryItem {
+            kind: kind.to_string(),
+            id,
+            reference: format!("REF{}", id),
+            status: "confirmed".to_string(),
+            title: format!("{} {}", kind, id),
+            location: "Mérida".to_string(),
+            starts_at,
+            ends_at,
+            starts_at_utc: starts_at.and_utc(),
+            ends_at_utc: ends_at.and_utc(),
+            total_price: 100.0,
+            data: serde_json::json!({}),
+        }
+    }
+
+    #[test]
+    fn un_hueco_de_hasta_un_dia_no_separa_el_viaje() {
+        let items = [
+            item("hotel", 1, at(1, 15), at(3, 11)),
+            // Empieza justo TRIP_GAP_DAYS después de la salida: mismo viaje
+            item("hotel", 2, at(4, 11), at(6, 11)),
+            // Un minuto más de hueco ya es otro viaje
+            item("transfer", 3, at(7, 11) + chrono::Duration::minutes(1), at(7, 13)),
+        ];
+
+        assert_eq!(split_trips(&items), [0..2, 2..3]);
+        assert!(split_trips(&[]).is_empty());
+    }
+
+    #[test]
+    fn una_estancia_larga_mantiene_unidas_las_reservas_que_abarca() {
+        let items = [
+            item("hotel", 1, at(1, 15), at(10, 11)),
+            item("transfer", 2, at(2, 9), at(2, 12)),
+            // Empieza después del traslado pero dentro de la estancia larga
+            item("transfer", 3, at(9, 9), at(9, 12)),
+            item("hotel", 4, at(20, 15), at(22, 11)),
+        ];
+
+        assert_eq!(split_trips(&items), [0..3, 3..4]);
+    }
+
+    #[test]
+    fn un_traslado_durante_una_estancia_no_es_conflicto() {
+        let items = [
+            item("hotel", 1, at(1, 15), at(4, 11)),
+            item("transfer", 2, at(2, 9), at(2, 12)),
+        ];
+
+        assert!(find_conflicts(&items).is_empty());
+    }
+
+    #[test]
+    fn estancias_o_traslados_que_se_traslapan_son_conflicto() {
+        let items = [
+            item("hotel", 1, at(1, 15), at(4, 11)),
+            item("transfer", 2, at(2, 9), at(2, 12)),
+            item("transfer", 3, at(2, 11), at(2, 14)),
+            item("hotel", 4, at(3, 15), at(5, 11)),
+            // Llegar el día de la salida del hotel anterior no choca
+            item("hotel", 5, at(5, 11), at(6, 11)),
+        ];
+        let conflicts: Vec<(usize, usize, NaiveDateTime, NaiveDateTime)> = find_conflicts(&items)
+            .into_iter()
+            .map(|c| (c.first, c.second, c.from, c.to))
+            .collect();
+
+        assert_eq!(conflicts, [(0, 3, at(3, 15), at(4, 11)), (1, 2, at(2, 11), at(2, 12))]);
+    }
+}
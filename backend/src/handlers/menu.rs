@@ -0,0 +1,463 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{
+        business::{verify_business_ownership, BUSINESS_TIMEZONE},
+        translation::{self, ListingKind},
+    },
+    models::{
+        MessageResponse, UserInfo,
+        menu::{
+            MenuItem, MenuItemCreatedResponse, MenuItemRequest, MenuSection, MenuSectionCreatedResponse,
+            MenuSectionRequest, OwnerMenu, PublicMenu, DIETARY_TAGS,
+        },
+    },
+    utils::{errors::AppError, i18n::t},
+};
+
+async fn verify_restaurant_owner(
+    pool: &PgPool,
+    business_id: i32,
+    user: &UserInfo,
+) -> Result<(), AppError> {
+    if !verify_business_ownership(pool, business_id, user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "menu.forbidden"));
+    }
+
+    let business = sqlx::query("SELECT business_type FROM businesses WHERE id = $1")
+        .bind(business_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "business.not_found"))?;
+
+    if business.get::<String, _>("business_type") != "restaurant" {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "menu.restaurant_only"));
+    }
+
+    Ok(())
+}
+
+// Normaliza una lista de etiquetas: minúsculas, sin espacios ni duplicados
+fn normalize_tags(tags: Option<Vec<String>>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags.unwrap_or_default() {
+        let tag = tag.trim().to_lowercase().replace(['-', ' '], "_");
+        if !tag.is_empty() && !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    normalized
+}
+
+fn validate_menu_item(req: &MenuItemRequest) -> Result<(Vec<String>, Vec<String>), AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    if req.available_from.is_some() != req.available_until.is_some() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "menu.availability_incomplete"));
+    }
+
+    let dietary_tags = normalize_tags(req.dietary_tags.clone());
+    if let Some(tag) = dietary_tags.iter().find(|t| !DIETARY_TAGS.contains(&t.as_str())) {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "menu.unknown_dietary_tag")
+            .arg("tag", tag)
+            .arg("allowed", DIETARY_TAGS.join(", ")));
+    }
+
+    Ok((dietary_tags, normalize_tags(req.allergens.clone())))
+}
+
+async fn load_menu(pool: &PgPool, business_id: i32) -> Result<Vec<MenuSection>, AppError> {
+    let sections = sqlx::query(
+        r#"
+        SELECT id, name, description, display_order
+        FROM menu_sections
+        WHERE business_id = $1
+        ORDER BY display_order, id
+        "#
+    )
+    .bind(business_id)
+    .fetch_all(pool)
+    .await?;
+
+    let items = sqlx::query(
+        r#"
+        SELECT i.id, i.section_id, i.name, i.description, i.price::float8 AS price,
+               i.dietary_tags, i.allergens, i.image_url, i.is_active,
+               i.available_from::text AS available_from,
+               i.available_until::text AS available_until,
+               COALESCE(i.sold_out_on >= (NOW() AT TIME ZONE $2)::date, false) AS sold_out,
+               CASE
+                   WHEN i.available_from IS NULL THEN true
+                   WHEN i.available_until > i.available_from THEN
+                       (NOW() AT TIME ZONE $2)::time >= i.available_from
+                       AND (NOW() AT TIME ZONE $2)::time < i.available_until
+                   ELSE
+                       (NOW() AT TIME ZONE $2)::time >= i.available_from
+                       OR (NOW() AT TIME ZONE $2)::time < i.available_until
+               END AS in_window
+        FROM menu_items i
+        WHERE i.business_id = $1
+        ORDER BY i.display_order, i.id
+        "#
+    )
+    .bind(business_id)
+    .bind(BUSINESS_TIMEZONE)
+    .fetch_all(pool)
+    .await?;
+
+    let menu = sections.into_iter().map(|s| {
+        let section_id = s.get::<i32, _>("id");
+        let section_items: Vec<MenuItem> = items
+            .iter()
+            .filter(|i| i.get::<i32, _>("section_id") == section_id)
+            .map(|i| {
+                let is_active = i.get::<bool, _>("is_active");
+                let sold_out = i.get::<bool, _>("sold_out");
+                let in_window = i.try_get::<Option<bool>, _>("in_window").unwrap_or(None).unwrap_or(true);
+                MenuItem {
+                    id: i.get::<i32, _>("id"),
+                    name: i.get::<String, _>("name"),
+                    description: i.try_get::<Option<String>, _>("description").unwrap_or(None),
+                    locale: None,
+                    price: i.get::<f64, _>("price"),
+                    dietary_tags: i.get::<Vec<String>, _>("dietary_tags"),
+                    allergens: i.get::<Vec<String>, _>("allergens"),
+                    image_url: i.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+                    available_from: i.try_get::<Option<String>, _>("available_from").unwrap_or(None),
+                    available_until: i.try_get::<Option<String>, _>("available_until").unwrap_or(None),
+                    is_active,
+                    sold_out,
+                    available_now: is_active && !sold_out && in_window,
+                }
+            })
+            .collect();
+
+        MenuSection {
+            id: section_id,
+            name: s.get::<String, _>("name"),
+            description: s.try_get::<Option<String>, _>("description").unwrap_or(None),
+            display_order: s.get::<i32, _>("display_order"),
+            items: section_items,
+        }
+    }).collect();
+
+    Ok(menu)
+}
+
+pub async fn get_public_menu(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    let business = sqlx::query(
+        "SELECT id, name, content_locale FROM businesses WHERE id = $1 AND status = 'approved' AND business_type = 'restaurant'"
+    )
+    .bind(business_id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "menu.restaurant_not_found"))?;
+
+    let sections = load_menu(pool.get_ref(), business_id).await?;
+
+    let item_ids: Vec<i32> = sections
+        .iter()
+        .flat_map(|section| section.items.iter().map(|item| item.id))
+        .collect();
+    let translations = translation::load_translations(pool.get_ref(), ListingKind::MenuItem, &item_ids).await?;
+    let source = translation::source_locale(&business.get::<String, _>("content_locale"));
+
+    // El público no ve platillos desactivados por el dueño; los demás salen en su idioma
+    let sections: Vec<MenuSection> = sections
+        .into_iter()
+        .map(|mut section| {
+            section.items.retain(|item| item.is_active);
+            for item in section.items.iter_mut() {
+                let content = translation::localize(
+                    source,
+                    std::mem::take(&mut item.name),
+                    item.description.take(),
+                    Vec::new(),
+                    translations.get(&item.id).map(Vec::as_slice).unwrap_or_default(),
+                );
+                item.name = content.name;
+                item.description = content.description;
+                item.locale = Some(content.locale.code().to_string());
+            }
+            section
+        })
+        .collect();
+
+    Ok(HttpResponse::Ok().json(PublicMenu {
+        business_id: business.get::<i32, _>("id"),
+        business_name: business.get::<String, _>("name"),
+        sections,
+    }))
+}
+
+pub async fn get_my_menu(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let sections = load_menu(pool.get_ref(), business_id).await?;
+
+    Ok(HttpResponse::Ok().json(OwnerMenu { business_id, sections }))
+}
+
+pub async fn create_menu_section(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<MenuSectionRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO menu_sections (business_id, name, description, display_order)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#
+    )
+    .bind(business_id)
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(req.display_order.unwrap_or(0))
+    .fetch_one(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(MenuSectionCreatedResponse {
+        message: t("menu.section_created"),
+        section_id: result.get::<i32, _>("id"),
+    }))
+}
+
+pub async fn update_menu_section(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<MenuSectionRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, section_id) = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let result = sqlx::query(
+        r#"
+        UPDATE menu_sections
+        SET name = $1, description = $2, display_order = $3, updated_at = NOW()
+        WHERE id = $4 AND business_id = $5
+        "#
+    )
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(req.display_order.unwrap_or(0))
+    .bind(section_id)
+    .bind(business_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "menu.section_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("menu.section_updated") }))
+}
+
+pub async fn delete_menu_section(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, section_id) = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let result = sqlx::query("DELETE FROM menu_sections WHERE id = $1 AND business_id = $2")
+        .bind(section_id)
+        .bind(business_id)
+        .execute(pool.get_ref())
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "menu.section_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("menu.section_deleted") }))
+}
+
+pub async fn create_menu_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<MenuItemRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let req = req.into_inner();
+    let (dietary_tags, allergens) = validate_menu_item(&req)?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO menu_items (section_id, business_id, name, description, price,
+                                dietary_tags, allergens, available_from, available_until,
+                                image_url, is_active, display_order)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12
+        WHERE EXISTS (SELECT 1 FROM menu_sections WHERE id = $1 AND business_id = $2)
+        RETURNING id
+        "#
+    )
+    .bind(req.section_id)
+    .bind(business_id)
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(req.price)
+    .bind(&dietary_tags)
+    .bind(&allergens)
+    .bind(req.available_from)
+    .bind(req.available_until)
+    .bind(req.image_url.as_deref())
+    .bind(req.is_active.unwrap_or(true))
+    .bind(req.display_order.unwrap_or(0))
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "menu.section_not_found"))?;
+
+    Ok(HttpResponse::Created().json(MenuItemCreatedResponse {
+        message: t("menu.item_created"),
+        item_id: result.get::<i32, _>("id"),
+    }))
+}
+
+pub async fn update_menu_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<MenuItemRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id) = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let req = req.into_inner();
+    let (dietary_tags, allergens) = validate_menu_item(&req)?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE menu_items
+        SET section_id = $1, name = $2, description = $3, price = $4,
+            dietary_tags = $5, allergens = $6, available_from = $7, available_until = $8,
+            image_url = $9, is_active = $10, display_order = $11, updated_at = NOW()
+        WHERE id = $12 AND business_id = $13
+          AND EXISTS (SELECT 1 FROM menu_sections WHERE id = $1 AND business_id = $13)
+        "#
+    )
+    .bind(req.section_id)
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(req.price)
+    .bind(&dietary_tags)
+    .bind(&allergens)
+    .bind(req.available_from)
+    .bind(req.available_until)
+    .bind(req.image_url.as_deref())
+    .bind(req.is_active.unwrap_or(true))
+    .bind(req.display_order.unwrap_or(0))
+    .bind(item_id)
+    .bind(business_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "menu.item_or_section_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("menu.item_updated") }))
+}
+
+pub async fn delete_menu_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id) = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let result = sqlx::query("DELETE FROM menu_items WHERE id = $1 AND business_id = $2")
+        .bind(item_id)
+        .bind(business_id)
+        .execute(pool.get_ref())
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "menu.item_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("menu.item_deleted") }))
+}
+
+// Marca el platillo como agotado hasta el final del día (hora local del negocio)
+pub async fn mark_item_sold_out(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id) = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE menu_items
+        SET sold_out_on = (NOW() AT TIME ZONE $3)::date, updated_at = NOW()
+        WHERE id = $1 AND business_id = $2
+        "#
+    )
+    .bind(item_id)
+    .bind(business_id)
+    .bind(BUSINESS_TIMEZONE)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "menu.item_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("menu.item_sold_out") }))
+}
+
+pub async fn clear_item_sold_out(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id) = path.into_inner();
+    verify_restaurant_owner(pool.get_ref(), business_id, &user).await?;
+
+    let result = sqlx::query(
+        "UPDATE menu_items SET sold_out_on = NULL, updated_at = NOW() WHERE id = $1 AND business_id = $2"
+    )
+    .bind(item_id)
+    .bind(business_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "menu.item_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("menu.item_available") }))
+}
@@ -3,12 +3,30 @@ pub mod hotel;
 pub mod booking;
 pub mod admin;
 pub mod business;
+pub mod menu;
+pub mod product;
+pub mod order;
+pub mod transport;
+pub mod notification;
+pub mod suspension;
+pub mod moderation;
+pub mod analytics;
+pub mod review;
+pub mod favorite;
+pub mod itinerary;
+pub mod calendar;
+pub mod document;
+pub mod check_in;
+pub mod translation;
 
 pub use auth::{register, login};
 pub use hotel::{
     create_hotel,
     get_my_hotels,
     get_public_hotels,
+    update_hotel,
+    resubmit_hotel,
+    get_my_hotel_review_history,
     verify_hotel_ownership,
 };
 pub use booking::{
@@ -21,11 +39,11 @@ pub use admin::{
     get_pending_hotels,
     approve_hotel,
     reject_hotel,
+    get_hotel_review_history,
     get_all_hotels,
     get_pending_businesses,
     approve_business,
     reject_business,
-    get_dashboard_stats,
 };
 pub use business::{
     create_business,
@@ -33,4 +51,127 @@ pub use business::{
     get_business_detail,
     update_business,
     delete_business,
+    get_public_businesses,
+    get_public_business_detail,
+    verify_business_ownership,
+};
+pub use menu::{
+    get_public_menu,
+    get_my_menu,
+    create_menu_section,
+    update_menu_section,
+    delete_menu_section,
+    create_menu_item,
+    update_menu_item,
+    delete_menu_item,
+    mark_item_sold_out,
+    clear_item_sold_out,
+};
+pub use product::{
+    get_public_products,
+    get_public_product_detail,
+    create_product,
+    update_product,
+    delete_product,
+    add_product_variant,
+    update_product_variant,
+};
+pub use order::{
+    get_cart,
+    add_cart_item,
+    update_cart_item,
+    remove_cart_item,
+    clear_cart,
+    checkout,
+    get_my_orders,
+    get_order_detail,
+    cancel_order,
+    get_business_orders,
+};
+pub use transport::{
+    get_my_vehicles,
+    create_vehicle,
+    update_vehicle,
+    get_my_routes,
+    create_route,
+    update_route,
+    create_trip,
+    cancel_trip,
+    get_business_transport_bookings,
+    get_public_routes,
+    get_route_trips,
+    create_transport_booking,
+    get_my_transport_bookings,
+    cancel_transport_booking,
+};
+pub use notification::{
+    get_my_notifications,
+    mark_notification_read,
+};
+pub use suspension::{
+    suspend_hotel,
+    reinstate_hotel,
+    suspend_business,
+    reinstate_business,
+    get_active_suspensions,
+    reinstate_expired_suspensions,
+};
+pub use moderation::{
+    get_moderation_queue,
+    bulk_moderate,
+};
+pub use analytics::{
+    get_dashboard_stats,
+    get_hotel_analytics,
+    export_hotel_analytics_csv,
+};
+pub use review::{
+    create_review,
+    get_my_reviews,
+    get_hotel_reviews,
+    get_business_reviews,
+    reply_to_review,
+    approve_review,
+    reject_review,
+    recompute_all_ratings,
+};
+pub use favorite::{
+    get_my_favorite_lists,
+    create_favorite_list,
+    get_favorite_list,
+    rename_favorite_list,
+    delete_favorite_list,
+    add_to_favorites,
+    add_favorite_list_item,
+    remove_favorite_list_item,
+    share_favorite_list,
+    unshare_favorite_list,
+    get_shared_favorite_list,
+    get_favorite_list_availability,
+};
+pub use itinerary::{get_my_itinerary, export_my_itinerary_ics};
+pub use calendar::{
+    get_hotel_ics_feed,
+    get_hotel_calendar_sync,
+    rotate_hotel_calendar_token,
+    get_hotel_calendar_conflicts,
+    add_hotel_calendar_feed,
+    upload_hotel_calendar,
+    sync_hotel_calendar_feed,
+    delete_hotel_calendar_feed,
+    sync_calendar_feeds,
+};
+pub use document::{get_booking_voucher, get_booking_invoice};
+pub use check_in::{get_booking_check_in_token, get_transport_check_in_token, check_in_booking};
+pub use translation::{
+    get_hotel_translations,
+    save_hotel_translation,
+    delete_hotel_translation,
+    get_business_translations,
+    save_business_translation,
+    delete_business_translation,
+    get_menu_item_translations,
+    save_menu_item_translation,
+    delete_menu_item_translation,
+    get_missing_translations,
 };
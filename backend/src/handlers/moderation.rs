@@ -0,0 +1,208 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{
+        admin::{require_admin, decide_hotel, decide_business},
+        review::decide_review,
+    },
+    models::{
+        UserInfo,
+        moderation::{BulkModerationRequest, BulkModerationResponse, ModerationItem, ModerationOwner, ModerationQueueQuery},
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
+};
+
+const MAX_BULK_ITEMS: usize = 100;
+const MODERATION_TYPES: [&str; 3] = ["hotel", "business", "review"];
+
+// Todo lo que espera revisión, con la fecha del último envío (o reenvío)
+const MODERATION_QUEUE: &str = r#"
+    WITH queue AS (
+        SELECT 'hotel'::text AS item_type, h.id, h.name, h.location, h.owner_id,
+               COALESCE((
+                   SELECT MAX(r.created_at) FROM hotel_review_history r
+                   WHERE r.hotel_id = h.id AND r.action IN ('submitted', 'resubmitted')
+               ), h.created_at) AS submitted_at,
+               EXISTS (
+                   SELECT 1 FROM hotel_review_history r
+                   WHERE r.hotel_id = h.id AND r.action = 'resubmitted'
+               ) AS resubmitted,
+               to_jsonb(h) - 'owner_id' - 'approved_by' AS details
+        FROM hotels h
+        WHERE h.status = 'pending'
+
+        UNION ALL
+
+        SELECT 'business'::text, b.id, b.name, b.location, b.owner_id,
+               b.created_at, FALSE,
+               (to_jsonb(b) - 'owner_id' - 'approved_by') || jsonb_build_object(
+                   'images', COALESCE((
+                       SELECT jsonb_agg(jsonb_build_object(
+                                  'image_url', bi.image_url,
+                                  'image_type', bi.image_type
+                              ) ORDER BY bi.display_order, bi.id)
+                       FROM business_images bi
+                       WHERE bi.business_id = b.id
+                   ), '[]'::jsonb)
+               )
+        FROM businesses b
+        WHERE b.status = 'pending'
+
+        UNION ALL
+
+        -- En las reseñas el "owner" es el autor de la reseña
+        SELECT 'review'::text, r.id, COALESCE(h.name, b.name), COALESCE(h.location, b.location),
+               r.user_id, r.created_at, FALSE,
+               (to_jsonb(r) - 'user_id' - 'moderated_by') || jsonb_build_object(
+                   'listing_type', CASE WHEN r.hotel_id IS NOT NULL THEN 'hotel' ELSE 'business' END
+               )
+        FROM reviews r
+        LEFT JOIN hotels h ON r.hotel_id = h.id
+        LEFT JOIN businesses b ON r.business_id = b.id
+        WHERE r.status = 'pending'
+    )
+"#;
+
+// Cola de moderación (GET /admin/moderation); por defecto primero lo que más espera.
+// Hoteles, negocios y reseñas comparten ids, así que la clave del cursor combina el id con el tipo.
+pub struct ModerationQueue;
+
+impl Listing for ModerationQueue {
+    const KEY: &'static str =
+        "q.id::bigint * 3 + array_position(ARRAY['hotel', 'business', 'review'], q.item_type)";
+    const SORTS: &'static [SortField] = &[SortField::new("submitted_at", "q.submitted_at", Kind::Timestamp)];
+    const DEFAULT_SORT: &'static str = "submitted_at";
+    const FILTERS: &'static [Filter] = &[];
+}
+
+// Filtros del listado ($1..$4); los de la página se numeran a partir de $5
+const MODERATION_FILTER: &str = r#"
+    WHERE ($1::text IS NULL OR q.item_type = $1)
+      AND ($2::text IS NULL OR q.location ILIKE '%' || $2 || '%')
+      AND ($3::int IS NULL OR q.submitted_at <= NOW() - make_interval(hours => $3::int))
+      AND ($4::int IS NULL OR q.submitted_at >= NOW() - make_interval(hours => $4::int))
+"#;
+
+pub async fn get_moderation_queue(
+    pool: web::Data<PgPool>,
+    query: web::Query<ModerationQueueQuery>,
+    page: Page<ModerationQueue>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+
+    let item_type = query.item_type.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    if let Some(t) = item_type {
+        if !MODERATION_TYPES.contains(&t) {
+            return Err(AppError::localized(StatusCode::BAD_REQUEST, "moderation.unknown_type").arg("type", t));
+        }
+    }
+
+    let location = query.location.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let min_age = query.min_age_hours.map(|h| h.clamp(0, i32::MAX as i64) as i32);
+    let max_age = query.max_age_hours.map(|h| h.clamp(0, i32::MAX as i64) as i32);
+
+    let sql = format!(
+        r#"
+        {}
+        SELECT q.item_type, q.id, q.name, q.location, q.submitted_at, q.resubmitted, q.details,
+               q.owner_id, u.email AS owner_email,
+               u.first_name || ' ' || u.last_name AS owner_name,
+               {}
+        FROM queue q
+        JOIN users u ON q.owner_id = u.id
+        {}{}
+        {}
+        "#,
+        MODERATION_QUEUE,
+        page.columns(),
+        MODERATION_FILTER,
+        page.conditions(5),
+        page.order_and_limit()
+    );
+    let query = sqlx::query(&sql)
+        .bind(item_type)
+        .bind(location)
+        .bind(min_age)
+        .bind(max_age);
+    let items = page.bind(query).fetch_all(pool.get_ref()).await?;
+    let (items, next_cursor) = page.finish(items);
+
+    let item_list: Vec<ModerationItem> = items.into_iter().map(|i| ModerationItem {
+        item_type: i.get::<String, _>("item_type"),
+        id: i.get::<i32, _>("id"),
+        name: i.get::<String, _>("name"),
+        location: i.get::<String, _>("location"),
+        submitted_at: i.get::<chrono::DateTime<chrono::Utc>, _>("submitted_at"),
+        resubmitted: i.try_get::<Option<bool>, _>("resubmitted").unwrap_or(None).unwrap_or(false),
+        owner: ModerationOwner {
+            id: i.get::<i32, _>("owner_id"),
+            name: i.try_get::<Option<String>, _>("owner_name").unwrap_or(None),
+            email: i.get::<String, _>("owner_email"),
+        },
+        details: i.get::<serde_json::Value, _>("details"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(item_list, next_cursor)))
+}
+
+pub async fn bulk_moderate(
+    pool: web::Data<PgPool>,
+    req: web::Json<BulkModerationRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let status = match req.action.as_str() {
+        "approve" => "approved",
+        "reject" => "rejected",
+        other => return Err(AppError::localized(StatusCode::BAD_REQUEST, "moderation.unknown_action").arg("action", other)),
+    };
+
+    let reason = req.reason.as_deref().map(str::trim).filter(|r| !r.is_empty());
+    if status == "rejected" && reason.is_none() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "admin.reason_required"));
+    }
+
+    if req.items.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "moderation.no_items"));
+    }
+    if req.items.len() > MAX_BULK_ITEMS {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "moderation.too_many_items").arg("max", MAX_BULK_ITEMS));
+    }
+
+    // Todo o nada: si algún elemento ya no está pendiente se revierte la operación completa
+    let mut tx = pool.begin().await?;
+
+    for item in &req.items {
+        let decided = match item.item_type.as_str() {
+            "hotel" => decide_hotel(&mut tx, item.id, status, user.id, reason).await?,
+            "business" => decide_business(&mut tx, item.id, status, user.id, reason).await?,
+            "review" => decide_review(&mut tx, item.id, status, user.id, reason).await?,
+            other => return Err(AppError::localized(StatusCode::BAD_REQUEST, "moderation.unknown_type").arg("type", other)),
+        };
+
+        if !decided {
+            return Err(AppError::localized(StatusCode::CONFLICT, "moderation.item_not_pending")
+                .arg("type", &item.item_type)
+                .arg("id", item.id));
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(BulkModerationResponse {
+        message: t("moderation.applied"),
+        status: status.to_string(),
+        processed: req.items.len(),
+    }))
+}
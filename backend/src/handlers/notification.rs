@@ -0,0 +1,128 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgConnection, PgPool, Row};
+use crate::{
+    models::{notification::{Notification, NotificationList}, MessageResponse, UserInfo},
+    utils::{errors::AppError, i18n::{negotiate, t, Locale}},
+};
+
+// Idioma en que se escriben las notificaciones de un usuario: su preferencia guardada, no la de
+// quien provoca el cambio
+pub(crate) async fn recipient_locale(conn: &mut PgConnection, user_id: i32) -> Result<Locale, AppError> {
+    let preference = sqlx::query("SELECT preferred_locale FROM users WHERE id = $1")
+        .bind(user_id)
+        .fetch_optional(conn)
+        .await?
+        .and_then(|row| row.get::<Option<String>, _>("preferred_locale"));
+
+    Ok(negotiate(preference.as_deref(), None))
+}
+
+// Crea una notificación dentro de la transacción del cambio que la origina
+pub(crate) async fn notify_user(
+    conn: &mut PgConnection,
+    user_id: i32,
+    kind: &str,
+    title: &str,
+    message: &str,
+    data: serde_json::Value,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO notifications (user_id, kind, title, message, data) VALUES ($1, $2, $3, $4, $5)"
+    )
+    .bind(user_id)
+    .bind(kind)
+    .bind(title)
+    .bind(message)
+    .bind(data)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+// Encola un correo ya traducido; lo envía un proceso aparte que lee email_outbox
+pub(crate) async fn queue_email(
+    conn: &mut PgConnection,
+    user_id: i32,
+    to: &str,
+    locale: Locale,
+    kind: &str,
+    subject: &str,
+    body: &str,
+) -> Result<(), AppError> {
+    sqlx::query(
+        "INSERT INTO email_outbox (user_id, to_email, locale, kind, subject, body) VALUES ($1, $2, $3, $4, $5, $6)"
+    )
+    .bind(user_id)
+    .bind(to)
+    .bind(locale.code())
+    .bind(kind)
+    .bind(subject)
+    .bind(body)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn get_my_notifications(
+    pool: web::Data<PgPool>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let notifications = sqlx::query(
+        r#"
+        SELECT id, kind, title, message, data, read_at, created_at
+        FROM notifications
+        WHERE user_id = $1
+        ORDER BY created_at DESC
+        LIMIT 100
+        "#
+    )
+    .bind(user.id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let mut unread = 0;
+    let notification_list: Vec<Notification> = notifications.into_iter().map(|n| {
+        let read_at = n.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("read_at").unwrap_or(None);
+        if read_at.is_none() {
+            unread += 1;
+        }
+        Notification {
+            id: n.get::<i32, _>("id"),
+            kind: n.get::<String, _>("kind"),
+            title: n.get::<String, _>("title"),
+            message: n.get::<String, _>("message"),
+            data: n.get::<serde_json::Value, _>("data"),
+            read_at,
+            created_at: n.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        }
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(NotificationList {
+        notifications: notification_list,
+        unread,
+    }))
+}
+
+pub async fn mark_notification_read(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let notification_id = path.into_inner();
+
+    let result = sqlx::query(
+        "UPDATE notifications SET read_at = COALESCE(read_at, NOW()) WHERE id = $1 AND user_id = $2"
+    )
+    .bind(notification_id)
+    .bind(user.id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "notification.not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("notification.marked_read") }))
+}
@@ -0,0 +1,726 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{
+        business::verify_business_ownership,
+        notification::{notify_user, recipient_locale},
+    },
+    models::{
+        MessageResponse, UserInfo,
+        order::{
+            AddCartItemRequest, BusinessOrderLine, CancelOrderRequest, CartItem, CartItemAddedResponse, CartResponse,
+            CheckoutRequest, CustomerOrder, OrderCreatedResponse, OrderDetail, OrderItem, OrderState,
+            OrderStateResponse, OrderSummary, UpdateCartItemRequest,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::{t, translate},
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
+};
+
+// Pedidos del cliente (GET /orders/my)
+pub struct MyOrders;
+
+impl Listing for MyOrders {
+    const KEY: &'static str = "o.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "o.created_at", Kind::Timestamp),
+        SortField::new("total_price", "o.total_price", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "o.status", Kind::Text),
+        Filter::any_of("payment_status", "o.payment_status", Kind::Text),
+    ];
+}
+
+// Líneas de pedido que debe atender un taller (GET /businesses/{id}/orders)
+pub struct BusinessOrders;
+
+impl Listing for BusinessOrders {
+    const KEY: &'static str = "oi.id";
+    const SORTS: &'static [SortField] = &[SortField::new("created_at", "o.created_at", Kind::Timestamp)];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "o.status", Kind::Text),
+        Filter::any_of("payment_status", "o.payment_status", Kind::Text),
+    ];
+}
+
+// Unidades máximas de una variante en el carrito; coincide con la validación de las peticiones
+const MAX_CART_QUANTITY: i32 = 50;
+
+pub async fn get_cart(
+    pool: web::Data<PgPool>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let items = sqlx::query(
+        r#"
+        SELECT c.variant_id, c.quantity, v.name AS variant_name, v.price::float8 AS price,
+               v.stock, v.is_active AND p.is_active AND b.status = 'approved' AS available,
+               p.id AS product_id, p.name AS product_name, b.name AS artisan,
+               (SELECT pi.image_url FROM product_images pi
+                WHERE pi.product_id = p.id
+                ORDER BY pi.display_order, pi.id
+                LIMIT 1) AS image_url
+        FROM cart_items c
+        JOIN product_variants v ON c.variant_id = v.id
+        JOIN products p ON v.product_id = p.id
+        JOIN businesses b ON p.business_id = b.id
+        WHERE c.user_id = $1
+        ORDER BY c.created_at
+        "#
+    )
+    .bind(user.id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let mut total = 0.0;
+    let item_list: Vec<CartItem> = items.into_iter().map(|i| {
+        let price = i.get::<f64, _>("price");
+        let quantity = i.get::<i32, _>("quantity");
+        total += price * quantity as f64;
+        CartItem {
+            variant_id: i.get::<i32, _>("variant_id"),
+            product_id: i.get::<i32, _>("product_id"),
+            product_name: i.get::<String, _>("product_name"),
+            variant_name: i.get::<String, _>("variant_name"),
+            artisan: i.get::<String, _>("artisan"),
+            image_url: i.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+            price,
+            quantity,
+            line_total: price * quantity as f64,
+            available: i.try_get::<Option<bool>, _>("available").unwrap_or(None).unwrap_or(false),
+            in_stock: i.get::<i32, _>("stock") >= quantity,
+        }
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(CartResponse { items: item_list, total }))
+}
+
+pub async fn add_cart_item(
+    pool: web::Data<PgPool>,
+    req: web::Json<AddCartItemRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let variant = sqlx::query(
+        r#"
+        SELECT v.stock, p.business_id
+        FROM product_variants v
+        JOIN products p ON v.product_id = p.id
+        JOIN businesses b ON p.business_id = b.id
+        WHERE v.id = $1 AND v.is_active AND p.is_active AND b.status = 'approved'
+        "#
+    )
+    .bind(req.variant_id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "order.product_unavailable"))?;
+
+    // Cada pedido lo confirma y cobra un solo taller, así que el carrito no mezcla artesanos
+    let other_business = sqlx::query(
+        r#"
+        SELECT 1
+        FROM cart_items c
+        JOIN product_variants v ON c.variant_id = v.id
+        JOIN products p ON v.product_id = p.id
+        WHERE c.user_id = $1 AND p.business_id != $2
+        LIMIT 1
+        "#
+    )
+    .bind(user.id)
+    .bind(variant.get::<i32, _>("business_id"))
+    .fetch_optional(pool.get_ref())
+    .await?;
+
+    if other_business.is_some() {
+        return Err(AppError::localized(StatusCode::CONFLICT, "order.cart_single_business"));
+    }
+
+    // Si la variante ya estaba en el carrito la suma no puede pasar del máximo
+    let result = sqlx::query(
+        r#"
+        INSERT INTO cart_items (user_id, variant_id, quantity)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (user_id, variant_id)
+        DO UPDATE SET quantity = cart_items.quantity + EXCLUDED.quantity, updated_at = NOW()
+        WHERE cart_items.quantity + EXCLUDED.quantity <= $4
+        RETURNING quantity
+        "#
+    )
+    .bind(user.id)
+    .bind(req.variant_id)
+    .bind(req.quantity)
+    .bind(MAX_CART_QUANTITY)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| {
+        AppError::localized(StatusCode::BAD_REQUEST, "order.cart_quantity_limit").arg("max", MAX_CART_QUANTITY)
+    })?;
+
+    let quantity = result.get::<i32, _>("quantity");
+
+    Ok(HttpResponse::Ok().json(CartItemAddedResponse {
+        message: t("order.cart_item_added"),
+        variant_id: req.variant_id,
+        quantity,
+        in_stock: variant.get::<i32, _>("stock") >= quantity,
+    }))
+}
+
+pub async fn update_cart_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<UpdateCartItemRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let variant_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let result = sqlx::query(
+        "UPDATE cart_items SET quantity = $1, updated_at = NOW() WHERE user_id = $2 AND variant_id = $3"
+    )
+    .bind(req.quantity)
+    .bind(user.id)
+    .bind(variant_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "order.cart_item_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("order.cart_updated") }))
+}
+
+pub async fn remove_cart_item(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let variant_id = path.into_inner();
+
+    let result = sqlx::query("DELETE FROM cart_items WHERE user_id = $1 AND variant_id = $2")
+        .bind(user.id)
+        .bind(variant_id)
+        .execute(pool.get_ref())
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "order.cart_item_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("order.cart_item_removed") }))
+}
+
+pub async fn clear_cart(
+    pool: web::Data<PgPool>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    sqlx::query("DELETE FROM cart_items WHERE user_id = $1")
+        .bind(user.id)
+        .execute(pool.get_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("order.cart_cleared") }))
+}
+
+pub async fn checkout(
+    pool: web::Data<PgPool>,
+    req: web::Json<CheckoutRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // Bloquear las variantes en orden de id para que dos compras simultáneas no se crucen
+    let items = sqlx::query(
+        r#"
+        SELECT c.variant_id, c.quantity, v.stock, v.name AS variant_name,
+               v.is_active AND p.is_active AND b.status = 'approved' AS available,
+               p.id AS product_id, p.name AS product_name, p.business_id
+        FROM cart_items c
+        JOIN product_variants v ON c.variant_id = v.id
+        JOIN products p ON v.product_id = p.id
+        JOIN businesses b ON p.business_id = b.id
+        WHERE c.user_id = $1
+        ORDER BY v.id
+        FOR UPDATE OF v
+        "#
+    )
+    .bind(user.id)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    if items.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "order.cart_empty"));
+    }
+
+    let business_id = items[0].get::<i32, _>("business_id");
+    if items.iter().any(|item| item.get::<i32, _>("business_id") != business_id) {
+        return Err(AppError::localized(StatusCode::CONFLICT, "order.cart_single_business"));
+    }
+
+    for item in &items {
+        let product_name = item.get::<String, _>("product_name");
+        if !item.try_get::<Option<bool>, _>("available").unwrap_or(None).unwrap_or(false) {
+            return Err(AppError::localized(StatusCode::CONFLICT, "order.product_no_longer_available").arg("product", product_name));
+        }
+        if item.get::<i32, _>("stock") < item.get::<i32, _>("quantity") {
+            return Err(AppError::localized(StatusCode::CONFLICT, "order.insufficient_stock").arg("product", product_name));
+        }
+    }
+
+    let order_reference = loop {
+        let reference = format!("MO{}", uuid::Uuid::new_v4().to_string().replace("-", "")[..6].to_uppercase());
+
+        let exists = sqlx::query("SELECT id FROM orders WHERE order_reference = $1")
+            .bind(&reference)
+            .fetch_optional(&mut *tx)
+            .await?;
+
+        if exists.is_none() {
+            break reference;
+        }
+    };
+
+    let order = sqlx::query(
+        r#"
+        INSERT INTO orders (user_id, order_reference, shipping_address, notes, status, payment_status)
+        VALUES ($1, $2, $3, $4, 'pending', 'pending')
+        RETURNING id, created_at
+        "#
+    )
+    .bind(user.id)
+    .bind(&order_reference)
+    .bind(&req.shipping_address)
+    .bind(req.notes.as_deref())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let order_id = order.get::<i32, _>("id");
+
+    for item in &items {
+        let variant_id = item.get::<i32, _>("variant_id");
+        let quantity = item.get::<i32, _>("quantity");
+
+        let updated = sqlx::query(
+            "UPDATE product_variants SET stock = stock - $1, updated_at = NOW() WHERE id = $2 AND stock >= $1"
+        )
+        .bind(quantity)
+        .bind(variant_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::localized(StatusCode::CONFLICT, "order.insufficient_stock")
+                .arg("product", item.get::<String, _>("product_name")));
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO order_items (order_id, product_id, variant_id, business_id,
+                                     product_name, variant_name, unit_price, quantity)
+            SELECT $1, $2, v.id, $3, $4, v.name, v.price, $5
+            FROM product_variants v
+            WHERE v.id = $6
+            "#
+        )
+        .bind(order_id)
+        .bind(item.get::<i32, _>("product_id"))
+        .bind(item.get::<i32, _>("business_id"))
+        .bind(item.get::<String, _>("product_name"))
+        .bind(quantity)
+        .bind(variant_id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    let totals = sqlx::query(
+        r#"
+        UPDATE orders
+        SET total_price = (SELECT COALESCE(SUM(line_total), 0) FROM order_items WHERE order_id = $1)
+        WHERE id = $1
+        RETURNING total_price::float8 AS total_price
+        "#
+    )
+    .bind(order_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query("DELETE FROM cart_items WHERE user_id = $1")
+        .bind(user.id)
+        .execute(&mut *tx)
+        .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(OrderCreatedResponse {
+        message: t("order.created"),
+        order: OrderSummary {
+            id: order_id,
+            reference: order_reference,
+            total_price: totals.get::<f64, _>("total_price"),
+            items: items.len(),
+            status: "pending".to_string(),
+            payment_status: "pending".to_string(),
+            created_at: order.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        },
+    }))
+}
+
+async fn load_order_items(pool: &PgPool, order_id: i32) -> Result<Vec<OrderItem>, AppError> {
+    let items = sqlx::query(
+        r#"
+        SELECT product_id, variant_id, product_name, variant_name,
+               unit_price::float8 AS unit_price, quantity, line_total::float8 AS line_total
+        FROM order_items
+        WHERE order_id = $1
+        ORDER BY id
+        "#
+    )
+    .bind(order_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(items.into_iter().map(|i| OrderItem {
+        product_id: i.try_get::<Option<i32>, _>("product_id").unwrap_or(None),
+        variant_id: i.try_get::<Option<i32>, _>("variant_id").unwrap_or(None),
+        product_name: i.get::<String, _>("product_name"),
+        variant_name: i.get::<String, _>("variant_name"),
+        unit_price: i.get::<f64, _>("unit_price"),
+        quantity: i.get::<i32, _>("quantity"),
+        line_total: i.get::<f64, _>("line_total"),
+    }).collect())
+}
+
+pub async fn get_my_orders(
+    pool: web::Data<PgPool>,
+    page: Page<MyOrders>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let sql = format!(
+        r#"
+        SELECT o.id, o.order_reference, o.total_price::float8 AS total_price, o.status,
+               o.payment_status, o.created_at,
+               (SELECT COALESCE(SUM(oi.quantity), 0) FROM order_items oi WHERE oi.order_id = o.id) AS item_count,
+               {}
+        FROM orders o
+        WHERE o.user_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let orders = page
+        .bind(sqlx::query(&sql).bind(user.id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (orders, next_cursor) = page.finish(orders);
+
+    let order_list: Vec<CustomerOrder> = orders.into_iter().map(|o| CustomerOrder {
+        id: o.get::<i32, _>("id"),
+        reference: o.get::<String, _>("order_reference"),
+        total_price: o.get::<f64, _>("total_price"),
+        status: o.get::<String, _>("status"),
+        payment_status: o.get::<String, _>("payment_status"),
+        item_count: o.try_get::<Option<i64>, _>("item_count").unwrap_or(None).unwrap_or(0),
+        created_at: o.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(order_list, next_cursor)))
+}
+
+pub async fn get_order_detail(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let order_id = path.into_inner();
+
+    let order = sqlx::query(
+        r#"
+        SELECT id, order_reference, total_price::float8 AS total_price, status, payment_status,
+               shipping_address, notes, created_at, cancelled_at, cancellation_reason
+        FROM orders
+        WHERE id = $1 AND user_id = $2
+        "#
+    )
+    .bind(order_id)
+    .bind(user.id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "order.not_found"))?;
+
+    let items = load_order_items(pool.get_ref(), order_id).await?;
+
+    Ok(HttpResponse::Ok().json(OrderDetail {
+        id: order.get::<i32, _>("id"),
+        reference: order.get::<String, _>("order_reference"),
+        total_price: order.get::<f64, _>("total_price"),
+        status: order.get::<String, _>("status"),
+        payment_status: order.get::<String, _>("payment_status"),
+        shipping_address: order.get::<String, _>("shipping_address"),
+        notes: order.try_get::<Option<String>, _>("notes").unwrap_or(None),
+        created_at: order.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        cancelled_at: order.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("cancelled_at").unwrap_or(None),
+        cancellation_reason: order.try_get::<Option<String>, _>("cancellation_reason").unwrap_or(None),
+        items,
+    }))
+}
+
+pub async fn cancel_order(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<CancelOrderRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let order_id = path.into_inner();
+
+    let mut tx = pool.begin().await?;
+
+    // Solo se cancelan pedidos pendientes que aún no se han pagado
+    let result = sqlx::query(
+        r#"
+        UPDATE orders
+        SET status = 'cancelled',
+            cancelled_at = CURRENT_TIMESTAMP,
+            cancellation_reason = $3,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND user_id = $2 AND status = 'pending' AND payment_status != 'paid'
+        RETURNING id
+        "#
+    )
+    .bind(order_id)
+    .bind(user.id)
+    .bind(req.cancellation_reason.as_deref())
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if result.is_none() {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "order.not_cancellable"));
+    }
+
+    // Devolver el inventario reservado
+    sqlx::query(
+        r#"
+        UPDATE product_variants v
+        SET stock = v.stock + oi.quantity, updated_at = NOW()
+        FROM order_items oi
+        WHERE oi.order_id = $1 AND oi.variant_id = v.id
+        "#
+    )
+    .bind(order_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("order.cancelled") }))
+}
+
+pub async fn get_business_orders(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    page: Page<BusinessOrders>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    if !verify_business_ownership(pool.get_ref(), business_id, &user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "order.business_orders_forbidden"));
+    }
+
+    let sql = format!(
+        r#"
+        SELECT o.id, o.order_reference, o.status, o.payment_status, o.shipping_address,
+               o.created_at, oi.product_name, oi.variant_name, oi.quantity,
+               oi.line_total::float8 AS line_total,
+               u.first_name || ' ' || u.last_name AS customer_name, u.email AS customer_email,
+               {}
+        FROM order_items oi
+        JOIN orders o ON oi.order_id = o.id
+        JOIN users u ON o.user_id = u.id
+        WHERE oi.business_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let rows = page
+        .bind(sqlx::query(&sql).bind(business_id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (rows, next_cursor) = page.finish(rows);
+
+    let lines: Vec<BusinessOrderLine> = rows.into_iter().map(|r| BusinessOrderLine {
+        order_id: r.get::<i32, _>("id"),
+        reference: r.get::<String, _>("order_reference"),
+        status: r.get::<String, _>("status"),
+        payment_status: r.get::<String, _>("payment_status"),
+        shipping_address: r.get::<String, _>("shipping_address"),
+        created_at: r.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        product_name: r.get::<String, _>("product_name"),
+        variant_name: r.get::<String, _>("variant_name"),
+        quantity: r.get::<i32, _>("quantity"),
+        line_total: r.get::<f64, _>("line_total"),
+        customer_name: r.try_get::<Option<String>, _>("customer_name").unwrap_or(None),
+        customer_email: r.get::<String, _>("customer_email"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(lines, next_cursor)))
+}
+
+// Pasos que el taller registra sobre un pedido: confirmarlo, anotar el pago y entregarlo
+#[derive(Clone, Copy)]
+enum OrderStep {
+    Confirm,
+    Payment,
+    Complete,
+}
+
+impl OrderStep {
+    // Clave del catálogo y tipo de notificación de cada paso
+    fn key(self) -> &'static str {
+        match self {
+            OrderStep::Confirm => "confirmed",
+            OrderStep::Payment => "paid",
+            OrderStep::Complete => "completed",
+        }
+    }
+
+    fn not_allowed(self) -> &'static str {
+        match self {
+            OrderStep::Confirm => "order.not_confirmable",
+            OrderStep::Payment => "order.not_payable",
+            OrderStep::Complete => "order.not_completable",
+        }
+    }
+
+    // Solo se confirma un pedido pendiente, el pago se anota una vez mientras el pedido
+    // sigue abierto y se entrega un pedido confirmado y pagado
+    fn allowed(self, status: &str, payment_status: &str) -> bool {
+        match self {
+            OrderStep::Confirm => status == "pending",
+            OrderStep::Payment => matches!(status, "pending" | "confirmed") && payment_status == "pending",
+            OrderStep::Complete => status == "confirmed" && payment_status == "paid",
+        }
+    }
+
+    fn next(self, status: String, payment_status: String) -> (String, String) {
+        match self {
+            OrderStep::Confirm => ("confirmed".to_string(), payment_status),
+            OrderStep::Payment => (status, "paid".to_string()),
+            OrderStep::Complete => ("completed".to_string(), payment_status),
+        }
+    }
+}
+
+async fn advance_order(
+    pool: &PgPool,
+    business_id: i32,
+    order_id: i32,
+    user: &UserInfo,
+    step: OrderStep,
+) -> Result<HttpResponse, AppError> {
+    if !verify_business_ownership(pool, business_id, user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "order.business_forbidden"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    // El pedido debe ser enteramente de este taller; se bloquea para que dos pasos no se crucen
+    let order = sqlx::query(
+        r#"
+        SELECT o.user_id, o.order_reference, o.status, o.payment_status
+        FROM orders o
+        WHERE o.id = $1
+          AND EXISTS (SELECT 1 FROM order_items oi WHERE oi.order_id = o.id AND oi.business_id = $2)
+          AND NOT EXISTS (SELECT 1 FROM order_items oi WHERE oi.order_id = o.id AND oi.business_id != $2)
+        FOR UPDATE
+        "#
+    )
+    .bind(order_id)
+    .bind(business_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "order.not_found"))?;
+
+    let status = order.get::<String, _>("status");
+    let payment_status = order.get::<String, _>("payment_status");
+    if !step.allowed(&status, &payment_status) {
+        return Err(AppError::localized(StatusCode::CONFLICT, step.not_allowed()));
+    }
+    let (status, payment_status) = step.next(status, payment_status);
+
+    sqlx::query("UPDATE orders SET status = $1, payment_status = $2, updated_at = NOW() WHERE id = $3")
+        .bind(&status)
+        .bind(&payment_status)
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let reference = order.get::<String, _>("order_reference");
+    let customer_id = order.get::<i32, _>("user_id");
+    let locale = recipient_locale(&mut tx, customer_id).await?;
+    let args = [("reference", reference.clone())];
+    notify_user(
+        &mut tx,
+        customer_id,
+        &format!("order_{}", step.key()),
+        &translate(locale, &format!("notification.order_{}.title", step.key()), &args),
+        &translate(locale, &format!("notification.order_{}.message", step.key()), &args),
+        serde_json::json!({ "order_id": order_id, "business_id": business_id }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(OrderStateResponse {
+        message: t(&format!("order.{}", step.key())),
+        order: OrderState { id: order_id, reference, status, payment_status },
+    }))
+}
+
+pub async fn confirm_order(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, order_id) = path.into_inner();
+    advance_order(pool.get_ref(), business_id, order_id, &user, OrderStep::Confirm).await
+}
+
+// Pago recibido por el taller (transferencia, efectivo o terminal propia)
+pub async fn mark_order_paid(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, order_id) = path.into_inner();
+    advance_order(pool.get_ref(), business_id, order_id, &user, OrderStep::Payment).await
+}
+
+pub async fn complete_order(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, order_id) = path.into_inner();
+    advance_order(pool.get_ref(), business_id, order_id, &user, OrderStep::Complete).await
+}
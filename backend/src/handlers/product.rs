@@ -0,0 +1,390 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::business::verify_business_ownership,
+    models::{
+        MessageResponse, UserInfo,
+        product::{
+            CreateProductRequest, ProductCreatedResponse, ProductDetail, ProductSummary, ProductVariant,
+            ProductVariantInput, PublicProduct, PublicProductQuery, UpdateProductRequest, VariantCreatedResponse,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
+};
+
+// Catálogo público de productos (GET /products)
+pub struct PublicProducts;
+
+impl Listing for PublicProducts {
+    const KEY: &'static str = "p.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "COALESCE(p.created_at, 'epoch')", Kind::Timestamp),
+        SortField::new("name", "p.name", Kind::Text),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[];
+    const MAX_LIMIT: i64 = 50;
+}
+
+async fn verify_artisan_owner(
+    pool: &PgPool,
+    business_id: i32,
+    user: &UserInfo,
+) -> Result<(), AppError> {
+    if !verify_business_ownership(pool, business_id, user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "product.forbidden"));
+    }
+
+    let business = sqlx::query("SELECT business_type FROM businesses WHERE id = $1")
+        .bind(business_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "business.not_found"))?;
+
+    if business.get::<String, _>("business_type") != "artisan" {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "product.artisan_only"));
+    }
+
+    Ok(())
+}
+
+// Verifica que el producto pertenezca a un negocio artesanal del usuario
+async fn verify_product_owner(
+    pool: &PgPool,
+    product_id: i32,
+    user: &UserInfo,
+) -> Result<(), AppError> {
+    let product = sqlx::query("SELECT business_id FROM products WHERE id = $1")
+        .bind(product_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "product.not_found"))?;
+
+    verify_artisan_owner(pool, product.get::<i32, _>("business_id"), user).await
+}
+
+fn validate_variant(variant: &ProductVariantInput) -> Result<(), AppError> {
+    variant.validate().map_err(AppError::Validation)
+}
+
+pub async fn get_public_products(
+    pool: web::Data<PgPool>,
+    query: web::Query<PublicProductQuery>,
+    page: Page<PublicProducts>,
+) -> Result<HttpResponse, AppError> {
+    let query = query.into_inner();
+
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let category = non_empty(query.category);
+    let text = non_empty(query.q);
+
+    let filter = r#"
+        WHERE p.is_active AND b.status = 'approved'
+          AND ($1::int IS NULL OR p.business_id = $1)
+          AND ($2::text IS NULL OR p.category = $2)
+          AND ($3::text IS NULL OR p.name ILIKE '%' || $3 || '%'
+                                OR p.description ILIKE '%' || $3 || '%'
+                                OR b.name ILIKE '%' || $3 || '%')
+    "#;
+
+    let sql = format!(
+        r#"
+        SELECT p.id, p.name, p.description, p.category, p.business_id, b.name AS artisan,
+               (SELECT MIN(v.price)::float8 FROM product_variants v
+                WHERE v.product_id = p.id AND v.is_active) AS price,
+               (SELECT COALESCE(SUM(v.stock), 0) FROM product_variants v
+                WHERE v.product_id = p.id AND v.is_active) AS stock,
+               (SELECT pi.image_url FROM product_images pi
+                WHERE pi.product_id = p.id
+                ORDER BY pi.display_order, pi.id
+                LIMIT 1) AS image_url,
+               {}
+        FROM products p
+        JOIN businesses b ON p.business_id = b.id
+        {}{}
+        {}
+        "#,
+        page.columns(),
+        filter,
+        page.conditions(4),
+        page.order_and_limit()
+    );
+    let query = sqlx::query(&sql)
+        .bind(query.business_id)
+        .bind(category.as_deref())
+        .bind(text.as_deref());
+    let products = page.bind(query).fetch_all(pool.get_ref()).await?;
+    let (products, next_cursor) = page.finish(products);
+
+    let product_list: Vec<PublicProduct> = products.into_iter().map(|p| PublicProduct {
+        id: p.get::<i32, _>("id"),
+        name: p.get::<String, _>("name"),
+        description: p.try_get::<Option<String>, _>("description").unwrap_or(None),
+        category: p.get::<String, _>("category"),
+        business_id: p.get::<i32, _>("business_id"),
+        artisan: p.get::<String, _>("artisan"),
+        price: p.try_get::<Option<f64>, _>("price").unwrap_or(None),
+        in_stock: p.try_get::<Option<i64>, _>("stock").unwrap_or(None).unwrap_or(0) > 0,
+        image_url: p.try_get::<Option<String>, _>("image_url").unwrap_or(None),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(product_list, next_cursor)))
+}
+
+pub async fn get_public_product_detail(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+) -> Result<HttpResponse, AppError> {
+    let product_id = path.into_inner();
+
+    let product = sqlx::query(
+        r#"
+        SELECT p.id, p.name, p.description, p.category, p.business_id, b.name AS artisan
+        FROM products p
+        JOIN businesses b ON p.business_id = b.id
+        WHERE p.id = $1 AND p.is_active AND b.status = 'approved'
+        "#
+    )
+    .bind(product_id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "product.not_found"))?;
+
+    let variants = sqlx::query(
+        r#"
+        SELECT id, sku, name, attributes, price::float8 AS price, stock
+        FROM product_variants
+        WHERE product_id = $1 AND is_active
+        ORDER BY price, id
+        "#
+    )
+    .bind(product_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let images = sqlx::query(
+        "SELECT image_url FROM product_images WHERE product_id = $1 ORDER BY display_order, id"
+    )
+    .bind(product_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let variant_list: Vec<ProductVariant> = variants.into_iter().map(|v| ProductVariant {
+        id: v.get::<i32, _>("id"),
+        sku: v.try_get::<Option<String>, _>("sku").unwrap_or(None),
+        name: v.get::<String, _>("name"),
+        attributes: v.get::<sqlx::types::JsonValue, _>("attributes"),
+        price: v.get::<f64, _>("price"),
+        stock: v.get::<i32, _>("stock"),
+    }).collect();
+
+    let image_list: Vec<String> = images.into_iter().map(|i| i.get::<String, _>("image_url")).collect();
+
+    Ok(HttpResponse::Ok().json(ProductDetail {
+        id: product.get::<i32, _>("id"),
+        name: product.get::<String, _>("name"),
+        description: product.try_get::<Option<String>, _>("description").unwrap_or(None),
+        category: product.get::<String, _>("category"),
+        business_id: product.get::<i32, _>("business_id"),
+        artisan: product.get::<String, _>("artisan"),
+        variants: variant_list,
+        images: image_list,
+    }))
+}
+
+pub async fn create_product(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<CreateProductRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_artisan_owner(pool.get_ref(), business_id, &user).await?;
+
+    let req = req.into_inner();
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+    if req.variants.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "product.variant_required"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let product = sqlx::query(
+        r#"
+        INSERT INTO products (business_id, name, description, category)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id, created_at
+        "#
+    )
+    .bind(business_id)
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(req.category.trim().to_lowercase())
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let product_id = product.get::<i32, _>("id");
+
+    for variant in &req.variants {
+        sqlx::query(
+            r#"
+            INSERT INTO product_variants (product_id, sku, name, attributes, price, stock, is_active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#
+        )
+        .bind(product_id)
+        .bind(variant.sku.as_deref())
+        .bind(&variant.name)
+        .bind(variant.attributes.clone().unwrap_or_else(|| serde_json::json!({})))
+        .bind(variant.price)
+        .bind(variant.stock)
+        .bind(variant.is_active.unwrap_or(true))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    for image in req.images.unwrap_or_default() {
+        sqlx::query(
+            "INSERT INTO product_images (product_id, image_url, display_order) VALUES ($1, $2, $3)"
+        )
+        .bind(product_id)
+        .bind(&image.image_url)
+        .bind(image.display_order)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(ProductCreatedResponse {
+        message: t("product.created"),
+        product: ProductSummary {
+            id: product_id,
+            name: req.name,
+            created_at: product.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        },
+    }))
+}
+
+pub async fn update_product(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<UpdateProductRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let product_id = path.into_inner();
+    verify_product_owner(pool.get_ref(), product_id, &user).await?;
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE products
+        SET name = $1, description = $2, category = $3, is_active = $4, updated_at = NOW()
+        WHERE id = $5
+        "#
+    )
+    .bind(&req.name)
+    .bind(req.description.as_deref())
+    .bind(req.category.trim().to_lowercase())
+    .bind(req.is_active.unwrap_or(true))
+    .bind(product_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("product.updated") }))
+}
+
+pub async fn delete_product(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let product_id = path.into_inner();
+    verify_product_owner(pool.get_ref(), product_id, &user).await?;
+
+    sqlx::query("DELETE FROM products WHERE id = $1")
+        .bind(product_id)
+        .execute(pool.get_ref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("product.deleted") }))
+}
+
+pub async fn add_product_variant(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<ProductVariantInput>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let product_id = path.into_inner();
+    verify_product_owner(pool.get_ref(), product_id, &user).await?;
+    validate_variant(&req)?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO product_variants (product_id, sku, name, attributes, price, stock, is_active)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id
+        "#
+    )
+    .bind(product_id)
+    .bind(req.sku.as_deref())
+    .bind(&req.name)
+    .bind(req.attributes.clone().unwrap_or_else(|| serde_json::json!({})))
+    .bind(req.price)
+    .bind(req.stock)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_one(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(VariantCreatedResponse {
+        message: t("product.variant_created"),
+        variant_id: result.get::<i32, _>("id"),
+    }))
+}
+
+pub async fn update_product_variant(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<ProductVariantInput>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (product_id, variant_id) = path.into_inner();
+    verify_product_owner(pool.get_ref(), product_id, &user).await?;
+    validate_variant(&req)?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE product_variants
+        SET sku = $1, name = $2, attributes = $3, price = $4, stock = $5,
+            is_active = $6, updated_at = NOW()
+        WHERE id = $7 AND product_id = $8
+        "#
+    )
+    .bind(req.sku.as_deref())
+    .bind(&req.name)
+    .bind(req.attributes.clone().unwrap_or_else(|| serde_json::json!({})))
+    .bind(req.price)
+    .bind(req.stock)
+    .bind(req.is_active.unwrap_or(true))
+    .bind(variant_id)
+    .bind(product_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "product.variant_not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("product.variant_updated") }))
+}
@@ -0,0 +1,526 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgConnection, PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{
+        admin::require_admin,
+        business::verify_business_ownership,
+        hotel::verify_hotel_ownership,
+    },
+    models::{
+        MessageResponse, UserInfo,
+        hotel::{ApproveNotes, RejectReason},
+        review::{
+            CreateReviewRequest, MyReview, PublicReview, PublicReviewPage, RatingDistribution, RatingSummary,
+            Review, ReviewCreatedResponse, ReviewReplyRequest, ReviewSummary,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::t,
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
+};
+
+// Reseñas escritas por el cliente (GET /reviews/my)
+pub struct MyReviews;
+
+impl Listing for MyReviews {
+    const KEY: &'static str = "r.id";
+    const SORTS: &'static [SortField] = &[SortField::new("created_at", "r.created_at", Kind::Timestamp)];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[Filter::any_of("status", "r.status", Kind::Text)];
+}
+
+// Reseñas publicadas de un hotel o negocio (GET /hotels/{id}/reviews, /businesses/{id}/reviews)
+pub struct PublicReviews;
+
+impl Listing for PublicReviews {
+    const KEY: &'static str = "r.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "r.created_at", Kind::Timestamp),
+        SortField::new("rating", "r.overall_rating", Kind::Integer),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::at_least("min_rating", "r.overall_rating", Kind::Integer),
+        Filter::at_most("max_rating", "r.overall_rating", Kind::Integer),
+    ];
+    const MAX_LIMIT: i64 = 50;
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ReviewTarget {
+    Hotel(i32),
+    Business(i32),
+}
+
+impl ReviewTarget {
+    fn from_row(row: &sqlx::postgres::PgRow) -> Option<Self> {
+        match (
+            row.try_get::<Option<i32>, _>("hotel_id").unwrap_or(None),
+            row.try_get::<Option<i32>, _>("business_id").unwrap_or(None),
+        ) {
+            (Some(hotel_id), _) => Some(ReviewTarget::Hotel(hotel_id)),
+            (None, Some(business_id)) => Some(ReviewTarget::Business(business_id)),
+            _ => None,
+        }
+    }
+}
+
+// Calificación pública y número de reseñas a partir de las reseñas aprobadas
+const RECOMPUTE_HOTEL_RATING: &str = r#"
+    UPDATE hotels h
+    SET rating = COALESCE((
+            SELECT ROUND(AVG(r.overall_rating)::numeric, 1) FROM reviews r
+            WHERE r.hotel_id = h.id AND r.status = 'approved'
+        ), 0),
+        review_count = (
+            SELECT COUNT(*)::int FROM reviews r
+            WHERE r.hotel_id = h.id AND r.status = 'approved'
+        )
+"#;
+
+const RECOMPUTE_BUSINESS_RATING: &str = r#"
+    UPDATE businesses b
+    SET rating = COALESCE((
+            SELECT ROUND(AVG(r.overall_rating)::numeric, 1) FROM reviews r
+            WHERE r.business_id = b.id AND r.status = 'approved'
+        ), 0),
+        review_count = (
+            SELECT COUNT(*)::int FROM reviews r
+            WHERE r.business_id = b.id AND r.status = 'approved'
+        )
+"#;
+
+async fn recompute_rating(conn: &mut PgConnection, target: ReviewTarget) -> Result<(), AppError> {
+    let (sql, id) = match target {
+        ReviewTarget::Hotel(id) => (format!("{} WHERE h.id = $1", RECOMPUTE_HOTEL_RATING), id),
+        ReviewTarget::Business(id) => (format!("{} WHERE b.id = $1", RECOMPUTE_BUSINESS_RATING), id),
+    };
+
+    sqlx::query(&sql).bind(id).execute(conn).await?;
+    Ok(())
+}
+
+// Recalcula todas las calificaciones; útil tras importar o corregir datos
+pub async fn recompute_all_ratings(pool: &PgPool) -> Result<u64, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let hotels = sqlx::query(RECOMPUTE_HOTEL_RATING).execute(&mut *tx).await?;
+    let businesses = sqlx::query(RECOMPUTE_BUSINESS_RATING).execute(&mut *tx).await?;
+
+    tx.commit().await?;
+
+    Ok(hotels.rows_affected() + businesses.rows_affected())
+}
+
+// Cambia el estado de moderación de una reseña y recalcula la calificación.
+// Devuelve false si la reseña no existe o ya tenía ese estado.
+pub(crate) async fn decide_review(
+    conn: &mut PgConnection,
+    review_id: i32,
+    status: &str,
+    admin_id: i32,
+    notes: Option<&str>,
+) -> Result<bool, AppError> {
+    let review = sqlx::query(
+        r#"
+        UPDATE reviews
+        SET status = $2, moderated_by = $3, moderated_at = NOW(), moderation_notes = $4,
+            updated_at = NOW()
+        WHERE id = $1 AND status != $2
+        RETURNING hotel_id, business_id
+        "#
+    )
+    .bind(review_id)
+    .bind(status)
+    .bind(admin_id)
+    .bind(notes)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    match review.as_ref().and_then(ReviewTarget::from_row) {
+        Some(target) => {
+            recompute_rating(conn, target).await?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn review_from_row(r: &sqlx::postgres::PgRow) -> Review {
+    Review {
+        id: r.get::<i32, _>("id"),
+        overall_rating: r.get::<i16, _>("overall_rating"),
+        cleanliness_rating: r.try_get::<Option<i16>, _>("cleanliness_rating").unwrap_or(None),
+        service_rating: r.try_get::<Option<i16>, _>("service_rating").unwrap_or(None),
+        location_rating: r.try_get::<Option<i16>, _>("location_rating").unwrap_or(None),
+        value_rating: r.try_get::<Option<i16>, _>("value_rating").unwrap_or(None),
+        title: r.try_get::<Option<String>, _>("title").unwrap_or(None),
+        comment: r.get::<String, _>("comment"),
+        owner_reply: r.try_get::<Option<String>, _>("owner_reply").unwrap_or(None),
+        owner_replied_at: r.try_get::<Option<chrono::DateTime<chrono::Utc>>, _>("owner_replied_at").unwrap_or(None),
+        created_at: r.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+    }
+}
+
+pub async fn create_review(
+    pool: web::Data<PgPool>,
+    req: web::Json<CreateReviewRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let pool = pool.get_ref();
+
+    // Una estancia cuenta como completada cuando el hotel la cierra, o cuando pasó el check-out de
+    // un huésped que sí registró su llegada
+    let target = match (req.booking_id, req.order_id, req.business_id, req.transport_booking_id) {
+        (Some(booking_id), None, None, None) => {
+            let booking = sqlx::query(
+                r#"
+                SELECT hotel_id FROM bookings
+                WHERE id = $1 AND user_id = $2
+                  AND (status = 'completed' OR (status = 'checked_in' AND check_out <= CURRENT_DATE))
+                "#
+            )
+            .bind(booking_id)
+            .bind(user.id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "review.stay_not_completed"))?;
+
+            ReviewTarget::Hotel(booking.get::<i32, _>("hotel_id"))
+        }
+        (None, Some(order_id), Some(business_id), None) => {
+            // Solo un pedido que el taller marcó como entregado
+            let purchase = sqlx::query(
+                r#"
+                SELECT 1 AS found
+                FROM orders o
+                JOIN order_items oi ON oi.order_id = o.id
+                WHERE o.id = $1 AND o.user_id = $2 AND oi.business_id = $3
+                  AND o.status = 'completed'
+                LIMIT 1
+                "#
+            )
+            .bind(order_id)
+            .bind(user.id)
+            .bind(business_id)
+            .fetch_optional(pool)
+            .await?;
+
+            if purchase.is_none() {
+                return Err(AppError::localized(StatusCode::BAD_REQUEST, "review.order_not_completed"));
+            }
+
+            ReviewTarget::Business(business_id)
+        }
+        (None, None, None, Some(transport_booking_id)) => {
+            let trip = sqlx::query(
+                r#"
+                SELECT r.business_id
+                FROM transport_bookings tb
+                JOIN transport_trips t ON tb.trip_id = t.id
+                JOIN transport_routes r ON t.route_id = r.id
+                WHERE tb.id = $1 AND tb.user_id = $2
+                  AND (tb.status = 'completed' OR (tb.status = 'checked_in' AND t.arrival_at <= NOW()))
+                "#
+            )
+            .bind(transport_booking_id)
+            .bind(user.id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "review.transfer_not_completed"))?;
+
+            ReviewTarget::Business(trip.get::<i32, _>("business_id"))
+        }
+        _ => {
+            return Err(AppError::localized(StatusCode::BAD_REQUEST, "review.target_required"));
+        }
+    };
+
+    let (hotel_id, business_id) = match target {
+        ReviewTarget::Hotel(id) => (Some(id), None),
+        ReviewTarget::Business(id) => (None, Some(id)),
+    };
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO reviews (user_id, hotel_id, business_id, booking_id, order_id, transport_booking_id,
+                             overall_rating, cleanliness_rating, service_rating, location_rating,
+                             value_rating, title, comment)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        RETURNING id, status, created_at
+        "#
+    )
+    .bind(user.id)
+    .bind(hotel_id)
+    .bind(business_id)
+    .bind(req.booking_id)
+    .bind(req.order_id)
+    .bind(req.transport_booking_id)
+    .bind(req.overall_rating)
+    .bind(req.cleanliness_rating)
+    .bind(req.service_rating)
+    .bind(req.location_rating)
+    .bind(req.value_rating)
+    .bind(req.title.as_deref().map(str::trim).filter(|t| !t.is_empty()))
+    .bind(req.comment.trim())
+    .fetch_one(pool)
+    .await;
+
+    let review = match result {
+        Ok(review) => review,
+        Err(sqlx::Error::Database(e)) if e.code().as_deref() == Some("23505") => {
+            return Err(AppError::localized(StatusCode::CONFLICT, "review.already_reviewed"));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(HttpResponse::Created().json(ReviewCreatedResponse {
+        message: t("review.submitted"),
+        review: ReviewSummary {
+            id: review.get::<i32, _>("id"),
+            status: review.get::<String, _>("status"),
+            created_at: review.get::<chrono::DateTime<chrono::Utc>, _>("created_at"),
+        },
+    }))
+}
+
+pub async fn get_my_reviews(
+    pool: web::Data<PgPool>,
+    page: Page<MyReviews>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let sql = format!(
+        r#"
+        SELECT r.*, COALESCE(h.name, b.name) AS listing_name, {}
+        FROM reviews r
+        LEFT JOIN hotels h ON r.hotel_id = h.id
+        LEFT JOIN businesses b ON r.business_id = b.id
+        WHERE r.user_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let reviews = page
+        .bind(sqlx::query(&sql).bind(user.id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (reviews, next_cursor) = page.finish(reviews);
+
+    let review_list: Vec<MyReview> = reviews.iter().map(|r| MyReview {
+        review: review_from_row(r),
+        hotel_id: r.try_get::<Option<i32>, _>("hotel_id").unwrap_or(None),
+        business_id: r.try_get::<Option<i32>, _>("business_id").unwrap_or(None),
+        listing_name: r.try_get::<Option<String>, _>("listing_name").unwrap_or(None),
+        status: r.get::<String, _>("status"),
+        moderation_notes: r.try_get::<Option<String>, _>("moderation_notes").unwrap_or(None),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(review_list, next_cursor)))
+}
+
+// Reseñas aprobadas de un hotel o negocio con el resumen de calificaciones
+async fn public_reviews(
+    pool: &PgPool,
+    target: ReviewTarget,
+    page: &Page<PublicReviews>,
+) -> Result<PublicReviewPage, AppError> {
+    let (column, listing_id) = match target {
+        ReviewTarget::Hotel(id) => ("hotel_id", id),
+        ReviewTarget::Business(id) => ("business_id", id),
+    };
+
+    let summary = sqlx::query(&format!(
+        r#"
+        SELECT COUNT(*) AS review_count,
+               ROUND(AVG(overall_rating)::numeric, 1)::float8 AS overall,
+               ROUND(AVG(cleanliness_rating)::numeric, 1)::float8 AS cleanliness,
+               ROUND(AVG(service_rating)::numeric, 1)::float8 AS service,
+               ROUND(AVG(location_rating)::numeric, 1)::float8 AS location,
+               ROUND(AVG(value_rating)::numeric, 1)::float8 AS value,
+               COUNT(*) FILTER (WHERE overall_rating = 5) AS stars_5,
+               COUNT(*) FILTER (WHERE overall_rating = 4) AS stars_4,
+               COUNT(*) FILTER (WHERE overall_rating = 3) AS stars_3,
+               COUNT(*) FILTER (WHERE overall_rating = 2) AS stars_2,
+               COUNT(*) FILTER (WHERE overall_rating = 1) AS stars_1
+        FROM reviews
+        WHERE {} = $1 AND status = 'approved'
+        "#,
+        column
+    ))
+    .bind(listing_id)
+    .fetch_one(pool)
+    .await?;
+
+    let sql = format!(
+        r#"
+        SELECT r.id, r.overall_rating, r.cleanliness_rating, r.service_rating, r.location_rating,
+               r.value_rating, r.title, r.comment, r.owner_reply, r.owner_replied_at, r.created_at,
+               TRIM(COALESCE(u.first_name, '') || ' ' || COALESCE(LEFT(u.last_name, 1) || '.', '')) AS reviewer,
+               {}
+        FROM reviews r
+        JOIN users u ON r.user_id = u.id
+        WHERE r.{} = $1 AND r.status = 'approved'{}
+        {}
+        "#,
+        page.columns(),
+        column,
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let reviews = page.bind(sqlx::query(&sql).bind(listing_id)).fetch_all(pool).await?;
+    let (reviews, next_cursor) = page.finish(reviews);
+
+    let review_list: Vec<PublicReview> = reviews.iter().map(|r| PublicReview {
+        review: review_from_row(r),
+        reviewer: r.try_get::<Option<String>, _>("reviewer").unwrap_or(None),
+    }).collect();
+
+    let avg = |name: &str| summary.try_get::<Option<f64>, _>(name).unwrap_or(None);
+
+    Ok(PublicReviewPage {
+        summary: RatingSummary {
+            review_count: summary.get::<i64, _>("review_count"),
+            overall: avg("overall"),
+            cleanliness: avg("cleanliness"),
+            service: avg("service"),
+            location: avg("location"),
+            value: avg("value"),
+            distribution: RatingDistribution {
+                five: summary.get::<i64, _>("stars_5"),
+                four: summary.get::<i64, _>("stars_4"),
+                three: summary.get::<i64, _>("stars_3"),
+                two: summary.get::<i64, _>("stars_2"),
+                one: summary.get::<i64, _>("stars_1"),
+            },
+        },
+        page: page.response(review_list, next_cursor),
+    })
+}
+
+pub async fn get_hotel_reviews(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    page: Page<PublicReviews>,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+
+    sqlx::query("SELECT id FROM hotels WHERE id = $1 AND status = 'approved'")
+        .bind(hotel_id)
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "hotel.not_found"))?;
+
+    let reviews = public_reviews(pool.get_ref(), ReviewTarget::Hotel(hotel_id), &page).await?;
+    Ok(HttpResponse::Ok().json(reviews))
+}
+
+pub async fn get_business_reviews(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    page: Page<PublicReviews>,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    sqlx::query("SELECT id FROM businesses WHERE id = $1 AND status = 'approved'")
+        .bind(business_id)
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "business.not_found"))?;
+
+    let reviews = public_reviews(pool.get_ref(), ReviewTarget::Business(business_id), &page).await?;
+    Ok(HttpResponse::Ok().json(reviews))
+}
+
+pub async fn reply_to_review(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<ReviewReplyRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let review_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let review = sqlx::query("SELECT hotel_id, business_id FROM reviews WHERE id = $1")
+        .bind(review_id)
+        .fetch_optional(pool.get_ref())
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "review.not_found"))?;
+
+    let allowed = match ReviewTarget::from_row(&review) {
+        Some(ReviewTarget::Hotel(id)) => verify_hotel_ownership(pool.get_ref(), id, &user).await?,
+        Some(ReviewTarget::Business(id)) => verify_business_ownership(pool.get_ref(), id, &user).await?,
+        None => false,
+    };
+
+    if !allowed {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "review.reply_forbidden"));
+    }
+
+    sqlx::query(
+        "UPDATE reviews SET owner_reply = $1, owner_replied_at = NOW(), updated_at = NOW() WHERE id = $2"
+    )
+    .bind(req.reply.trim())
+    .bind(review_id)
+    .execute(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("review.reply_published") }))
+}
+
+pub async fn approve_review(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<ApproveNotes>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    let review_id = path.into_inner();
+    let notes = req
+        .and_then(|r| r.into_inner().admin_notes)
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+
+    let mut tx = pool.begin().await?;
+    if !decide_review(&mut tx, review_id, "approved", user.id, notes.as_deref()).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "review.not_approvable"));
+    }
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("review.approved") }))
+}
+
+pub async fn reject_review(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<RejectReason>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    let review_id = path.into_inner();
+    let reason = req.reason.trim();
+
+    if reason.is_empty() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "admin.reason_required"));
+    }
+
+    // Rechazar una reseña ya publicada la retira y recalcula la calificación
+    let mut tx = pool.begin().await?;
+    if !decide_review(&mut tx, review_id, "rejected", user.id, Some(reason)).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "review.not_rejectable"));
+    }
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("review.rejected") }))
+}
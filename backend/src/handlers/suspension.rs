@@ -0,0 +1,342 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, Utc};
+use sqlx::{PgConnection, PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{admin::require_admin, notification::{notify_user, recipient_locale}},
+    models::{
+        MessageResponse, UserInfo,
+        suspension::{
+            ActiveSuspension, ReinstateListingRequest, SuspendListingRequest, Suspension, SuspensionList,
+            SuspensionResponse,
+        },
+    },
+    utils::{errors::AppError, i18n::{t, t_args, translate}},
+};
+
+// Cada cuánto revisa el servidor las suspensiones vencidas
+pub const SUSPENSION_CHECK_INTERVAL_SECS: u64 = 300;
+
+#[derive(Debug, Clone, Copy)]
+enum ListingKind {
+    Hotel,
+    Business,
+}
+
+impl ListingKind {
+    fn from_str(kind: &str) -> Option<Self> {
+        match kind {
+            "hotel" => Some(ListingKind::Hotel),
+            "business" => Some(ListingKind::Business),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            ListingKind::Hotel => "hotel",
+            ListingKind::Business => "business",
+        }
+    }
+
+    fn table(self) -> &'static str {
+        match self {
+            ListingKind::Hotel => "hotels",
+            ListingKind::Business => "businesses",
+        }
+    }
+
+    // Nombre de la publicación dentro de los mensajes ("hotel", "negocio")
+    fn label_key(self) -> &'static str {
+        match self {
+            ListingKind::Hotel => "suspension.hotel",
+            ListingKind::Business => "suspension.business",
+        }
+    }
+}
+
+async fn suspend_listing(
+    pool: &PgPool,
+    kind: ListingKind,
+    listing_id: i32,
+    req: &SuspendListingRequest,
+    admin: &UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(admin)?;
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    if matches!(req.until, Some(until) if until <= Utc::now()) {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "suspension.end_in_past"));
+    }
+
+    let reason = req.reason.trim();
+    let mut tx = pool.begin().await?;
+
+    let listing = sqlx::query(&format!(
+        "SELECT owner_id, name, status FROM {} WHERE id = $1 FOR UPDATE",
+        kind.table()
+    ))
+    .bind(listing_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "suspension.not_found").arg("listing", t(kind.label_key())))?;
+
+    match listing.get::<String, _>("status").as_str() {
+        "approved" => {}
+        "suspended" => return Err(AppError::localized(StatusCode::CONFLICT, "suspension.already_suspended").arg("listing", t(kind.label_key()))),
+        _ => return Err(AppError::localized(StatusCode::CONFLICT, "suspension.not_approved")),
+    }
+
+    sqlx::query(&format!(
+        "UPDATE {} SET status = 'suspended', updated_at = NOW() WHERE id = $1",
+        kind.table()
+    ))
+    .bind(listing_id)
+    .execute(&mut *tx)
+    .await?;
+
+    let suspension = sqlx::query(
+        r#"
+        INSERT INTO listing_suspensions (listing_type, listing_id, reason, suspended_by, ends_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id, suspended_at
+        "#
+    )
+    .bind(kind.as_str())
+    .bind(listing_id)
+    .bind(reason)
+    .bind(admin.id)
+    .bind(req.until)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let owner_id = listing.get::<i32, _>("owner_id");
+    let locale = recipient_locale(&mut tx, owner_id).await?;
+    let mut args = vec![
+        ("listing", translate(locale, kind.label_key(), &[])),
+        ("name", listing.get::<String, _>("name")),
+        ("reason", reason.to_string()),
+    ];
+    let message_key = match req.until {
+        Some(until) => {
+            args.push(("until", until.format("%Y-%m-%d %H:%M UTC").to_string()));
+            "notification.listing_suspended.message_until"
+        }
+        None => "notification.listing_suspended.message",
+    };
+
+    notify_user(
+        &mut tx,
+        owner_id,
+        "listing_suspended",
+        &translate(locale, "notification.listing_suspended.title", &args),
+        &translate(locale, message_key, &args),
+        serde_json::json!({
+            "listing_type": kind.as_str(),
+            "listing_id": listing_id,
+            "until": req.until
+        }),
+    ).await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(SuspensionResponse {
+        message: t_args("suspension.suspended", &[("listing", t(kind.label_key()))]),
+        suspension: Suspension {
+            id: suspension.get::<i32, _>("id"),
+            listing_type: kind.as_str().to_string(),
+            listing_id,
+            reason: reason.to_string(),
+            suspended_at: suspension.get::<DateTime<Utc>, _>("suspended_at"),
+            ends_at: req.until,
+        },
+    }))
+}
+
+// Levanta la suspensión activa y devuelve la publicación a "approved".
+// Devuelve false si no había una suspensión activa.
+async fn lift_suspension(
+    conn: &mut PgConnection,
+    kind: ListingKind,
+    listing_id: i32,
+    lifted_by: Option<i32>,
+    lift_reason: &str,
+) -> Result<bool, AppError> {
+    let lifted = sqlx::query(
+        r#"
+        UPDATE listing_suspensions
+        SET lifted_at = NOW(), lifted_by = $3, lift_reason = $4
+        WHERE listing_type = $1 AND listing_id = $2 AND lifted_at IS NULL
+        RETURNING id
+        "#
+    )
+    .bind(kind.as_str())
+    .bind(listing_id)
+    .bind(lifted_by)
+    .bind(lift_reason)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if lifted.is_none() {
+        return Ok(false);
+    }
+
+    let listing = sqlx::query(&format!(
+        "UPDATE {} SET status = 'approved', updated_at = NOW() WHERE id = $1 AND status = 'suspended' RETURNING owner_id, name",
+        kind.table()
+    ))
+    .bind(listing_id)
+    .fetch_optional(&mut *conn)
+    .await?;
+
+    if let Some(listing) = listing {
+        let owner_id = listing.get::<i32, _>("owner_id");
+        let locale = recipient_locale(&mut *conn, owner_id).await?;
+        let args = [
+            ("listing", translate(locale, kind.label_key(), &[])),
+            ("name", listing.get::<String, _>("name")),
+        ];
+        notify_user(
+            conn,
+            owner_id,
+            "listing_reinstated",
+            &translate(locale, "notification.listing_reinstated.title", &args),
+            &translate(locale, "notification.listing_reinstated.message", &args),
+            serde_json::json!({
+                "listing_type": kind.as_str(),
+                "listing_id": listing_id
+            }),
+        ).await?;
+    }
+
+    Ok(true)
+}
+
+async fn reinstate_listing(
+    pool: &PgPool,
+    kind: ListingKind,
+    listing_id: i32,
+    req: Option<ReinstateListingRequest>,
+    admin: &UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(admin)?;
+
+    let notes = req.and_then(|r| r.notes).map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    let mut tx = pool.begin().await?;
+
+    if !lift_suspension(&mut tx, kind, listing_id, Some(admin.id), notes.as_deref().unwrap_or("manual")).await? {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "suspension.not_suspended").arg("listing", t(kind.label_key())));
+    }
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse {
+        message: t_args("suspension.reinstated", &[("listing", t(kind.label_key()))]),
+    }))
+}
+
+// Levanta las suspensiones cuyo plazo ya venció. La ejecuta la tarea periódica de main.
+pub async fn reinstate_expired_suspensions(pool: &PgPool) -> Result<u64, AppError> {
+    let mut tx = pool.begin().await?;
+
+    let expired = sqlx::query(
+        r#"
+        SELECT listing_type, listing_id
+        FROM listing_suspensions
+        WHERE lifted_at IS NULL AND ends_at IS NOT NULL AND ends_at <= NOW()
+        ORDER BY ends_at
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut reinstated = 0;
+    for row in expired {
+        let Some(kind) = ListingKind::from_str(&row.get::<String, _>("listing_type")) else {
+            continue;
+        };
+        if lift_suspension(&mut tx, kind, row.get::<i32, _>("listing_id"), None, "expired").await? {
+            reinstated += 1;
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(reinstated)
+}
+
+pub async fn suspend_hotel(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<SuspendListingRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    suspend_listing(pool.get_ref(), ListingKind::Hotel, path.into_inner(), &req, &user).await
+}
+
+pub async fn reinstate_hotel(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<ReinstateListingRequest>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    reinstate_listing(pool.get_ref(), ListingKind::Hotel, path.into_inner(), req.map(|r| r.into_inner()), &user).await
+}
+
+pub async fn suspend_business(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<SuspendListingRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    suspend_listing(pool.get_ref(), ListingKind::Business, path.into_inner(), &req, &user).await
+}
+
+pub async fn reinstate_business(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: Option<web::Json<ReinstateListingRequest>>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    reinstate_listing(pool.get_ref(), ListingKind::Business, path.into_inner(), req.map(|r| r.into_inner()), &user).await
+}
+
+pub async fn get_active_suspensions(
+    pool: web::Data<PgPool>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+
+    let suspensions = sqlx::query(
+        r#"
+        SELECT s.id, s.listing_type, s.listing_id, s.reason, s.suspended_at, s.ends_at,
+               COALESCE(h.name, b.name) AS listing_name,
+               u.first_name || ' ' || u.last_name AS suspended_by_name
+        FROM listing_suspensions s
+        LEFT JOIN hotels h ON s.listing_type = 'hotel' AND h.id = s.listing_id
+        LEFT JOIN businesses b ON s.listing_type = 'business' AND b.id = s.listing_id
+        LEFT JOIN users u ON s.suspended_by = u.id
+        WHERE s.lifted_at IS NULL
+        ORDER BY s.suspended_at DESC
+        "#
+    )
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let suspension_list: Vec<ActiveSuspension> = suspensions.into_iter().map(|s| ActiveSuspension {
+        id: s.get::<i32, _>("id"),
+        listing_type: s.get::<String, _>("listing_type"),
+        listing_id: s.get::<i32, _>("listing_id"),
+        listing_name: s.try_get::<Option<String>, _>("listing_name").unwrap_or(None),
+        reason: s.get::<String, _>("reason"),
+        suspended_at: s.get::<DateTime<Utc>, _>("suspended_at"),
+        ends_at: s.try_get::<Option<DateTime<Utc>>, _>("ends_at").unwrap_or(None),
+        suspended_by: s.try_get::<Option<String>, _>("suspended_by_name").unwrap_or(None),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(SuspensionList { suspensions: suspension_list }))
+}
@@ -0,0 +1,517 @@
+// Traducciones del contenido de hoteles, negocios y platillos (tabla listing_translations).
+// Los propietarios guardan una traducción por idioma; los endpoints públicos sirven la mejor
+// versión de cada campo según utils::i18n::best_match.
+use std::collections::HashMap;
+
+use actix_web::{http::StatusCode, web, HttpResponse};
+use sqlx::{PgPool, Row};
+use validator::Validate;
+use crate::{
+    handlers::{admin::require_admin, business::verify_business_ownership, hotel::{return_to_review, verify_hotel_ownership}},
+    middleware::locale::current_locale,
+    models::{
+        MessageResponse, UserInfo,
+        translation::{
+            ListingTranslation, ListingTranslations, MissingTranslation, MissingTranslations,
+            MissingTranslationsQuery, TranslationRequest, TranslationSavedResponse,
+        },
+    },
+    utils::{errors::AppError, i18n::{best_match, t, Locale, DEFAULT_LOCALE}},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ListingKind {
+    Hotel,
+    Business,
+    MenuItem,
+}
+
+impl ListingKind {
+    const ALL: [ListingKind; 3] = [ListingKind::Hotel, ListingKind::Business, ListingKind::MenuItem];
+
+    fn code(self) -> &'static str {
+        match self {
+            ListingKind::Hotel => "hotel",
+            ListingKind::Business => "business",
+            ListingKind::MenuItem => "menu_item",
+        }
+    }
+
+    // Columna de listing_translations que apunta a la publicación
+    fn column(self) -> &'static str {
+        match self {
+            ListingKind::Hotel => "hotel_id",
+            ListingKind::Business => "business_id",
+            ListingKind::MenuItem => "menu_item_id",
+        }
+    }
+
+    fn parse(code: &str) -> Option<ListingKind> {
+        Self::ALL.into_iter().find(|kind| kind.code() == code)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Translation {
+    pub locale: Locale,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub amenities: Option<Vec<String>>,
+}
+
+// Contenido de una publicación en el idioma de la petición. `locale` es el idioma en que se sirve
+// la descripción (o el nombre si no hay descripción).
+pub(crate) struct LocalizedContent {
+    pub name: String,
+    pub description: Option<String>,
+    pub amenities: Vec<String>,
+    pub locale: Locale,
+}
+
+// Idioma del contenido original de una publicación; los valores desconocidos cuentan como español
+pub(crate) fn source_locale(code: &str) -> Locale {
+    Locale::parse(code).unwrap_or(DEFAULT_LOCALE)
+}
+
+// Idioma declarado por el propietario al crear o editar una publicación
+pub(crate) fn requested_content_locale(value: Option<&str>) -> Result<Option<Locale>, AppError> {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        None => Ok(None),
+        Some(code) => Locale::parse(code)
+            .map(Some)
+            .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "unsupported_locale").arg("locale", code)),
+    }
+}
+
+// Lista de amenidades sin espacios sobrantes, vacíos ni duplicados
+pub(crate) fn normalize_amenities(amenities: Option<Vec<String>>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for amenity in amenities.unwrap_or_default() {
+        let amenity = amenity.trim().to_string();
+        if !amenity.is_empty() && !normalized.contains(&amenity) {
+            normalized.push(amenity);
+        }
+    }
+    normalized
+}
+
+pub(crate) fn localize(
+    source: Locale,
+    name: String,
+    description: Option<String>,
+    amenities: Vec<String>,
+    translations: &[Translation],
+) -> LocalizedContent {
+    let requested = current_locale();
+    let find = |locale: Locale| translations.iter().find(|t| t.locale == locale);
+
+    let (name, name_locale) = best_match(requested, source, Some(&name), |l| find(l).and_then(|t| t.name.clone()));
+    let (description, description_locale) =
+        best_match(requested, source, description.as_ref(), |l| find(l).and_then(|t| t.description.clone()));
+    let original_amenities = (!amenities.is_empty()).then_some(&amenities);
+    let (localized_amenities, _) =
+        best_match(requested, source, original_amenities, |l| find(l).and_then(|t| t.amenities.clone()));
+
+    LocalizedContent {
+        name: name.unwrap_or_default(),
+        locale: if description.is_some() { description_locale } else { name_locale },
+        description,
+        amenities: localized_amenities.unwrap_or_default(),
+    }
+}
+
+// Traducciones de varias publicaciones del mismo tipo, agrupadas por id
+pub(crate) async fn load_translations(
+    pool: &PgPool,
+    kind: ListingKind,
+    ids: &[i32],
+) -> Result<HashMap<i32, Vec<Translation>>, AppError> {
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows = sqlx::query(&format!(
+        "SELECT {column} AS listing_id, locale, name, description, amenities
+         FROM listing_translations
+         WHERE {column} = ANY($1)",
+        column = kind.column()
+    ))
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+
+    let mut grouped: HashMap<i32, Vec<Translation>> = HashMap::new();
+    for row in rows {
+        let Some(locale) = Locale::parse(&row.get::<String, _>("locale")) else { continue };
+        grouped.entry(row.get::<i32, _>("listing_id")).or_default().push(Translation {
+            locale,
+            name: row.try_get("name").unwrap_or(None),
+            description: row.try_get("description").unwrap_or(None),
+            amenities: row.try_get("amenities").unwrap_or(None),
+        });
+    }
+    Ok(grouped)
+}
+
+// Comprueba que el usuario puede traducir la publicación y devuelve el idioma de su contenido original.
+// Los platillos se identifican con (negocio, platillo).
+async fn authorize(
+    pool: &PgPool,
+    kind: ListingKind,
+    business_id: i32,
+    listing_id: i32,
+    user: &UserInfo,
+) -> Result<Locale, AppError> {
+    let allowed = match kind {
+        ListingKind::Hotel => verify_hotel_ownership(pool, listing_id, user).await?,
+        ListingKind::Business | ListingKind::MenuItem => verify_business_ownership(pool, business_id, user).await?,
+    };
+    if !allowed {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "translation.forbidden"));
+    }
+
+    let row = match kind {
+        ListingKind::Hotel => {
+            sqlx::query("SELECT content_locale FROM hotels WHERE id = $1")
+                .bind(listing_id)
+                .fetch_optional(pool)
+                .await?
+        }
+        ListingKind::Business => {
+            sqlx::query("SELECT content_locale FROM businesses WHERE id = $1")
+                .bind(listing_id)
+                .fetch_optional(pool)
+                .await?
+        }
+        ListingKind::MenuItem => {
+            sqlx::query(
+                r#"
+                SELECT b.content_locale
+                FROM menu_items i
+                JOIN businesses b ON b.id = i.business_id
+                WHERE i.id = $1 AND i.business_id = $2
+                "#
+            )
+            .bind(listing_id)
+            .bind(business_id)
+            .fetch_optional(pool)
+            .await?
+        }
+    };
+
+    row.map(|r| source_locale(&r.get::<String, _>("content_locale")))
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "translation.listing_not_found"))
+}
+
+fn path_locale(code: &str) -> Result<Locale, AppError> {
+    Locale::parse(code)
+        .filter(|locale| locale.code() == code.trim().to_ascii_lowercase())
+        .ok_or_else(|| AppError::localized(StatusCode::BAD_REQUEST, "unsupported_locale").arg("locale", code.trim()))
+}
+
+async fn list_translations(
+    pool: &PgPool,
+    kind: ListingKind,
+    business_id: i32,
+    listing_id: i32,
+    user: &UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let source = authorize(pool, kind, business_id, listing_id, user).await?;
+
+    let rows = sqlx::query(&format!(
+        "SELECT locale, name, description, amenities, updated_at
+         FROM listing_translations
+         WHERE {} = $1
+         ORDER BY locale",
+        kind.column()
+    ))
+    .bind(listing_id)
+    .fetch_all(pool)
+    .await?;
+
+    let translated: Vec<String> = rows.iter().map(|r| r.get::<String, _>("locale")).collect();
+    let missing: Vec<String> = Locale::ALL
+        .iter()
+        .filter(|l| **l != source && !translated.iter().any(|code| code == l.code()))
+        .map(|l| l.code().to_string())
+        .collect();
+
+    let translations: Vec<ListingTranslation> = rows.into_iter().map(|r| ListingTranslation {
+        locale: r.get::<String, _>("locale"),
+        name: r.try_get::<Option<String>, _>("name").unwrap_or(None),
+        description: r.try_get::<Option<String>, _>("description").unwrap_or(None),
+        amenities: r.try_get::<Option<Vec<String>>, _>("amenities").unwrap_or(None),
+        updated_at: r.get::<chrono::DateTime<chrono::Utc>, _>("updated_at"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(ListingTranslations {
+        listing_type: kind.code().to_string(),
+        id: listing_id,
+        content_locale: source.code().to_string(),
+        translations,
+        missing_locales: missing,
+    }))
+}
+
+async fn save_translation(
+    pool: &PgPool,
+    kind: ListingKind,
+    business_id: i32,
+    listing_id: i32,
+    locale: &str,
+    req: TranslationRequest,
+    user: &UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+    let locale = path_locale(locale)?;
+    let source = authorize(pool, kind, business_id, listing_id, user).await?;
+
+    // El idioma original se edita en la propia publicación
+    if locale == source {
+        return Err(AppError::localized(StatusCode::CONFLICT, "translation.same_as_source").arg("locale", locale.code()));
+    }
+    if kind != ListingKind::Hotel && req.amenities.is_some() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "translation.amenities_hotel_only"));
+    }
+
+    let non_empty = |value: Option<String>| value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+    let amenities = req.amenities.is_some().then(|| normalize_amenities(req.amenities));
+
+    let mut tx = pool.begin().await?;
+
+    let row = sqlx::query(&format!(
+        r#"
+        INSERT INTO listing_translations ({column}, locale, name, description, amenities)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT ({column}, locale) WHERE {column} IS NOT NULL
+        DO UPDATE SET name = EXCLUDED.name, description = EXCLUDED.description,
+                      amenities = EXCLUDED.amenities, updated_at = NOW()
+        RETURNING updated_at
+        "#,
+        column = kind.column()
+    ))
+    .bind(listing_id)
+    .bind(locale.code())
+    .bind(non_empty(req.name))
+    .bind(non_empty(req.description))
+    .bind(amenities)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    // Una traducción nueva de un hotel aprobado se modera igual que una edición del original
+    let returned = kind == ListingKind::Hotel && return_to_review(&mut tx, listing_id, user).await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(TranslationSavedResponse {
+        message: t(if returned { "translation.saved_pending_review" } else { "translation.saved" }),
+        locale: locale.code().to_string(),
+        updated_at: row.get::<chrono::DateTime<chrono::Utc>, _>("updated_at"),
+    }))
+}
+
+async fn remove_translation(
+    pool: &PgPool,
+    kind: ListingKind,
+    business_id: i32,
+    listing_id: i32,
+    locale: &str,
+    user: &UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let locale = path_locale(locale)?;
+    authorize(pool, kind, business_id, listing_id, user).await?;
+
+    let result = sqlx::query(&format!(
+        "DELETE FROM listing_translations WHERE {} = $1 AND locale = $2",
+        kind.column()
+    ))
+    .bind(listing_id)
+    .bind(locale.code())
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "translation.not_found"));
+    }
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("translation.deleted") }))
+}
+
+pub async fn get_hotel_translations(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let hotel_id = path.into_inner();
+    list_translations(pool.get_ref(), ListingKind::Hotel, 0, hotel_id, &user).await
+}
+
+pub async fn save_hotel_translation(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, String)>,
+    req: web::Json<TranslationRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, locale) = path.into_inner();
+    save_translation(pool.get_ref(), ListingKind::Hotel, 0, hotel_id, &locale, req.into_inner(), &user).await
+}
+
+pub async fn delete_hotel_translation(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, String)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (hotel_id, locale) = path.into_inner();
+    remove_translation(pool.get_ref(), ListingKind::Hotel, 0, hotel_id, &locale, &user).await
+}
+
+pub async fn get_business_translations(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    list_translations(pool.get_ref(), ListingKind::Business, business_id, business_id, &user).await
+}
+
+pub async fn save_business_translation(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, String)>,
+    req: web::Json<TranslationRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, locale) = path.into_inner();
+    save_translation(pool.get_ref(), ListingKind::Business, business_id, business_id, &locale, req.into_inner(), &user).await
+}
+
+pub async fn delete_business_translation(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, String)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, locale) = path.into_inner();
+    remove_translation(pool.get_ref(), ListingKind::Business, business_id, business_id, &locale, &user).await
+}
+
+pub async fn get_menu_item_translations(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id) = path.into_inner();
+    list_translations(pool.get_ref(), ListingKind::MenuItem, business_id, item_id, &user).await
+}
+
+pub async fn save_menu_item_translation(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32, String)>,
+    req: web::Json<TranslationRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id, locale) = path.into_inner();
+    save_translation(pool.get_ref(), ListingKind::MenuItem, business_id, item_id, &locale, req.into_inner(), &user).await
+}
+
+pub async fn delete_menu_item_translation(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32, String)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, item_id, locale) = path.into_inner();
+    remove_translation(pool.get_ref(), ListingKind::MenuItem, business_id, item_id, &locale, &user).await
+}
+
+// Publicaciones aprobadas con contenido sin traducir a un idioma. Cada consulta recibe el idioma en $1
+// y devuelve id, name, business_id (solo platillos), owner_email y missing_fields.
+fn missing_translations_query(kind: ListingKind) -> &'static str {
+    match kind {
+        ListingKind::Hotel => r#"
+            SELECT * FROM (
+                SELECT h.id, h.name, NULL::int AS business_id, u.email AS owner_email,
+                       ARRAY_REMOVE(ARRAY[
+                           CASE WHEN COALESCE(h.description, '') <> '' AND t.description IS NULL THEN 'description' END,
+                           CASE WHEN cardinality(h.amenities) > 0 AND t.amenities IS NULL THEN 'amenities' END
+                       ], NULL) AS missing_fields
+                FROM hotels h
+                JOIN users u ON u.id = h.owner_id
+                LEFT JOIN listing_translations t ON t.hotel_id = h.id AND t.locale = $1
+                WHERE h.status = 'approved' AND h.content_locale <> $1
+            ) m
+            WHERE cardinality(missing_fields) > 0
+            ORDER BY id
+        "#,
+        ListingKind::Business => r#"
+            SELECT * FROM (
+                SELECT b.id, b.name, NULL::int AS business_id, u.email AS owner_email,
+                       ARRAY_REMOVE(ARRAY[
+                           CASE WHEN COALESCE(b.description, '') <> '' AND t.description IS NULL THEN 'description' END
+                       ], NULL) AS missing_fields
+                FROM businesses b
+                JOIN users u ON u.id = b.owner_id
+                LEFT JOIN listing_translations t ON t.business_id = b.id AND t.locale = $1
+                WHERE b.status = 'approved' AND b.content_locale <> $1
+            ) m
+            WHERE cardinality(missing_fields) > 0
+            ORDER BY id
+        "#,
+        // En los platillos también se traduce el nombre ("Sopa de lima")
+        ListingKind::MenuItem => r#"
+            SELECT * FROM (
+                SELECT i.id, i.name, i.business_id, u.email AS owner_email,
+                       ARRAY_REMOVE(ARRAY[
+                           CASE WHEN t.name IS NULL THEN 'name' END,
+                           CASE WHEN COALESCE(i.description, '') <> '' AND t.description IS NULL THEN 'description' END
+                       ], NULL) AS missing_fields
+                FROM menu_items i
+                JOIN businesses b ON b.id = i.business_id
+                JOIN users u ON u.id = b.owner_id
+                LEFT JOIN listing_translations t ON t.menu_item_id = i.id AND t.locale = $1
+                WHERE b.status = 'approved' AND i.is_active AND b.content_locale <> $1
+            ) m
+            WHERE cardinality(missing_fields) > 0
+            ORDER BY business_id, id
+        "#,
+    }
+}
+
+pub async fn get_missing_translations(
+    pool: web::Data<PgPool>,
+    query: web::Query<MissingTranslationsQuery>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    require_admin(&user)?;
+    let query = query.into_inner();
+    let locale = path_locale(&query.locale)?;
+
+    let kinds: Vec<ListingKind> = match query.listing_type.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+        None => ListingKind::ALL.to_vec(),
+        Some(code) => vec![ListingKind::parse(code).ok_or_else(|| {
+            AppError::localized(StatusCode::BAD_REQUEST, "translation.invalid_listing_type").arg("listing_type", code)
+        })?],
+    };
+
+    let mut missing = Vec::new();
+    for kind in kinds {
+        let rows = sqlx::query(missing_translations_query(kind))
+            .bind(locale.code())
+            .fetch_all(pool.get_ref())
+            .await?;
+
+        missing.extend(rows.into_iter().map(|r| MissingTranslation {
+            listing_type: kind.code().to_string(),
+            id: r.get::<i32, _>("id"),
+            name: r.get::<String, _>("name"),
+            business_id: r.try_get::<Option<i32>, _>("business_id").unwrap_or(None),
+            owner_email: r.get::<String, _>("owner_email"),
+            missing_fields: r.get::<Vec<String>, _>("missing_fields"),
+        }));
+    }
+
+    Ok(HttpResponse::Ok().json(MissingTranslations {
+        locale: locale.code().to_string(),
+        total: missing.len(),
+        missing,
+    }))
+}
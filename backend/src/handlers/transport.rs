@@ -0,0 +1,1024 @@
+use actix_web::{http::StatusCode, web, HttpResponse};
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use validator::Validate;
+use crate::{
+    handlers::{
+        business::{verify_business_ownership, BUSINESS_TIMEZONE},
+        notification::{notify_user, recipient_locale},
+    },
+    models::{
+        CreatedResponse, MessageResponse, UserInfo,
+        transport::{
+            BusinessTransportBooking, CreateTransportBookingRequest, CreateTripRequest, CustomerTransportBooking,
+            PublicRoute, Route, RouteCreatedResponse, RouteList, RouteRequest, RouteSearchQuery,
+            RouteUpdatedResponse, TransportBookingCreatedResponse, TransportBookingState,
+            TransportBookingStateResponse, TransportBookingSummary, Trip, TripCancelledResponse,
+            TripCreatedResponse, TripSearchQuery, Vehicle, VehicleList, VehicleRequest,
+        },
+    },
+    utils::{
+        errors::AppError,
+        i18n::{t, translate},
+        pagination::{Filter, Kind, Listing, Page, SortField},
+    },
+};
+
+// Búsqueda pública de rutas (GET /transport/routes)
+pub struct PublicRoutes;
+
+impl Listing for PublicRoutes {
+    const KEY: &'static str = "r.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("fare", "transport_route_fare(r.fixed_price, r.distance_km, r.duration_minutes)", Kind::Numeric),
+        SortField::new("origin", "r.origin", Kind::Text),
+        SortField::new("duration", "r.duration_minutes", Kind::Integer),
+    ];
+    const DEFAULT_SORT: &'static str = "fare";
+    const FILTERS: &'static [Filter] = &[];
+}
+
+// Próximas salidas de una ruta (GET /transport/routes/{id}/trips)
+pub struct RouteTrips;
+
+impl Listing for RouteTrips {
+    const KEY: &'static str = "t.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("departure_at", "t.departure_at", Kind::Timestamp),
+        SortField::new("price", "t.price_per_seat", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "departure_at";
+    const FILTERS: &'static [Filter] = &[];
+}
+
+// Traslados reservados por el cliente (GET /transport/bookings/my)
+pub struct MyTransportBookings;
+
+impl Listing for MyTransportBookings {
+    const KEY: &'static str = "tb.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("departure_at", "t.departure_at", Kind::Timestamp),
+        SortField::new("created_at", "tb.created_at", Kind::Timestamp),
+    ];
+    const DEFAULT_SORT: &'static str = "-departure_at";
+    const FILTERS: &'static [Filter] = &[Filter::any_of("status", "tb.status", Kind::Text)];
+}
+
+// Pasajeros de un negocio de transporte (GET /businesses/{id}/transport/bookings)
+pub struct BusinessTransportBookings;
+
+impl Listing for BusinessTransportBookings {
+    const KEY: &'static str = "tb.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("departure_at", "t.departure_at", Kind::Timestamp),
+        SortField::new("created_at", "tb.created_at", Kind::Timestamp),
+    ];
+    const DEFAULT_SORT: &'static str = "departure_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "tb.status", Kind::Text),
+        Filter::any_of("trip_id", "t.id", Kind::Integer),
+        Filter::at_least("departure_from", "t.departure_at", Kind::Timestamp),
+        Filter::at_most("departure_to", "t.departure_at", Kind::Timestamp),
+    ];
+}
+
+// Margen mínimo para reservar un traslado a demanda
+const ON_DEMAND_MIN_NOTICE_MINUTES: i64 = 60;
+
+async fn verify_transport_owner(
+    pool: &PgPool,
+    business_id: i32,
+    user: &UserInfo,
+) -> Result<(), AppError> {
+    if !verify_business_ownership(pool, business_id, user).await? {
+        return Err(AppError::localized(StatusCode::FORBIDDEN, "transport.forbidden"));
+    }
+
+    let business = sqlx::query("SELECT business_type FROM businesses WHERE id = $1")
+        .bind(business_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "business.not_found"))?;
+
+    if business.get::<String, _>("business_type") != "transport" {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "transport.transport_only"));
+    }
+
+    Ok(())
+}
+
+// Comprueba que el vehículo no tenga otra salida activa que se traslape con el intervalo.
+// Debe llamarse con el vehículo bloqueado (FOR UPDATE) dentro de la transacción.
+async fn vehicle_is_free(
+    tx: &mut Transaction<'_, Postgres>,
+    vehicle_id: i32,
+    departure_at: DateTime<Utc>,
+    arrival_at: DateTime<Utc>,
+) -> Result<bool, AppError> {
+    let overlapping = sqlx::query(
+        r#"
+        SELECT id FROM transport_trips
+        WHERE vehicle_id = $1
+          AND status = 'scheduled'
+          AND departure_at < $3
+          AND arrival_at > $2
+        LIMIT 1
+        "#
+    )
+    .bind(vehicle_id)
+    .bind(departure_at)
+    .bind(arrival_at)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(overlapping.is_none())
+}
+
+async fn generate_transport_reference(tx: &mut Transaction<'_, Postgres>) -> Result<String, AppError> {
+    loop {
+        let reference = format!("MT{}", uuid::Uuid::new_v4().to_string().replace("-", "")[..6].to_uppercase());
+
+        let exists = sqlx::query("SELECT id FROM transport_bookings WHERE booking_reference = $1")
+            .bind(&reference)
+            .fetch_optional(&mut **tx)
+            .await?;
+
+        if exists.is_none() {
+            return Ok(reference);
+        }
+    }
+}
+
+fn route_from_row(r: &sqlx::postgres::PgRow) -> Route {
+    Route {
+        id: r.get::<i32, _>("id"),
+        origin: r.get::<String, _>("origin"),
+        destination: r.get::<String, _>("destination"),
+        distance_km: r.get::<f64, _>("distance_km"),
+        duration_minutes: r.get::<i32, _>("duration_minutes"),
+        fixed_price: r.try_get::<Option<f64>, _>("fixed_price").unwrap_or(None),
+        fare: r.get::<f64, _>("fare"),
+        is_active: r.get::<bool, _>("is_active"),
+    }
+}
+
+// ---- Administración del servicio (dueño) ----
+
+pub async fn get_my_vehicles(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let vehicles = sqlx::query(
+        r#"
+        SELECT id, name, vehicle_type, plate, seat_capacity, is_active
+        FROM transport_vehicles
+        WHERE business_id = $1
+        ORDER BY name, id
+        "#
+    )
+    .bind(business_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let vehicle_list: Vec<Vehicle> = vehicles.into_iter().map(|v| Vehicle {
+        id: v.get::<i32, _>("id"),
+        name: v.get::<String, _>("name"),
+        vehicle_type: v.get::<String, _>("vehicle_type"),
+        plate: v.try_get::<Option<String>, _>("plate").unwrap_or(None),
+        seat_capacity: v.get::<i32, _>("seat_capacity"),
+        is_active: v.get::<bool, _>("is_active"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(VehicleList { vehicles: vehicle_list }))
+}
+
+pub async fn create_vehicle(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<VehicleRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let vehicle = sqlx::query(
+        r#"
+        INSERT INTO transport_vehicles (business_id, name, vehicle_type, plate, seat_capacity, is_active)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        RETURNING id
+        "#
+    )
+    .bind(business_id)
+    .bind(&req.name)
+    .bind(&req.vehicle_type)
+    .bind(req.plate.as_deref())
+    .bind(req.seat_capacity)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_one(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(CreatedResponse {
+        message: t("transport.vehicle_created"),
+        id: vehicle.get::<i32, _>("id"),
+    }))
+}
+
+pub async fn update_vehicle(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<VehicleRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, vehicle_id) = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT id FROM transport_vehicles WHERE id = $1 AND business_id = $2 FOR UPDATE")
+        .bind(vehicle_id)
+        .bind(business_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.vehicle_not_found"))?;
+
+    // No se puede reducir la capacidad por debajo de los asientos ya vendidos en salidas futuras
+    let max_reserved = sqlx::query(
+        r#"
+        SELECT COALESCE(MAX(reserved), 0) AS max_reserved
+        FROM (
+            SELECT SUM(tb.seats) AS reserved
+            FROM transport_trips t
+            JOIN transport_bookings tb ON tb.trip_id = t.id AND tb.status != 'cancelled'
+            WHERE t.vehicle_id = $1 AND t.status = 'scheduled' AND t.departure_at > NOW()
+            GROUP BY t.id
+        ) r
+        "#
+    )
+    .bind(vehicle_id)
+    .fetch_one(&mut *tx)
+    .await?
+    .try_get::<Option<i64>, _>("max_reserved")
+    .unwrap_or(None)
+    .unwrap_or(0);
+
+    if (req.seat_capacity as i64) < max_reserved {
+        return Err(AppError::localized(StatusCode::CONFLICT, "transport.capacity_below_reserved")
+            .arg("seats", max_reserved));
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE transport_vehicles
+        SET name = $1, vehicle_type = $2, plate = $3, seat_capacity = $4,
+            is_active = $5, updated_at = NOW()
+        WHERE id = $6
+        "#
+    )
+    .bind(&req.name)
+    .bind(&req.vehicle_type)
+    .bind(req.plate.as_deref())
+    .bind(req.seat_capacity)
+    .bind(req.is_active.unwrap_or(true))
+    .bind(vehicle_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("transport.vehicle_updated") }))
+}
+
+pub async fn get_my_routes(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let routes = sqlx::query(
+        r#"
+        SELECT id, origin, destination, distance_km::float8 AS distance_km, duration_minutes,
+               fixed_price::float8 AS fixed_price,
+               transport_route_fare(fixed_price, distance_km, duration_minutes)::float8 AS fare,
+               is_active
+        FROM transport_routes
+        WHERE business_id = $1
+        ORDER BY origin, destination
+        "#
+    )
+    .bind(business_id)
+    .fetch_all(pool.get_ref())
+    .await?;
+
+    let route_list: Vec<Route> = routes.iter().map(route_from_row).collect();
+
+    Ok(HttpResponse::Ok().json(RouteList { routes: route_list }))
+}
+
+pub async fn create_route(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<RouteRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let route = sqlx::query(
+        r#"
+        INSERT INTO transport_routes (business_id, origin, destination, distance_km, duration_minutes,
+                                      fixed_price, is_active)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING id, transport_route_fare(fixed_price, distance_km, duration_minutes)::float8 AS fare
+        "#
+    )
+    .bind(business_id)
+    .bind(req.origin.trim())
+    .bind(req.destination.trim())
+    .bind(req.distance_km)
+    .bind(req.duration_minutes)
+    .bind(req.fixed_price)
+    .bind(req.is_active.unwrap_or(true))
+    .fetch_one(pool.get_ref())
+    .await?;
+
+    Ok(HttpResponse::Created().json(RouteCreatedResponse {
+        message: t("transport.route_created"),
+        id: route.get::<i32, _>("id"),
+        fare: route.get::<f64, _>("fare"),
+    }))
+}
+
+pub async fn update_route(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    req: web::Json<RouteRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, route_id) = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    // Las salidas ya programadas conservan su precio y horario
+    let route = sqlx::query(
+        r#"
+        UPDATE transport_routes
+        SET origin = $1, destination = $2, distance_km = $3, duration_minutes = $4,
+            fixed_price = $5, is_active = $6, updated_at = NOW()
+        WHERE id = $7 AND business_id = $8
+        RETURNING transport_route_fare(fixed_price, distance_km, duration_minutes)::float8 AS fare
+        "#
+    )
+    .bind(req.origin.trim())
+    .bind(req.destination.trim())
+    .bind(req.distance_km)
+    .bind(req.duration_minutes)
+    .bind(req.fixed_price)
+    .bind(req.is_active.unwrap_or(true))
+    .bind(route_id)
+    .bind(business_id)
+    .fetch_optional(pool.get_ref())
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.route_not_found"))?;
+
+    Ok(HttpResponse::Ok().json(RouteUpdatedResponse {
+        message: t("transport.route_updated"),
+        fare: route.get::<f64, _>("fare"),
+    }))
+}
+
+pub async fn create_trip(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    req: web::Json<CreateTripRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    if req.departure_at <= Utc::now() {
+        return Err(AppError::localized(StatusCode::BAD_REQUEST, "transport.departure_in_past"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let route = sqlx::query(
+        r#"
+        SELECT duration_minutes,
+               transport_route_fare(fixed_price, distance_km, duration_minutes)::float8 AS fare
+        FROM transport_routes
+        WHERE id = $1 AND business_id = $2 AND is_active
+        "#
+    )
+    .bind(req.route_id)
+    .bind(business_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.route_not_found"))?;
+
+    sqlx::query(
+        "SELECT id FROM transport_vehicles WHERE id = $1 AND business_id = $2 AND is_active FOR UPDATE"
+    )
+    .bind(req.vehicle_id)
+    .bind(business_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.vehicle_not_found"))?;
+
+    let arrival_at = req.departure_at + chrono::Duration::minutes(route.get::<i32, _>("duration_minutes") as i64);
+
+    if !vehicle_is_free(&mut tx, req.vehicle_id, req.departure_at, arrival_at).await? {
+        return Err(AppError::localized(StatusCode::CONFLICT, "transport.vehicle_busy"));
+    }
+
+    let price_per_seat = req.price_per_seat.unwrap_or_else(|| route.get::<f64, _>("fare"));
+
+    let trip = sqlx::query(
+        r#"
+        INSERT INTO transport_trips (route_id, vehicle_id, trip_type, departure_at, arrival_at, price_per_seat)
+        VALUES ($1, $2, 'scheduled', $3, $4, $5)
+        RETURNING id
+        "#
+    )
+    .bind(req.route_id)
+    .bind(req.vehicle_id)
+    .bind(req.departure_at)
+    .bind(arrival_at)
+    .bind(price_per_seat)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(TripCreatedResponse {
+        message: t("transport.trip_created"),
+        id: trip.get::<i32, _>("id"),
+        departure_at: req.departure_at,
+        arrival_at,
+        price_per_seat,
+    }))
+}
+
+pub async fn cancel_trip(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, trip_id) = path.into_inner();
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE transport_trips t
+        SET status = 'cancelled', updated_at = NOW()
+        FROM transport_routes r
+        WHERE t.id = $1 AND t.route_id = r.id AND r.business_id = $2 AND t.status = 'scheduled'
+        RETURNING t.id
+        "#
+    )
+    .bind(trip_id)
+    .bind(business_id)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if result.is_none() {
+        return Err(AppError::localized(StatusCode::NOT_FOUND, "transport.trip_not_cancellable"));
+    }
+
+    let cancelled = sqlx::query(
+        r#"
+        UPDATE transport_bookings
+        SET status = 'cancelled',
+            cancelled_at = CURRENT_TIMESTAMP,
+            cancellation_reason = 'Salida cancelada por el operador',
+            updated_at = CURRENT_TIMESTAMP
+        WHERE trip_id = $1 AND status IN ('pending', 'confirmed')
+        "#
+    )
+    .bind(trip_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(TripCancelledResponse {
+        message: t("transport.trip_cancelled"),
+        cancelled_bookings: cancelled.rows_affected(),
+    }))
+}
+
+pub async fn get_business_transport_bookings(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    page: Page<BusinessTransportBookings>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let business_id = path.into_inner();
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let sql = format!(
+        r#"
+        SELECT tb.id, tb.booking_reference, tb.seats, tb.total_price::float8 AS total_price,
+               tb.status, tb.payment_status, tb.passenger_name, tb.flight_number,
+               tb.special_requests, tb.created_at,
+               t.id AS trip_id, t.trip_type, t.departure_at, r.origin, r.destination,
+               v.name AS vehicle_name,
+               u.first_name || ' ' || u.last_name AS customer_name, u.email AS customer_email,
+               {}
+        FROM transport_bookings tb
+        JOIN transport_trips t ON tb.trip_id = t.id
+        JOIN transport_routes r ON t.route_id = r.id
+        JOIN transport_vehicles v ON t.vehicle_id = v.id
+        JOIN users u ON tb.user_id = u.id
+        WHERE r.business_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let bookings = page
+        .bind(sqlx::query(&sql).bind(business_id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (bookings, next_cursor) = page.finish(bookings);
+
+    let booking_list: Vec<BusinessTransportBooking> = bookings.into_iter().map(|b| BusinessTransportBooking {
+        id: b.get::<i32, _>("id"),
+        reference: b.get::<String, _>("booking_reference"),
+        seats: b.get::<i32, _>("seats"),
+        total_price: b.get::<f64, _>("total_price"),
+        status: b.get::<String, _>("status"),
+        payment_status: b.get::<String, _>("payment_status"),
+        passenger_name: b.try_get::<Option<String>, _>("passenger_name").unwrap_or(None),
+        flight_number: b.try_get::<Option<String>, _>("flight_number").unwrap_or(None),
+        special_requests: b.try_get::<Option<String>, _>("special_requests").unwrap_or(None),
+        created_at: b.get::<DateTime<Utc>, _>("created_at"),
+        trip_id: b.get::<i32, _>("trip_id"),
+        trip_type: b.get::<String, _>("trip_type"),
+        departure_at: b.get::<DateTime<Utc>, _>("departure_at"),
+        origin: b.get::<String, _>("origin"),
+        destination: b.get::<String, _>("destination"),
+        vehicle_name: b.get::<String, _>("vehicle_name"),
+        customer_name: b.try_get::<Option<String>, _>("customer_name").unwrap_or(None),
+        customer_email: b.get::<String, _>("customer_email"),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(booking_list, next_cursor)))
+}
+
+// El negocio confirma una reserva pendiente; solo las confirmadas admiten el abordaje
+pub async fn confirm_transport_booking(
+    pool: web::Data<PgPool>,
+    path: web::Path<(i32, i32)>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let (business_id, booking_id) = path.into_inner();
+    verify_transport_owner(pool.get_ref(), business_id, &user).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let booking = sqlx::query(
+        r#"
+        UPDATE transport_bookings tb
+        SET status = 'confirmed', updated_at = CURRENT_TIMESTAMP
+        FROM transport_trips t, transport_routes r
+        WHERE tb.id = $1 AND tb.trip_id = t.id AND t.route_id = r.id
+          AND r.business_id = $2 AND tb.status = 'pending'
+        RETURNING tb.user_id, tb.booking_reference, tb.payment_status, r.origin, r.destination
+        "#
+    )
+    .bind(booking_id)
+    .bind(business_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::CONFLICT, "transport.not_confirmable"))?;
+
+    let reference = booking.get::<String, _>("booking_reference");
+    let customer_id = booking.get::<i32, _>("user_id");
+    let locale = recipient_locale(&mut tx, customer_id).await?;
+    let args = [
+        ("reference", reference.clone()),
+        ("origin", booking.get::<String, _>("origin")),
+        ("destination", booking.get::<String, _>("destination")),
+    ];
+    notify_user(
+        &mut tx,
+        customer_id,
+        "transport_booking_confirmed",
+        &translate(locale, "notification.transport_booking_confirmed.title", &args),
+        &translate(locale, "notification.transport_booking_confirmed.message", &args),
+        serde_json::json!({ "transport_booking_id": booking_id, "business_id": business_id }),
+    )
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(TransportBookingStateResponse {
+        message: t("transport.confirmed"),
+        booking: TransportBookingState {
+            id: booking_id,
+            reference,
+            status: "confirmed".to_string(),
+            payment_status: booking.get::<String, _>("payment_status"),
+        },
+    }))
+}
+
+// ---- Consulta pública ----
+
+pub async fn get_public_routes(
+    pool: web::Data<PgPool>,
+    query: web::Query<RouteSearchQuery>,
+    page: Page<PublicRoutes>,
+) -> Result<HttpResponse, AppError> {
+    let origin = query.origin.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(|s| format!("%{}%", s));
+    let destination = query.destination.as_deref().map(str::trim).filter(|s| !s.is_empty()).map(|s| format!("%{}%", s));
+
+    let sql = format!(
+        r#"
+        SELECT r.id, r.origin, r.destination, r.distance_km::float8 AS distance_km, r.duration_minutes,
+               r.fixed_price::float8 AS fixed_price,
+               transport_route_fare(r.fixed_price, r.distance_km, r.duration_minutes)::float8 AS fare,
+               r.is_active, b.id AS business_id, b.name AS business_name,
+               (SELECT MAX(v.seat_capacity) FROM transport_vehicles v
+                WHERE v.business_id = b.id AND v.is_active) AS max_seats,
+               {}
+        FROM transport_routes r
+        JOIN businesses b ON r.business_id = b.id
+        WHERE r.is_active AND b.status = 'approved' AND b.business_type = 'transport'
+          AND ($1::text IS NULL OR r.origin ILIKE $1)
+          AND ($2::text IS NULL OR r.destination ILIKE $2){}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(3),
+        page.order_and_limit()
+    );
+    let routes = page
+        .bind(sqlx::query(&sql).bind(origin).bind(destination))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (routes, next_cursor) = page.finish(routes);
+
+    let route_list: Vec<PublicRoute> = routes.iter().map(|r| PublicRoute {
+        route: route_from_row(r),
+        business_id: r.get::<i32, _>("business_id"),
+        business_name: r.get::<String, _>("business_name"),
+        // Sin vehículos activos no se pueden pedir traslados privados
+        on_demand_max_seats: r.try_get::<Option<i32>, _>("max_seats").unwrap_or(None),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(route_list, next_cursor)))
+}
+
+pub async fn get_route_trips(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    query: web::Query<TripSearchQuery>,
+    page: Page<RouteTrips>,
+) -> Result<HttpResponse, AppError> {
+    let route_id = path.into_inner();
+
+    let sql = format!(
+        r#"
+        SELECT t.id, t.departure_at, t.arrival_at, t.price_per_seat::float8 AS price_per_seat,
+               v.name AS vehicle_name, v.vehicle_type, v.seat_capacity,
+               v.seat_capacity - COALESCE((
+                   SELECT SUM(tb.seats) FROM transport_bookings tb
+                   WHERE tb.trip_id = t.id AND tb.status != 'cancelled'
+               ), 0) AS seats_available,
+               {}
+        FROM transport_trips t
+        JOIN transport_routes r ON t.route_id = r.id
+        JOIN businesses b ON r.business_id = b.id
+        JOIN transport_vehicles v ON t.vehicle_id = v.id
+        WHERE t.route_id = $1
+          AND r.is_active AND b.status = 'approved'
+          AND t.trip_type = 'scheduled' AND t.status = 'scheduled'
+          AND t.departure_at > NOW()
+          AND ($2::date IS NULL OR (t.departure_at AT TIME ZONE $3)::date = $2){}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(4),
+        page.order_and_limit()
+    );
+    let query = sqlx::query(&sql).bind(route_id).bind(query.date).bind(BUSINESS_TIMEZONE);
+    let trips = page.bind(query).fetch_all(pool.get_ref()).await?;
+    let (trips, next_cursor) = page.finish(trips);
+
+    let trip_list: Vec<Trip> = trips.into_iter().map(|t| Trip {
+        id: t.get::<i32, _>("id"),
+        departure_at: t.get::<DateTime<Utc>, _>("departure_at"),
+        arrival_at: t.get::<DateTime<Utc>, _>("arrival_at"),
+        price_per_seat: t.get::<f64, _>("price_per_seat"),
+        vehicle_name: t.get::<String, _>("vehicle_name"),
+        vehicle_type: t.get::<String, _>("vehicle_type"),
+        seat_capacity: t.get::<i32, _>("seat_capacity"),
+        seats_available: t.try_get::<Option<i64>, _>("seats_available").unwrap_or(None).unwrap_or(0),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(trip_list, next_cursor)))
+}
+
+// ---- Reservas del cliente ----
+
+pub async fn create_transport_booking(
+    pool: web::Data<PgPool>,
+    req: web::Json<CreateTransportBookingRequest>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    if let Err(errors) = req.validate() {
+        return Err(AppError::Validation(errors));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let (trip_id, trip_type, departure_at, total_price) = match (req.trip_id, req.route_id, req.departure_at) {
+        (Some(trip_id), None, None) => {
+            // Salida compartida: se bloquea la salida para que dos reservas no vendan el mismo asiento,
+            // y el vehículo en modo compartido para que update_vehicle no reduzca su capacidad a la vez
+            let trip = sqlx::query(
+                r#"
+                SELECT t.departure_at, t.price_per_seat::float8 AS price_per_seat, v.seat_capacity
+                FROM transport_trips t
+                JOIN transport_routes r ON t.route_id = r.id
+                JOIN businesses b ON r.business_id = b.id
+                JOIN transport_vehicles v ON t.vehicle_id = v.id
+                WHERE t.id = $1 AND t.trip_type = 'scheduled' AND t.status = 'scheduled'
+                  AND r.is_active AND b.status = 'approved'
+                FOR UPDATE OF t FOR SHARE OF v
+                "#
+            )
+            .bind(trip_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.trip_unavailable"))?;
+
+            let departure_at = trip.get::<DateTime<Utc>, _>("departure_at");
+            if departure_at <= Utc::now() {
+                return Err(AppError::localized(StatusCode::BAD_REQUEST, "transport.trip_departed"));
+            }
+
+            let reserved = sqlx::query(
+                "SELECT COALESCE(SUM(seats), 0) AS reserved FROM transport_bookings WHERE trip_id = $1 AND status != 'cancelled'"
+            )
+            .bind(trip_id)
+            .fetch_one(&mut *tx)
+            .await?
+            .try_get::<Option<i64>, _>("reserved")
+            .unwrap_or(None)
+            .unwrap_or(0);
+
+            let available = trip.get::<i32, _>("seat_capacity") as i64 - reserved;
+            if (req.seats as i64) > available {
+                return Err(AppError::localized(StatusCode::CONFLICT, "transport.seats_left").arg("seats", available.max(0)));
+            }
+
+            let total = trip.get::<f64, _>("price_per_seat") * req.seats as f64;
+            (trip_id, "scheduled", departure_at, total)
+        }
+        (None, Some(route_id), Some(departure_at)) => {
+            // Traslado privado: se asigna el vehículo libre más pequeño que tenga cupo
+            if departure_at < Utc::now() + chrono::Duration::minutes(ON_DEMAND_MIN_NOTICE_MINUTES) {
+                return Err(AppError::localized(StatusCode::BAD_REQUEST, "transport.min_notice")
+                    .arg("minutes", ON_DEMAND_MIN_NOTICE_MINUTES));
+            }
+
+            let route = sqlx::query(
+                r#"
+                SELECT r.business_id, r.duration_minutes,
+                       transport_route_fare(r.fixed_price, r.distance_km, r.duration_minutes)::float8 AS fare
+                FROM transport_routes r
+                JOIN businesses b ON r.business_id = b.id
+                WHERE r.id = $1 AND r.is_active AND b.status = 'approved'
+                "#
+            )
+            .bind(route_id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.route_unavailable"))?;
+
+            let arrival_at = departure_at + chrono::Duration::minutes(route.get::<i32, _>("duration_minutes") as i64);
+
+            let candidates = sqlx::query(
+                r#"
+                SELECT id FROM transport_vehicles
+                WHERE business_id = $1 AND is_active AND seat_capacity >= $2
+                ORDER BY seat_capacity, id
+                FOR UPDATE
+                "#
+            )
+            .bind(route.get::<i32, _>("business_id"))
+            .bind(req.seats)
+            .fetch_all(&mut *tx)
+            .await?;
+
+            if candidates.is_empty() {
+                return Err(AppError::localized(StatusCode::CONFLICT, "transport.no_vehicle_capacity"));
+            }
+
+            let mut vehicle_id = None;
+            for candidate in &candidates {
+                let id = candidate.get::<i32, _>("id");
+                if vehicle_is_free(&mut tx, id, departure_at, arrival_at).await? {
+                    vehicle_id = Some(id);
+                    break;
+                }
+            }
+
+            let vehicle_id = vehicle_id.ok_or_else(|| {
+                AppError::localized(StatusCode::CONFLICT, "transport.no_vehicle_free")
+            })?;
+
+            // El precio del traslado privado es por vehículo, no por asiento
+            let fare = route.get::<f64, _>("fare");
+
+            let trip = sqlx::query(
+                r#"
+                INSERT INTO transport_trips (route_id, vehicle_id, trip_type, departure_at, arrival_at, price_per_seat)
+                VALUES ($1, $2, 'on_demand', $3, $4, 0)
+                RETURNING id
+                "#
+            )
+            .bind(route_id)
+            .bind(vehicle_id)
+            .bind(departure_at)
+            .bind(arrival_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            (trip.get::<i32, _>("id"), "on_demand", departure_at, fare)
+        }
+        _ => {
+            return Err(AppError::localized(StatusCode::BAD_REQUEST, "transport.target_required"));
+        }
+    };
+
+    let booking_reference = generate_transport_reference(&mut tx).await?;
+
+    let booking = sqlx::query(
+        r#"
+        INSERT INTO transport_bookings (user_id, trip_id, seats, total_price, passenger_name,
+                                        flight_number, special_requests, booking_reference,
+                                        status, payment_status)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 'pending', 'pending')
+        RETURNING id, total_price::float8 AS total_price, created_at
+        "#
+    )
+    .bind(user.id)
+    .bind(trip_id)
+    .bind(req.seats)
+    .bind(total_price)
+    .bind(req.passenger_name.as_deref())
+    .bind(req.flight_number.as_deref())
+    .bind(req.special_requests.as_deref())
+    .bind(&booking_reference)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Created().json(TransportBookingCreatedResponse {
+        message: t("transport.booked"),
+        booking: TransportBookingSummary {
+            id: booking.get::<i32, _>("id"),
+            reference: booking_reference,
+            trip_id,
+            trip_type: trip_type.to_string(),
+            departure_at,
+            seats: req.seats,
+            total_price: booking.get::<f64, _>("total_price"),
+            status: "pending".to_string(),
+            payment_status: "pending".to_string(),
+            created_at: booking.get::<DateTime<Utc>, _>("created_at"),
+        },
+    }))
+}
+
+pub async fn get_my_transport_bookings(
+    pool: web::Data<PgPool>,
+    page: Page<MyTransportBookings>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let sql = format!(
+        r#"
+        SELECT tb.id, tb.booking_reference, tb.seats, tb.total_price::float8 AS total_price,
+               tb.status, tb.payment_status, tb.passenger_name, tb.flight_number, tb.created_at,
+               t.trip_type, t.departure_at, t.arrival_at, r.origin, r.destination,
+               v.name AS vehicle_name, v.vehicle_type, b.name AS business_name, b.phone AS business_phone,
+               {}
+        FROM transport_bookings tb
+        JOIN transport_trips t ON tb.trip_id = t.id
+        JOIN transport_routes r ON t.route_id = r.id
+        JOIN transport_vehicles v ON t.vehicle_id = v.id
+        JOIN businesses b ON r.business_id = b.id
+        WHERE tb.user_id = $1{}
+        {}
+        "#,
+        page.columns(),
+        page.conditions(2),
+        page.order_and_limit()
+    );
+    let bookings = page
+        .bind(sqlx::query(&sql).bind(user.id))
+        .fetch_all(pool.get_ref())
+        .await?;
+    let (bookings, next_cursor) = page.finish(bookings);
+
+    let booking_list: Vec<CustomerTransportBooking> = bookings.into_iter().map(|b| CustomerTransportBooking {
+        id: b.get::<i32, _>("id"),
+        reference: b.get::<String, _>("booking_reference"),
+        seats: b.get::<i32, _>("seats"),
+        total_price: b.get::<f64, _>("total_price"),
+        status: b.get::<String, _>("status"),
+        payment_status: b.get::<String, _>("payment_status"),
+        passenger_name: b.try_get::<Option<String>, _>("passenger_name").unwrap_or(None),
+        flight_number: b.try_get::<Option<String>, _>("flight_number").unwrap_or(None),
+        created_at: b.get::<DateTime<Utc>, _>("created_at"),
+        trip_type: b.get::<String, _>("trip_type"),
+        departure_at: b.get::<DateTime<Utc>, _>("departure_at"),
+        arrival_at: b.get::<DateTime<Utc>, _>("arrival_at"),
+        origin: b.get::<String, _>("origin"),
+        destination: b.get::<String, _>("destination"),
+        vehicle_name: b.get::<String, _>("vehicle_name"),
+        vehicle_type: b.get::<String, _>("vehicle_type"),
+        business_name: b.get::<String, _>("business_name"),
+        business_phone: b.try_get::<Option<String>, _>("business_phone").unwrap_or(None),
+    }).collect();
+
+    Ok(HttpResponse::Ok().json(page.response(booking_list, next_cursor)))
+}
+
+pub async fn cancel_transport_booking(
+    pool: web::Data<PgPool>,
+    path: web::Path<i32>,
+    user: UserInfo,
+) -> Result<HttpResponse, AppError> {
+    let booking_id = path.into_inner();
+
+    let mut tx = pool.begin().await?;
+
+    let booking = sqlx::query(
+        r#"
+        UPDATE transport_bookings tb
+        SET status = 'cancelled',
+            cancelled_at = CURRENT_TIMESTAMP,
+            updated_at = CURRENT_TIMESTAMP
+        FROM transport_trips t
+        WHERE tb.id = $1 AND tb.user_id = $2 AND tb.trip_id = t.id
+          AND tb.status IN ('pending', 'confirmed')
+          AND t.departure_at > NOW()
+        RETURNING t.id AS trip_id, t.trip_type
+        "#
+    )
+    .bind(booking_id)
+    .bind(user.id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| AppError::localized(StatusCode::NOT_FOUND, "transport.booking_not_cancellable"))?;
+
+    // Un traslado privado libera el vehículo al cancelarse
+    if booking.get::<String, _>("trip_type") == "on_demand" {
+        sqlx::query("UPDATE transport_trips SET status = 'cancelled', updated_at = NOW() WHERE id = $1")
+            .bind(booking.get::<i32, _>("trip_id"))
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(HttpResponse::Ok().json(MessageResponse { message: t("transport.booking_cancelled") }))
+}
@@ -1,40 +1,290 @@
 // lib.rs
 
 // --- Módulos de la Aplicación ---
+pub mod config;
 pub mod models;
 pub mod handlers;
 pub mod middleware;
 pub mod utils;
+pub mod openapi;
 
-use actix_web::{dev::Server, web, App, HttpServer, HttpResponse, Responder, get, middleware::Logger};
+use std::{net::TcpListener, time::Duration};
+use actix_web::{
+    body::MessageBody,
+    dev::{Server, ServiceFactory, ServiceRequest, ServiceResponse},
+    get, http::StatusCode, middleware::{from_fn, Logger}, web, App, Error, HttpResponse, HttpServer, Scope,
+};
 use actix_cors::Cors;
-use sqlx::{PgPool, postgres::PgPoolOptions};
-use std::net::TcpListener;
+use sqlx::{migrate::Migrator, PgPool};
+use utoipa_swagger_ui::{Config as SwaggerConfig, SwaggerUi};
 
-// Importaciones de handlers (igual que en main.rs)
-use handlers::{
-    // ... (todas tus importaciones de handlers)
-};
+use config::Settings;
+use middleware::{deprecation, locale, request_id};
+use utils::{errors::{self, AppError}, i18n::t};
+
+use handlers::{auth, admin, hotel, booking, business, menu, product, order, transport, notification, suspension, moderation, analytics, review, favorite, itinerary, calendar, document, check_in, translation};
+
+// Única fuente del esquema: backend/migrations, embebidas en el binario.
+// El servidor las aplica al arrancar si database.run_migrations está activo; maya-admin migrate
+// permite consultarlas, aplicarlas y revertirlas a mano.
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+async fn route_not_found() -> Result<HttpResponse, AppError> {
+    Err(AppError::localized(StatusCode::NOT_FOUND, "route_not_found"))
+}
+
+#[get("/health")]
+async fn health() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "OK",
+        "message": t("service_healthy")
+    }))
+}
+
+// Prefijos de la API. Cada versión se monta en su propio scope, así una v2 puede convivir con la v1.
+pub const API_V1: &str = "/api/v1";
+// Rutas sin versión: las mismas de la v1, con cabeceras Deprecation/Sunset hasta su retirada
+pub const LEGACY_API: &str = "/api";
+// Registro e inicio de sesión de la primera versión, en la raíz: (ruta antigua, ruta en la v1)
+pub const LEGACY_ROOT_ROUTES: &[(&str, &str)] = &[("/register", "/auth/register"), ("/login", "/auth/login")];
+
+// Declara las rutas de una versión una sola vez: genera la lista (método, ruta), que usan la
+// especificación OpenAPI y sus pruebas, y la función que las monta en un scope.
+macro_rules! api_routes {
+    ($routes:ident, $mount:ident { $($method:ident $path:literal => $handler:path,)* }) => {
+        pub const $routes: &[(&str, &str)] = &[$((stringify!($method), $path)),*];
+
+        fn $mount(scope: Scope) -> Scope {
+            scope$(.route($path, web::$method().to($handler)))*
+        }
+    };
+}
+
+api_routes!(V1_ROUTES, mount_v1 {
+    get "/openapi.json" => openapi::get_openapi_spec,
+    post "/auth/register" => auth::register,
+    post "/auth/login" => auth::login,
+    get "/auth/me" => auth::me,
+    put "/auth/me/locale" => auth::update_locale,
+    post "/hotels" => hotel::create_hotel,
+    get "/hotels/my" => hotel::get_my_hotels,
+    get "/hotels/public" => hotel::get_public_hotels,
+    put "/hotels/{id}" => hotel::update_hotel,
+    post "/hotels/{id}/resubmit" => hotel::resubmit_hotel,
+    get "/hotels/{id}/review-history" => hotel::get_my_hotel_review_history,
+    get "/hotels/{id}/translations" => translation::get_hotel_translations,
+    put "/hotels/{id}/translations/{locale}" => translation::save_hotel_translation,
+    delete "/hotels/{id}/translations/{locale}" => translation::delete_hotel_translation,
+    get "/hotels/{id}/bookings" => booking::get_hotel_bookings,
+    put "/hotels/{id}/bookings/{booking_id}/confirm" => booking::confirm_booking,
+    put "/hotels/{id}/bookings/{booking_id}/payment" => booking::mark_booking_paid,
+    put "/hotels/{id}/bookings/{booking_id}/complete" => booking::complete_booking,
+    get "/hotels/{id}/reviews" => review::get_hotel_reviews,
+    get "/hotels/{id}/analytics" => analytics::get_hotel_analytics,
+    get "/hotels/{id}/calendar" => calendar::get_hotel_calendar_sync,
+    put "/hotels/{id}/calendar/token" => calendar::rotate_hotel_calendar_token,
+    get "/hotels/{id}/calendar/conflicts" => calendar::get_hotel_calendar_conflicts,
+    post "/hotels/{id}/calendar/imports" => calendar::add_hotel_calendar_feed,
+    post "/hotels/{id}/calendar/imports/upload" => calendar::upload_hotel_calendar,
+    post "/hotels/{id}/calendar/imports/{feed_id}/sync" => calendar::sync_hotel_calendar_feed,
+    delete "/hotels/{id}/calendar/imports/{feed_id}" => calendar::delete_hotel_calendar_feed,
+    get "/calendar/hotels/{token}.ics" => calendar::get_hotel_ics_feed,
+    get "/hotels/{id}/analytics.csv" => analytics::export_hotel_analytics_csv,
+    post "/businesses" => business::create_business,
+    get "/businesses/my" => business::get_my_businesses,
+    get "/businesses" => business::get_public_businesses,
+    get "/businesses/{id}" => business::get_public_business_detail,
+    put "/businesses/{id}" => business::update_business,
+    delete "/businesses/{id}" => business::delete_business,
+    get "/businesses/{id}/manage" => business::get_business_detail,
+    get "/businesses/{id}/translations" => translation::get_business_translations,
+    put "/businesses/{id}/translations/{locale}" => translation::save_business_translation,
+    delete "/businesses/{id}/translations/{locale}" => translation::delete_business_translation,
+    get "/businesses/{id}/reviews" => review::get_business_reviews,
+    get "/businesses/{id}/menu" => menu::get_public_menu,
+    get "/businesses/{id}/menu/manage" => menu::get_my_menu,
+    post "/businesses/{id}/menu/sections" => menu::create_menu_section,
+    put "/businesses/{id}/menu/sections/{section_id}" => menu::update_menu_section,
+    delete "/businesses/{id}/menu/sections/{section_id}" => menu::delete_menu_section,
+    post "/businesses/{id}/menu/items" => menu::create_menu_item,
+    put "/businesses/{id}/menu/items/{item_id}" => menu::update_menu_item,
+    delete "/businesses/{id}/menu/items/{item_id}" => menu::delete_menu_item,
+    post "/businesses/{id}/menu/items/{item_id}/sold-out" => menu::mark_item_sold_out,
+    delete "/businesses/{id}/menu/items/{item_id}/sold-out" => menu::clear_item_sold_out,
+    get "/businesses/{id}/menu/items/{item_id}/translations" => translation::get_menu_item_translations,
+    put "/businesses/{id}/menu/items/{item_id}/translations/{locale}" => translation::save_menu_item_translation,
+    delete "/businesses/{id}/menu/items/{item_id}/translations/{locale}" => translation::delete_menu_item_translation,
+    post "/businesses/{id}/products" => product::create_product,
+    get "/businesses/{id}/orders" => order::get_business_orders,
+    put "/businesses/{id}/orders/{order_id}/confirm" => order::confirm_order,
+    put "/businesses/{id}/orders/{order_id}/payment" => order::mark_order_paid,
+    put "/businesses/{id}/orders/{order_id}/complete" => order::complete_order,
+    get "/businesses/{id}/transport/vehicles" => transport::get_my_vehicles,
+    post "/businesses/{id}/transport/vehicles" => transport::create_vehicle,
+    put "/businesses/{id}/transport/vehicles/{vehicle_id}" => transport::update_vehicle,
+    get "/businesses/{id}/transport/routes" => transport::get_my_routes,
+    post "/businesses/{id}/transport/routes" => transport::create_route,
+    put "/businesses/{id}/transport/routes/{route_id}" => transport::update_route,
+    post "/businesses/{id}/transport/trips" => transport::create_trip,
+    put "/businesses/{id}/transport/trips/{trip_id}/cancel" => transport::cancel_trip,
+    get "/businesses/{id}/transport/bookings" => transport::get_business_transport_bookings,
+    put "/businesses/{id}/transport/bookings/{booking_id}/confirm" => transport::confirm_transport_booking,
+    get "/products" => product::get_public_products,
+    get "/products/{id}" => product::get_public_product_detail,
+    put "/products/{id}" => product::update_product,
+    delete "/products/{id}" => product::delete_product,
+    post "/products/{id}/variants" => product::add_product_variant,
+    put "/products/{id}/variants/{variant_id}" => product::update_product_variant,
+    get "/cart" => order::get_cart,
+    delete "/cart" => order::clear_cart,
+    post "/cart/items" => order::add_cart_item,
+    put "/cart/items/{variant_id}" => order::update_cart_item,
+    delete "/cart/items/{variant_id}" => order::remove_cart_item,
+    post "/cart/checkout" => order::checkout,
+    get "/orders/my" => order::get_my_orders,
+    get "/orders/{id}" => order::get_order_detail,
+    put "/orders/{id}/cancel" => order::cancel_order,
+    get "/transport/routes" => transport::get_public_routes,
+    get "/transport/routes/{id}/trips" => transport::get_route_trips,
+    post "/transport/bookings" => transport::create_transport_booking,
+    get "/transport/bookings/my" => transport::get_my_transport_bookings,
+    put "/transport/bookings/{id}/cancel" => transport::cancel_transport_booking,
+    post "/bookings" => booking::create_booking,
+    get "/bookings/my" => booking::get_my_bookings,
+    put "/bookings/{id}/cancel" => booking::cancel_booking,
+    get "/bookings/{id}/voucher.pdf" => document::get_booking_voucher,
+    get "/bookings/{id}/invoice.pdf" => document::get_booking_invoice,
+    get "/bookings/{id}/check-in-token" => check_in::get_booking_check_in_token,
+    get "/transport/bookings/{id}/check-in-token" => check_in::get_transport_check_in_token,
+    post "/check-in" => check_in::check_in_booking,
+    get "/itinerary" => itinerary::get_my_itinerary,
+    get "/itinerary.ics" => itinerary::export_my_itinerary_ics,
+    post "/reviews" => review::create_review,
+    get "/reviews/my" => review::get_my_reviews,
+    put "/reviews/{id}/reply" => review::reply_to_review,
+    post "/favorites" => favorite::add_to_favorites,
+    get "/favorites/lists" => favorite::get_my_favorite_lists,
+    post "/favorites/lists" => favorite::create_favorite_list,
+    get "/favorites/lists/{id}" => favorite::get_favorite_list,
+    put "/favorites/lists/{id}" => favorite::rename_favorite_list,
+    delete "/favorites/lists/{id}" => favorite::delete_favorite_list,
+    post "/favorites/lists/{id}/items" => favorite::add_favorite_list_item,
+    delete "/favorites/lists/{id}/items/{item_id}" => favorite::remove_favorite_list_item,
+    put "/favorites/lists/{id}/share" => favorite::share_favorite_list,
+    delete "/favorites/lists/{id}/share" => favorite::unshare_favorite_list,
+    get "/favorites/lists/{id}/availability" => favorite::get_favorite_list_availability,
+    get "/favorites/shared/{token}" => favorite::get_shared_favorite_list,
+    get "/admin/metrics" => analytics::get_dashboard_stats,
+    get "/admin/hotels" => admin::get_all_hotels,
+    get "/admin/hotels/pending" => admin::get_pending_hotels,
+    put "/admin/hotels/{id}/approve" => admin::approve_hotel,
+    put "/admin/hotels/{id}/reject" => admin::reject_hotel,
+    get "/admin/hotels/{id}/history" => admin::get_hotel_review_history,
+    get "/admin/businesses/pending" => admin::get_pending_businesses,
+    put "/admin/businesses/{id}/approve" => admin::approve_business,
+    put "/admin/businesses/{id}/reject" => admin::reject_business,
+    put "/admin/reviews/{id}/approve" => review::approve_review,
+    put "/admin/reviews/{id}/reject" => review::reject_review,
+    get "/admin/moderation" => moderation::get_moderation_queue,
+    post "/admin/moderation/bulk" => moderation::bulk_moderate,
+    get "/admin/suspensions" => suspension::get_active_suspensions,
+    put "/admin/hotels/{id}/suspend" => suspension::suspend_hotel,
+    put "/admin/hotels/{id}/reinstate" => suspension::reinstate_hotel,
+    put "/admin/businesses/{id}/suspend" => suspension::suspend_business,
+    put "/admin/businesses/{id}/reinstate" => suspension::reinstate_business,
+    get "/admin/translations/missing" => translation::get_missing_translations,
+    get "/notifications" => notification::get_my_notifications,
+    put "/notifications/{id}/read" => notification::mark_notification_read,
+});
+
+// Registro de todas las rutas. El scope sin versión coincide con cualquier /api/..., así que la
+// documentación y las versiones deben registrarse antes que él.
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(health)
+        .service(web::resource("/register").route(web::post().to(auth::register)).wrap(from_fn(deprecation::legacy_api)))
+        .service(web::resource("/login").route(web::post().to(auth::login)).wrap(from_fn(deprecation::legacy_api)))
+        .service(SwaggerUi::new("/api/docs/{_:.*}").config(SwaggerConfig::new([format!("{}/openapi.json", API_V1)])))
+        .service(mount_v1(web::scope(API_V1)))
+        .service(mount_v1(web::scope(LEGACY_API)).wrap(from_fn(deprecation::legacy_api)));
+}
+
+// La aplicación completa con CORS y logging; la usan main.rs y las pruebas de integración
+pub fn build_app(
+    pool: PgPool,
+    settings: Settings,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error = Error,
+        InitError = (),
+    >,
+> {
+    let mut cors = Cors::default()
+        .allow_any_method()
+        .allow_any_header()
+        .expose_headers([request_id::REQUEST_ID_HEADER])
+        .expose_headers(deprecation::DEPRECATION_HEADERS)
+        .max_age(3600);
+    for origin in &settings.server.cors_origins {
+        cors = if origin == "*" { cors.allow_any_origin() } else { cors.allowed_origin(origin) };
+    }
+
+    App::new()
+        .app_data(web::Data::new(pool))
+        .app_data(web::Data::new(settings))
+        .app_data(web::JsonConfig::default().error_handler(errors::json_error_handler))
+        .app_data(web::QueryConfig::default().error_handler(errors::query_error_handler))
+        .app_data(web::PathConfig::default().error_handler(errors::path_error_handler))
+        .wrap(from_fn(locale::locale))
+        .wrap(from_fn(request_id::request_id))
+        .wrap(cors)
+        .wrap(Logger::new(r#"%a "%r" %s %b %T %{x-request-id}o"#))
+        .configure(configure)
+        .default_service(web::to(route_not_found))
+}
+
+// Tareas periódicas en segundo plano
+pub fn spawn_background_jobs(pool: &PgPool) {
+    // Reactiva automáticamente las publicaciones cuya suspensión ya venció
+    let job_pool = pool.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(Duration::from_secs(suspension::SUSPENSION_CHECK_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match suspension::reinstate_expired_suspensions(&job_pool).await {
+                Ok(0) => {}
+                Ok(count) => log::info!("Suspensiones vencidas levantadas: {}", count),
+                Err(e) => log::error!("Error al levantar suspensiones vencidas: {}", e),
+            }
+        }
+    });
+
+    // Descarga periódicamente los calendarios externos de los hoteles
+    let sync_pool = pool.clone();
+    actix_web::rt::spawn(async move {
+        let mut interval = actix_web::rt::time::interval(Duration::from_secs(calendar::CALENDAR_SYNC_INTERVAL_SECS));
+        loop {
+            interval.tick().await;
+            match calendar::sync_calendar_feeds(&sync_pool).await {
+                Ok((0, 0)) => {}
+                Ok((synced, failed)) => log::info!("Calendarios sincronizados: {}, con error: {}", synced, failed),
+                Err(e) => log::error!("Error al sincronizar calendarios: {}", e),
+            }
+        }
+    });
+}
+
+pub fn run(listener: TcpListener, db_pool: PgPool, settings: Settings) -> Result<Server, std::io::Error> {
+    let request_timeout = settings.request_timeout();
+    let keep_alive = settings.keep_alive();
+
+    let server = HttpServer::new(move || build_app(db_pool.clone(), settings.clone()))
+        .client_request_timeout(request_timeout)
+        .keep_alive(keep_alive)
+        .listen(listener)?
+        .run();
 
-// ... (todos tus endpoints estáticos como get_restaurantes, etc.)
-
-// --- La función RUN ---
-// Esta es la función clave que será llamada tanto por main.rs como por las pruebas.
-pub fn run(listener: TcpListener, db_pool: PgPool) -> Result<Server, std::io::Error> {
-    let db_pool = web::Data::new(db_pool);
-    let server = HttpServer::new(move || {
-        let cors = Cors::default()
-            // ... tu configuración de CORS
-            ;
-
-        App::new()
-            .app_data(db_pool.clone())
-            .wrap(cors)
-            .wrap(Logger::default())
-            // ... (todo el registro de rutas y servicios que tenías en main.rs)
-    })
-    .listen(listener)?
-    .run();
-    
     Ok(server)
 }
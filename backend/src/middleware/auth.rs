@@ -1,10 +1,21 @@
-use actix_web::{Error, FromRequest, HttpRequest};
-use actix_web::error::{ErrorUnauthorized, ErrorInternalServerError};
+use actix_web::{http::StatusCode, web, Error, FromRequest, HttpRequest};
 use futures::future::{ok, err, Ready};
 use sqlx::{PgPool, Row};
 
+use crate::config::{AuthSettings, Settings};
 use crate::models::{UserInfo, UserRole};
-use crate::utils::jwt::verify_jwt;
+use crate::utils::{errors::AppError, jwt::verify_jwt};
+
+// Los rechazos usan el mismo formato de error (y el mismo catálogo) que los handlers
+fn unauthorized(key: &'static str) -> Error {
+    AppError::localized(StatusCode::UNAUTHORIZED, key).into()
+}
+
+fn auth_settings(req: &HttpRequest) -> Result<&AuthSettings, Error> {
+    req.app_data::<web::Data<Settings>>()
+        .map(|settings| &settings.get_ref().auth)
+        .ok_or_else(|| AppError::InternalServerError("Configuración no disponible".to_string()).into())
+}
 
 pub struct JwtMiddleware;
 
@@ -13,22 +24,25 @@ impl FromRequest for JwtMiddleware {
     type Future = Ready<Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        let auth = match auth_settings(req) {
+            Ok(auth) => auth,
+            Err(e) => return err(e),
+        };
         if let Some(auth_header) = req.headers().get("Authorization") {
             if let Ok(auth_str) = auth_header.to_str() {
-                if auth_str.starts_with("Bearer ") {
-                    let token = &auth_str[7..];
-                    match verify_jwt(token) {
+                if let Some(token) = auth_str.strip_prefix("Bearer ") {
+                    match verify_jwt(auth, token) {
                         Ok(_claims) => {
                             return ok(JwtMiddleware);
                         }
                         Err(_) => {
-                            return err(ErrorUnauthorized("Token JWT inválido"));
+                            return err(unauthorized("auth.token_invalid"));
                         }
                     }
                 }
             }
         }
-        err(ErrorUnauthorized("Token de autorización inválido o faltante"))
+        err(unauthorized("auth.token_missing"))
     }
 }
 
@@ -37,11 +51,14 @@ impl FromRequest for UserInfo {
     type Future = Ready<Result<Self, Self::Error>>;
 
     fn from_request(req: &HttpRequest, _payload: &mut actix_web::dev::Payload) -> Self::Future {
+        let auth = match auth_settings(req) {
+            Ok(auth) => auth,
+            Err(e) => return err(e),
+        };
         if let Some(auth_header) = req.headers().get("Authorization") {
             if let Ok(auth_str) = auth_header.to_str() {
-                if auth_str.starts_with("Bearer ") {
-                    let token = &auth_str[7..];
-                    match verify_jwt(token) {
+                if let Some(token) = auth_str.strip_prefix("Bearer ") {
+                    match verify_jwt(auth, token) {
                         Ok(claims) => {
                             let role = UserRole::from_string(&claims.role);
                             let user_info = UserInfo {
@@ -51,37 +68,39 @@ impl FromRequest for UserInfo {
                                 first_name: claims.first_name.unwrap_or_else(|| "".to_string()).into(),
                                 last_name: claims.last_name.unwrap_or_else(|| "".to_string()).into(),
                                 phone: claims.phone.unwrap_or_else(|| "".to_string()).into(),
+                                locale: claims.locale,
                             };
                             return ok(user_info);
                         }
                         Err(_) => {
-                            return err(ErrorUnauthorized("Token JWT inválido"));
+                            return err(unauthorized("auth.token_invalid"));
                         }
                     }
                 }
             }
         }
-        err(ErrorUnauthorized("Token de autorización inválido o faltante"))
+        err(unauthorized("auth.token_missing"))
     }
 }
 
 pub async fn get_user_from_token_with_db(
     token: &str,
     pool: &PgPool,
+    auth: &AuthSettings,
 ) -> Result<UserInfo, Error> {
-    let claims = verify_jwt(token)
-        .map_err(|e| ErrorUnauthorized(format!("Token inválido: {}", e)))?;
+    let claims = verify_jwt(auth, token)
+        .map_err(|_| unauthorized("auth.token_invalid"))?;
     
     let user_id: i32 = claims.sub.parse()
-        .map_err(|_| ErrorUnauthorized("ID de usuario inválido"))?;
+        .map_err(|_| unauthorized("auth.token_invalid"))?;
     
     let user_record = sqlx::query(
-        "SELECT id, email, first_name, last_name, phone, role FROM users WHERE id = $1"
+        "SELECT id, email, first_name, last_name, phone, role, preferred_locale FROM users WHERE id = $1"
     )
     .bind(user_id)
     .fetch_optional(pool)
     .await
-    .map_err(|e| ErrorInternalServerError(format!("Error de BD: {}", e)))?;
+    .map_err(AppError::from)?;
     
     match user_record {
         Some(user) => {
@@ -93,8 +112,9 @@ pub async fn get_user_from_token_with_db(
                 first_name: user.try_get("first_name").ok(),
                 last_name: user.try_get("last_name").ok(),
                 phone: user.try_get("phone").ok(),
+                locale: user.try_get::<Option<String>, _>("preferred_locale").unwrap_or(None),
             })
         }
-        None => Err(ErrorUnauthorized("Usuario no encontrado"))
+        None => Err(unauthorized("auth.user_not_found"))
     }
 }
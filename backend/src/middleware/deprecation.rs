@@ -0,0 +1,54 @@
+// Cabeceras de retirada para las rutas sin versión (/api/... y /register, /login en la raíz), que siguen
+// funcionando mientras los clientes migran a /api/v1: Deprecation (RFC 9745), Sunset (RFC 8594) y un
+// Link a la ruta nueva.
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue, LINK},
+    middleware::Next,
+    web, Error,
+};
+use chrono::NaiveTime;
+
+use crate::{config::Settings, API_V1, LEGACY_API, LEGACY_ROOT_ROUTES};
+
+// Se exponen por CORS para que el frontend pueda detectarlas
+pub const DEPRECATION_HEADERS: [&str; 3] = ["deprecation", "sunset", "link"];
+
+fn successor_link(path: &str) -> Option<HeaderValue> {
+    let rest = LEGACY_ROOT_ROUTES
+        .iter()
+        .find(|(legacy, _)| *legacy == path)
+        .map(|(_, current)| *current)
+        .or_else(|| path.strip_prefix(LEGACY_API))?;
+    HeaderValue::from_str(&format!("<{}{}>; rel=\"successor-version\"", API_V1, rest)).ok()
+}
+
+pub async fn legacy_api(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let dates = req
+        .app_data::<web::Data<Settings>>()
+        .map(|settings| (settings.server.legacy_api_deprecated_since, settings.server.legacy_api_sunset));
+    let link = successor_link(req.path());
+
+    let mut res = next.call(req).await?;
+
+    let headers = res.headers_mut();
+    if let Some((deprecated_since, sunset)) = dates {
+        // Deprecation usa una fecha estructurada (@segundos desde epoch); Sunset, una fecha HTTP
+        let since = format!("@{}", deprecated_since.and_time(NaiveTime::MIN).and_utc().timestamp());
+        if let Ok(value) = HeaderValue::from_str(&since) {
+            headers.insert(HeaderName::from_static(DEPRECATION_HEADERS[0]), value);
+        }
+        let http_date = sunset.and_time(NaiveTime::MIN).format("%a, %d %b %Y %H:%M:%S GMT");
+        if let Ok(value) = HeaderValue::from_str(&http_date.to_string()) {
+            headers.insert(HeaderName::from_static(DEPRECATION_HEADERS[1]), value);
+        }
+    }
+    if let Some(link) = link {
+        headers.insert(LINK, link);
+    }
+    Ok(res)
+}
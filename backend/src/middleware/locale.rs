@@ -0,0 +1,55 @@
+// Idioma de cada petición: la preferencia guardada del usuario (viaja en su token) o, si no hay,
+// Accept-Language. Se informa en Content-Language y lo usan los mensajes traducidos.
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{self, HeaderValue},
+    middleware::Next,
+    web, Error,
+};
+
+use crate::config::Settings;
+use crate::utils::{
+    i18n::{negotiate, Locale, DEFAULT_LOCALE},
+    jwt::verify_jwt,
+};
+
+tokio::task_local! {
+    static LOCALE: Locale;
+}
+
+// Idioma de la petición en curso; el idioma por defecto fuera de una petición
+pub fn current_locale() -> Locale {
+    LOCALE.try_with(|locale| *locale).unwrap_or(DEFAULT_LOCALE)
+}
+
+// Preferencia del usuario autenticado; un token inválido se ignora aquí y lo rechaza el extractor
+fn token_preference(req: &ServiceRequest) -> Option<String> {
+    let settings = req.app_data::<web::Data<Settings>>()?;
+    let token = req
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")?;
+    verify_jwt(&settings.auth, token).ok()?.locale
+}
+
+pub async fn locale(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let accept_language = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let locale = negotiate(token_preference(&req).as_deref(), accept_language.as_deref());
+
+    let mut res = LOCALE.scope(locale, next.call(req)).await?;
+
+    let headers = res.headers_mut();
+    headers.insert(header::CONTENT_LANGUAGE, HeaderValue::from_static(locale.code()));
+    headers.append(header::VARY, HeaderValue::from_static("Accept-Language"));
+    Ok(res)
+}
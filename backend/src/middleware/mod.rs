@@ -1,2 +1,5 @@
 pub mod auth;
 
+pub mod request_id;
+pub mod locale;
+pub mod deprecation;
@@ -0,0 +1,44 @@
+// Identificador de cada petición: se respeta el X-Request-Id entrante (si es razonable) o se genera
+// uno nuevo. Se devuelve en la cabecera de la respuesta y en el cuerpo de los errores.
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    middleware::Next,
+    Error,
+};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_INCOMING_ID_LEN: usize = 64;
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+// Request id de la petición en curso; None fuera de una petición (tareas en segundo plano, CLI)
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+fn incoming_id(req: &ServiceRequest) -> Option<String> {
+    let value = req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?.trim();
+    let valid = !value.is_empty()
+        && value.len() <= MAX_INCOMING_ID_LEN
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    valid.then(|| value.to_string())
+}
+
+pub async fn request_id(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    let id = incoming_id(&req).unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+
+    // Los errores de los handlers y extractores se convierten en respuesta dentro de este alcance
+    let mut res = REQUEST_ID.scope(id.clone(), next.call(req)).await?;
+
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        res.headers_mut().insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+    }
+    Ok(res)
+}
@@ -1 +0,0 @@
-[Pega aquí todo el contenido del models.rs que te proporcioné]
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DashboardQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    // "day" (por defecto), "week" o "month"
+    pub granularity: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct HotelAnalyticsQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    // Solo para la exportación CSV: "nightly" (por defecto) o "monthly"
+    pub report: Option<String>,
+}
+
+// Métricas del panel de administración; se guardan un minuto en caché, de ahí el Clone
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DashboardStats {
+    pub users: i64,
+    pub hotels: i64,
+    pub businesses: i64,
+    pub range: DashboardRange,
+    pub bookings: DashboardBookings,
+    pub pending_moderation: PendingModeration,
+    pub series: Vec<DashboardPeriod>,
+    pub occupancy: Vec<HotelOccupancy>,
+    pub top_locations: Vec<LocationStats>,
+    pub generated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DashboardRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub granularity: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DashboardBookings {
+    pub total: i64,
+    pub cancelled: i64,
+    pub cancellation_rate: f64,
+    pub revenue: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PendingModeration {
+    pub hotels: i64,
+    pub businesses: i64,
+    pub reviews: i64,
+}
+
+// Un punto de la serie: el día, la semana o el mes que empieza en `period`
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DashboardPeriod {
+    pub period: NaiveDate,
+    pub bookings: i64,
+    pub cancelled: i64,
+    pub revenue: f64,
+    pub new_users: i64,
+    pub new_hotels: i64,
+    pub new_businesses: i64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct HotelOccupancy {
+    pub hotel_id: i32,
+    pub name: String,
+    pub location: String,
+    pub room_nights_sold: i64,
+    pub room_nights_available: i64,
+    pub occupancy_rate: f64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct LocationStats {
+    pub location: String,
+    pub bookings: i64,
+    pub revenue: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AnalyticsHotel {
+    pub id: i32,
+    pub name: String,
+    pub rooms_available: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DateRange {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+}
+
+// ADR: ingreso por habitación vendida. RevPAR: ingreso por habitación disponible.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PeriodStats {
+    pub nights: i64,
+    pub room_nights_sold: i64,
+    pub room_nights_available: i64,
+    pub occupancy_rate: f64,
+    pub revenue: f64,
+    pub adr: f64,
+    pub revpar: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MonthlyStats {
+    // "AAAA-MM"
+    pub month: String,
+    #[serde(flatten)]
+    pub stats: PeriodStats,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NightOccupancy {
+    pub date: NaiveDate,
+    pub rooms_sold: i64,
+    pub occupancy_rate: f64,
+    pub revenue: f64,
+}
+
+// Reservas según los días de anticipación con que se hicieron
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LeadTimeDistribution {
+    #[serde(rename = "0_7")]
+    pub within_week: i64,
+    #[serde(rename = "8_30")]
+    pub within_month: i64,
+    #[serde(rename = "31_90")]
+    pub within_quarter: i64,
+    #[serde(rename = "90_plus")]
+    pub beyond_quarter: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LeadTime {
+    pub bookings: i64,
+    pub avg_days: Option<f64>,
+    pub median_days: Option<f64>,
+    pub distribution: LeadTimeDistribution,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CancellationStats {
+    pub bookings: i64,
+    pub cancelled: i64,
+    pub cancellation_rate: f64,
+    pub lost_revenue: f64,
+    pub avg_days_before_check_in: Option<f64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelAnalytics {
+    pub hotel: AnalyticsHotel,
+    pub range: DateRange,
+    pub summary: PeriodStats,
+    pub occupancy_by_night: Vec<NightOccupancy>,
+    pub revenue_by_month: Vec<MonthlyStats>,
+    pub lead_time: LeadTime,
+    pub cancellations: CancellationStats,
+}
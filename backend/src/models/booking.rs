@@ -1,29 +1,120 @@
 use serde::{Serialize, Deserialize};
 use sqlx::FromRow;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use validator::Validate;
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "booking_status", rename_all = "snake_case")]
-pub enum BookingStatus { // "pub" añadido aquí
-    Confirmed,
-    Cancelled,
-    Pending,
-}
-
-#[derive(Debug, Serialize, FromRow)]
+#[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct Booking {
     pub id: i32,
+    pub user_id: i32,
     pub hotel_id: i32,
-    pub customer_id: i32,
-    pub check_in_date: DateTime<Utc>,
-    pub check_out_date: DateTime<Utc>,
-    pub status: BookingStatus,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: i32,
+    pub rooms: i32,
+    pub total_price: bigdecimal::BigDecimal,
+    pub special_requests: Option<String>,
+    pub addon_services: Option<serde_json::Value>,
+    pub status: String,
+    pub payment_status: String,
+    pub booking_reference: String,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateBookingRequest {
     pub hotel_id: i32,
-    pub check_in_date: DateTime<Utc>,
-    pub check_out_date: DateTime<Utc>,
+
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+
+    #[validate(range(min = 1, max = 10, message = "El número de huéspedes debe estar entre 1 y 10"))]
+    pub guests: i32,
+
+    #[validate(range(min = 1, max = 5, message = "El número de habitaciones debe estar entre 1 y 5"))]
+    pub rooms: i32,
+
+    pub special_requests: Option<String>,
+    pub addon_services: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateBookingStatusRequest {
+    pub status: Option<String>,
+    pub cancellation_reason: Option<String>,
+}
+
+// Reserva recién creada
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookingSummary {
+    pub id: i32,
+    pub reference: String,
+    pub hotel_name: String,
+    pub hotel_location: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: i32,
+    pub rooms: i32,
+    pub total_price: f64,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookingCreatedResponse {
+    pub message: String,
+    pub booking: BookingSummary,
+}
+
+// Reserva en el listado del cliente
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomerBooking {
+    pub id: i32,
+    pub hotel_name: String,
+    pub hotel_location: String,
+    pub hotel_address: Option<String>,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: i32,
+    pub rooms: i32,
+    pub total_price: f64,
+    pub status: String,
+    pub payment_status: String,
+    pub special_requests: Option<String>,
+    pub addon_services: Option<serde_json::Value>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub booking_reference: String,
+}
+
+// Reserva en el listado del hotel
+#[derive(Debug, Serialize, FromRow, ToSchema)]
+pub struct HotelBookingDetail {
+    pub id: i32,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: i32,
+    pub total_price: f64,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub customer_name: String,
+    pub customer_email: String,
+}
+
+// Estado en que queda una reserva tras confirmarla, cobrarla o cerrar la estancia
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookingState {
+    pub id: i32,
+    pub reference: String,
+    pub status: String,
+    pub payment_status: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BookingStateResponse {
+    pub message: String,
+    pub booking: BookingState,
 }
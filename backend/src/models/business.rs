@@ -1,6 +1,7 @@
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use sqlx::types::JsonValue;
+use utoipa::{IntoParams, ToSchema};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Business {
@@ -23,7 +24,8 @@ pub struct Business {
     pub approved_by: Option<i32>,
 }
 
-#[derive(Debug, Serialize)]
+// Ficha completa del negocio para su propietario
+#[derive(Debug, Serialize, ToSchema)]
 pub struct BusinessResponse {
     pub id: i32,
     pub business_type: String,
@@ -37,11 +39,12 @@ pub struct BusinessResponse {
     pub status: String,
     pub business_data: JsonValue,
     pub operating_hours: JsonValue,
+    pub content_locale: String,
     pub images: Vec<BusinessImage>,
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BusinessImage {
     pub id: i32,
     pub business_id: i32,
@@ -51,7 +54,7 @@ pub struct BusinessImage {
     pub created_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CreateBusinessRequest {
     pub business_type: String,
     pub name: String,
@@ -64,11 +67,94 @@ pub struct CreateBusinessRequest {
     pub business_data: JsonValue,
     pub operating_hours: JsonValue,
     pub images: Option<Vec<BusinessImageInput>>,
+    // Idioma en que el propietario escribe el nombre y la descripción ("es" o "en")
+    pub content_locale: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct BusinessImageInput {
     pub image_url: String,
     pub image_type: String,
     pub display_order: i32,
 }
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PublicBusinessQuery {
+    pub business_type: Option<String>,
+    pub location: Option<String>,
+    pub open_now: Option<bool>,
+    pub q: Option<String>,
+}
+
+// Negocio en el listado del propietario
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OwnerBusiness {
+    pub id: i32,
+    pub name: String,
+    pub business_type: String,
+    pub location: String,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BusinessCreatedResponse {
+    pub message: String,
+    pub business: OwnerBusiness,
+}
+
+// Negocio del directorio público, con el contenido en el idioma de la petición
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicBusiness {
+    pub id: i32,
+    pub business_type: String,
+    pub name: String,
+    pub description: Option<String>,
+    // Idioma en que se devuelve el contenido
+    pub locale: String,
+    pub location: String,
+    pub address: Option<String>,
+    pub operating_hours: JsonValue,
+    pub is_open_now: bool,
+    pub rating: f64,
+    pub review_count: i32,
+    // Primera imagen de la galería
+    pub image_url: Option<String>,
+}
+
+// Ficha pública del negocio
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicBusinessDetail {
+    pub id: i32,
+    pub business_type: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub locale: String,
+    pub location: String,
+    pub address: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    pub business_data: JsonValue,
+    pub operating_hours: JsonValue,
+    pub is_open_now: bool,
+    pub rating: f64,
+    pub review_count: i32,
+    pub images: Vec<PublicBusinessImage>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicBusinessImage {
+    pub image_url: String,
+    pub image_type: String,
+    pub display_order: Option<i32>,
+}
+
+// Negocio en la cola de revisión del administrador
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PendingBusiness {
+    pub id: i32,
+    pub name: String,
+    pub status: String,
+}
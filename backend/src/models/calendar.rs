@@ -0,0 +1,108 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CalendarFeedRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    // http(s) o webcal
+    #[validate(length(min = 1, max = 2000))]
+    pub url: String,
+}
+
+// El cuerpo de la petición es el contenido del archivo .ics
+#[derive(Debug, Deserialize, Validate, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CalendarUploadQuery {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+// Enlace iCalendar que el hotel pega en otras plataformas
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarExport {
+    pub path: String,
+    pub token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarExportResponse {
+    pub message: String,
+    pub export: CalendarExport,
+}
+
+// Calendario externo importado
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeed {
+    pub id: i32,
+    pub name: String,
+    // "url" o "upload"
+    pub source: String,
+    pub url: Option<String>,
+    pub is_active: bool,
+    pub blocked_events: i64,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub last_status: Option<String>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CalendarConflictBooking {
+    pub id: i32,
+    pub reference: String,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub rooms: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CalendarConflictBlock {
+    pub id: i32,
+    pub feed: String,
+    pub summary: Option<String>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+// Noche en que las reservas y los bloqueos externos superan las habitaciones del hotel
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarConflict {
+    pub night: NaiveDate,
+    pub rooms_available: i32,
+    pub booked_rooms: i32,
+    pub blocked_rooms: i32,
+    pub bookings: Vec<CalendarConflictBooking>,
+    pub blocks: Vec<CalendarConflictBlock>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarConflicts {
+    pub hotel_id: i32,
+    pub conflicts: Vec<CalendarConflict>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarSync {
+    pub export: CalendarExport,
+    pub imports: Vec<CalendarFeed>,
+    pub conflicts: Vec<CalendarConflict>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeedAddedResponse {
+    pub message: String,
+    // Motivo por el que falló la primera descarga; el calendario queda registrado igualmente
+    pub sync_error: Option<String>,
+    pub feed: CalendarFeed,
+    pub conflicts: Vec<CalendarConflict>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CalendarFeedResponse {
+    pub message: String,
+    pub feed: CalendarFeed,
+    pub conflicts: Vec<CalendarConflict>,
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::ToSchema;
+
+// Contenido leído del código QR del comprobante
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckInRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub token: String,
+}
+
+// Código QR de una reserva para mostrarlo al llegar
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CheckInTokenResponse {
+    pub token: String,
+    pub booking_reference: String,
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelCheckIn {
+    pub booking_id: i32,
+    pub booking_reference: String,
+    pub hotel_id: i32,
+    pub hotel_name: String,
+    pub customer_name: Option<String>,
+    pub customer_phone: Option<String>,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub guests: i32,
+    pub rooms: i32,
+    pub special_requests: Option<String>,
+    pub checked_in_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransportCheckIn {
+    pub booking_id: i32,
+    pub booking_reference: String,
+    pub business_id: i32,
+    pub trip_id: i32,
+    pub origin: String,
+    pub destination: String,
+    pub departure_at: DateTime<Utc>,
+    pub passenger_name: Option<String>,
+    pub customer_name: Option<String>,
+    pub customer_phone: Option<String>,
+    pub seats: i32,
+    pub flight_number: Option<String>,
+    pub special_requests: Option<String>,
+    pub checked_in_at: DateTime<Utc>,
+}
+
+// Reserva registrada; "kind" indica si es una estancia o un traslado
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum CheckedInBooking {
+    Hotel(HotelCheckIn),
+    Transport(TransportCheckIn),
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CheckInResponse {
+    pub message: String,
+    pub booking: CheckedInBooking,
+}
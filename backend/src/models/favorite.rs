@@ -0,0 +1,143 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct FavoriteListRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+// Indicar exactamente uno de `hotel_id`, `business_id` o `product_id`.
+// Sin `list_id` el elemento se guarda en la lista predeterminada.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AddFavoriteRequest {
+    pub list_id: Option<i32>,
+    pub hotel_id: Option<i32>,
+    pub business_id: Option<i32>,
+    pub product_id: Option<i32>,
+    #[validate(length(max = 500))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FavoriteAvailabilityQuery {
+    pub check_in: chrono::NaiveDate,
+    pub check_out: chrono::NaiveDate,
+    pub rooms: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteList {
+    pub id: i32,
+    pub name: String,
+    pub shared: bool,
+    // Ruta pública de la lista mientras está compartida
+    pub share_path: Option<String>,
+    pub share_token: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteListSummary {
+    #[serde(flatten)]
+    pub list: FavoriteList,
+    pub item_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteLists {
+    pub lists: Vec<FavoriteListSummary>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteListResponse {
+    pub message: String,
+    pub list: FavoriteList,
+}
+
+// Hotel, negocio o producto guardado, con sus datos públicos
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteItem {
+    pub id: i32,
+    // "hotel", "business" o "product"
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub target_id: Option<i32>,
+    pub name: Option<String>,
+    pub location: Option<String>,
+    pub image_url: Option<String>,
+    pub price: Option<f64>,
+    pub rating: Option<f64>,
+    pub business_type: Option<String>,
+    pub artisan: Option<String>,
+    // Si sigue visible en el directorio
+    pub is_listed: bool,
+    pub added_at: DateTime<Utc>,
+}
+
+// Elemento visto por el dueño de la lista, con sus notas
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SavedFavoriteItem {
+    #[serde(flatten)]
+    pub item: FavoriteItem,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteListDetail {
+    #[serde(flatten)]
+    pub list: FavoriteList,
+    pub items: Vec<SavedFavoriteItem>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteListDetailResponse {
+    pub list: FavoriteListDetail,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteSavedResponse {
+    pub message: String,
+    pub list_id: i32,
+    pub item_id: i32,
+}
+
+// Lista compartida: sin notas y solo con lo que sigue publicado
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SharedFavoriteList {
+    pub name: String,
+    pub owner_name: Option<String>,
+    pub updated_at: DateTime<Utc>,
+    pub items: Vec<FavoriteItem>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SharedFavoriteListResponse {
+    pub list: SharedFavoriteList,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteHotelAvailability {
+    pub hotel_id: i32,
+    pub name: String,
+    pub location: String,
+    pub image_url: Option<String>,
+    pub price_per_night: f64,
+    pub estimated_total: f64,
+    pub rooms_left: i32,
+    pub available: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FavoriteListAvailability {
+    pub list_id: i32,
+    pub check_in: NaiveDate,
+    pub check_out: NaiveDate,
+    pub nights: i64,
+    pub rooms: i32,
+    pub hotels: Vec<FavoriteHotelAvailability>,
+}
@@ -2,6 +2,7 @@ use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
 use bigdecimal::BigDecimal;
 use validator::Validate;
+use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Hotel {
@@ -32,7 +33,7 @@ pub enum HotelStatus {
     Rejected,
 }
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
 pub struct CreateHotelRequest {
     #[validate(length(min = 1, max = 255))]
     pub name: String,
@@ -52,18 +53,129 @@ pub struct CreateHotelRequest {
     pub website: Option<String>,
     #[validate(range(min = 1))]
     pub rooms_available: i32,
+    pub amenities: Option<Vec<String>>,
+    // Idioma en que el propietario escribe el nombre y la descripción ("es" o "en")
+    pub content_locale: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+// Hotel del directorio público, con el contenido en el idioma de la petición
+#[derive(Debug, Serialize, ToSchema)]
 pub struct PublicHotel {
     pub id: i32,
     pub name: String,
+    pub description: Option<String>,
+    pub amenities: Vec<String>,
+    // Idioma en que se devuelve el contenido
+    pub locale: String,
     pub location: String,
     pub price: f64,
     pub image_url: Option<String>,
+    pub rating: f64,
+    pub review_count: i32,
+}
+
+// Hotel en el listado del propietario, con la última decisión de moderación
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OwnerHotel {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub location: String,
+    pub address: Option<String>,
+    // Decimal exacto como texto
+    pub price: String,
+    pub image_url: Option<String>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub admin_notes: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub website: Option<String>,
+    pub rooms_available: i32,
+    pub rating: Option<String>,
+    pub amenities: Vec<String>,
+    pub content_locale: String,
+    pub latest_review: Option<LatestHotelReview>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LatestHotelReview {
+    pub action: String,
+    pub notes: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelSummary {
+    pub id: i32,
+    pub name: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelCreatedResponse {
+    pub message: String,
+    pub hotel: HotelSummary,
 }
 
-#[derive(Debug, Deserialize)]
+// Resultado de editar o reenviar un hotel: el estado en que queda
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelStatusResponse {
+    pub message: String,
+    pub status: String,
+}
+
+// Entrada del historial de revisión (hotel_review_history)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelReviewEntry {
+    pub id: i32,
+    pub action: String,
+    pub notes: Option<String>,
+    pub actor_id: Option<i32>,
+    pub actor_name: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct HotelReviewHistory {
+    pub history: Vec<HotelReviewEntry>,
+}
+
+// Hotel en la cola de revisión del administrador
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PendingHotel {
+    pub id: i32,
+    pub name: String,
+    pub status: String,
+    pub admin_notes: Option<String>,
+    // Ya fue rechazado antes y el propietario lo reenvió
+    pub resubmitted: bool,
+}
+
+// Hotel en el listado completo del administrador
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AdminHotel {
+    pub id: i32,
+    pub owner_id: i32,
+    pub name: String,
+    pub location: String,
+    pub status: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct RejectReason {
     pub reason: String,
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ApproveNotes {
+    pub admin_notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ResubmitHotelRequest {
+    pub notes: Option<String>,
+}
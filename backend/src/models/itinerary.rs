@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use chrono::{NaiveDate, NaiveDateTime};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ItineraryQuery {
+    // Por defecto desde hoy y sin fecha final
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+// Reserva dentro de un día del itinerario; las horas son locales de la zona del viaje
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ItineraryEntry {
+    // "hotel" o "transfer"
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub id: i32,
+    pub reference: String,
+    pub status: String,
+    pub title: String,
+    pub location: String,
+    pub starts_at: NaiveDateTime,
+    pub ends_at: NaiveDateTime,
+    pub total_price: f64,
+    // Datos propios de cada tipo de reserva (fechas de la estancia, vuelo, vehículo...)
+    pub details: serde_json::Value,
+    // "start", "end" u "ongoing" según lo que ocurre con la reserva ese día
+    pub moment: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ItineraryDay {
+    pub date: NaiveDate,
+    pub items: Vec<ItineraryEntry>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ItineraryBookingRef {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub id: i32,
+    pub reference: String,
+}
+
+// Dos reservas del mismo viaje que se solapan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ItineraryConflict {
+    pub items: Vec<ItineraryBookingRef>,
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ItineraryTrip {
+    pub trip: usize,
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+    pub locations: Vec<String>,
+    pub bookings: usize,
+    pub total_price: f64,
+    pub days: Vec<ItineraryDay>,
+    pub conflicts: Vec<ItineraryConflict>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Itinerary {
+    pub timezone: String,
+    pub trips: Vec<ItineraryTrip>,
+    pub total_bookings: usize,
+    pub total_conflicts: usize,
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use chrono::NaiveTime;
+use validator::Validate;
+use utoipa::ToSchema;
+
+// Etiquetas dietéticas aceptadas para los platillos del menú.
+pub const DIETARY_TAGS: &[&str] = &[
+    "vegetarian",
+    "vegan",
+    "gluten_free",
+    "dairy_free",
+    "nut_free",
+    "spicy",
+];
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MenuSectionRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    pub display_order: Option<i32>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct MenuItemRequest {
+    pub section_id: i32,
+    #[validate(length(min = 1, max = 150))]
+    pub name: String,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    #[validate(range(min = 0.0))]
+    pub price: f64,
+    pub dietary_tags: Option<Vec<String>>,
+    pub allergens: Option<Vec<String>>,
+    pub available_from: Option<NaiveTime>,
+    pub available_until: Option<NaiveTime>,
+    #[validate(url)]
+    pub image_url: Option<String>,
+    pub is_active: Option<bool>,
+    pub display_order: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuItem {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    // Idioma del nombre y la descripción; solo en el menú público
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    pub price: f64,
+    pub dietary_tags: Vec<String>,
+    pub allergens: Vec<String>,
+    pub image_url: Option<String>,
+    // Horario "HH:MM:SS" en que se sirve; null si se sirve todo el día
+    pub available_from: Option<String>,
+    pub available_until: Option<String>,
+    pub is_active: bool,
+    pub sold_out: bool,
+    pub available_now: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuSection {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub display_order: i32,
+    pub items: Vec<MenuItem>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicMenu {
+    pub business_id: i32,
+    pub business_name: String,
+    pub sections: Vec<MenuSection>,
+}
+
+// Menú completo, con los platillos desactivados
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OwnerMenu {
+    pub business_id: i32,
+    pub sections: Vec<MenuSection>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuSectionCreatedResponse {
+    pub message: String,
+    pub section_id: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MenuItemCreatedResponse {
+    pub message: String,
+    pub item_id: i32,
+}
@@ -1,13 +1,43 @@
 // models/mod.rs
 
+use serde::Serialize;
+use utoipa::ToSchema;
+
 pub mod user;
 pub mod hotel;
 pub mod booking;
 pub mod business;
+pub mod menu;
+pub mod product;
+pub mod order;
+pub mod transport;
+pub mod suspension;
+pub mod moderation;
+pub mod analytics;
+pub mod review;
+pub mod favorite;
+pub mod itinerary;
+pub mod calendar;
+pub mod check_in;
+pub mod translation;
+pub mod notification;
 
 // Re-export main types
 pub use user::*;
 pub use hotel::*;
 pub use booking::*;
 // 🔥 COMENTADO para evitar warning si no se usa
-// pub use business::*;
\ No newline at end of file
+// pub use business::*;
+
+// Respuesta de las acciones que solo confirman el resultado
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MessageResponse {
+    pub message: String,
+}
+
+// Respuesta de una creación que solo devuelve el id del registro nuevo
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedResponse {
+    pub message: String,
+    pub id: i32,
+}
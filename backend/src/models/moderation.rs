@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ModerationQueueQuery {
+    // "hotel", "business" o "review"; sin valor se listan todos
+    #[serde(rename = "type")]
+    pub item_type: Option<String>,
+    pub location: Option<String>,
+    // Antigüedad del envío en horas
+    pub min_age_hours: Option<i64>,
+    pub max_age_hours: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ModerationItemRef {
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub id: i32,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct BulkModerationRequest {
+    // "approve" o "reject"
+    pub action: String,
+    #[validate(length(max = 1000))]
+    pub reason: Option<String>,
+    pub items: Vec<ModerationItemRef>,
+}
+
+// Elemento de la cola de moderación
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModerationItem {
+    // "hotel", "business" o "review"
+    #[serde(rename = "type")]
+    pub item_type: String,
+    pub id: i32,
+    pub name: String,
+    pub location: String,
+    pub submitted_at: DateTime<Utc>,
+    pub resubmitted: bool,
+    pub owner: ModerationOwner,
+    // Datos propios de cada tipo para decidir sin abrir la ficha
+    pub details: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModerationOwner {
+    pub id: i32,
+    pub name: Option<String>,
+    pub email: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkModerationResponse {
+    pub message: String,
+    // Estado aplicado: "approved" o "rejected"
+    pub status: String,
+    pub processed: usize,
+}
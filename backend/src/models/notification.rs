@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Notification {
+    pub id: i32,
+    pub kind: String,
+    pub title: String,
+    pub message: String,
+    // Ids de lo que originó el aviso (reserva, pedido, negocio...)
+    pub data: serde_json::Value,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Últimas 100 notificaciones y cuántas siguen sin leer
+#[derive(Debug, Serialize, ToSchema)]
+pub struct NotificationList {
+    pub notifications: Vec<Notification>,
+    pub unread: i64,
+}
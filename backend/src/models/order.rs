@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AddCartItemRequest {
+    pub variant_id: i32,
+    #[validate(range(min = 1, max = 50))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateCartItemRequest {
+    #[validate(range(min = 1, max = 50))]
+    pub quantity: i32,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CheckoutRequest {
+    #[validate(length(min = 5, max = 500))]
+    pub shipping_address: String,
+    #[validate(length(max = 1000))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CancelOrderRequest {
+    pub cancellation_reason: Option<String>,
+}
+
+// Línea del carrito con el precio vigente de la variante
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CartItem {
+    pub variant_id: i32,
+    pub product_id: i32,
+    pub product_name: String,
+    pub variant_name: String,
+    pub artisan: String,
+    pub image_url: Option<String>,
+    pub price: f64,
+    pub quantity: i32,
+    pub line_total: f64,
+    // El producto sigue publicado y el taller aprobado
+    pub available: bool,
+    pub in_stock: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CartResponse {
+    pub items: Vec<CartItem>,
+    pub total: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CartItemAddedResponse {
+    pub message: String,
+    pub variant_id: i32,
+    // Unidades de la variante que quedan en el carrito
+    pub quantity: i32,
+    pub in_stock: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderSummary {
+    pub id: i32,
+    pub reference: String,
+    pub total_price: f64,
+    // Número de líneas del pedido
+    pub items: usize,
+    pub status: String,
+    pub payment_status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderCreatedResponse {
+    pub message: String,
+    pub order: OrderSummary,
+}
+
+// Pedido en el listado del cliente
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomerOrder {
+    pub id: i32,
+    pub reference: String,
+    pub total_price: f64,
+    pub status: String,
+    pub payment_status: String,
+    // Unidades en total
+    pub item_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderItem {
+    pub product_id: Option<i32>,
+    pub variant_id: Option<i32>,
+    pub product_name: String,
+    pub variant_name: String,
+    pub unit_price: f64,
+    pub quantity: i32,
+    pub line_total: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderDetail {
+    pub id: i32,
+    pub reference: String,
+    pub total_price: f64,
+    pub status: String,
+    pub payment_status: String,
+    pub shipping_address: String,
+    pub notes: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub cancelled_at: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
+    pub items: Vec<OrderItem>,
+}
+
+// Línea de pedido que atiende el taller
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BusinessOrderLine {
+    pub order_id: i32,
+    pub reference: String,
+    pub status: String,
+    pub payment_status: String,
+    pub shipping_address: String,
+    pub created_at: DateTime<Utc>,
+    pub product_name: String,
+    pub variant_name: String,
+    pub quantity: i32,
+    pub line_total: f64,
+    pub customer_name: Option<String>,
+    pub customer_email: String,
+}
+
+// Estado en que queda un pedido tras confirmarlo, cobrarlo o entregarlo
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderState {
+    pub id: i32,
+    pub reference: String,
+    pub status: String,
+    pub payment_status: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct OrderStateResponse {
+    pub message: String,
+    pub order: OrderState,
+}
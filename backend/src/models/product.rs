@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::JsonValue;
+use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateProductRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub category: String,
+    #[validate]
+    pub variants: Vec<ProductVariantInput>,
+    #[validate]
+    pub images: Option<Vec<ProductImageInput>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateProductRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: String,
+    #[validate(length(max = 2000))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub category: String,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ProductVariantInput {
+    #[validate(length(min = 1, max = 64))]
+    pub sku: Option<String>,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    pub attributes: Option<JsonValue>,
+    #[validate(range(min = 0.01))]
+    pub price: f64,
+    #[validate(range(min = 0))]
+    pub stock: i32,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ProductImageInput {
+    #[validate(url)]
+    pub image_url: String,
+    pub display_order: i32,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PublicProductQuery {
+    pub business_id: Option<i32>,
+    pub category: Option<String>,
+    pub q: Option<String>,
+}
+
+// Producto del catálogo público, con el precio de su variante más barata
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicProduct {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: String,
+    pub business_id: i32,
+    pub artisan: String,
+    pub price: Option<f64>,
+    pub in_stock: bool,
+    pub image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProductVariant {
+    pub id: i32,
+    pub sku: Option<String>,
+    pub name: String,
+    pub attributes: JsonValue,
+    pub price: f64,
+    pub stock: i32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProductDetail {
+    pub id: i32,
+    pub name: String,
+    pub description: Option<String>,
+    pub category: String,
+    pub business_id: i32,
+    pub artisan: String,
+    pub variants: Vec<ProductVariant>,
+    pub images: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProductSummary {
+    pub id: i32,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProductCreatedResponse {
+    pub message: String,
+    pub product: ProductSummary,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VariantCreatedResponse {
+    pub message: String,
+    pub variant_id: i32,
+}
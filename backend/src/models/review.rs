@@ -0,0 +1,121 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::ToSchema;
+
+use crate::utils::pagination::PageResponse;
+
+// Indicar exactamente una fuente: `booking_id` (hotel), `transport_booking_id`,
+// o `order_id` junto con el `business_id` del artesano reseñado
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateReviewRequest {
+    pub booking_id: Option<i32>,
+    pub order_id: Option<i32>,
+    pub business_id: Option<i32>,
+    pub transport_booking_id: Option<i32>,
+    #[validate(range(min = 1, max = 5))]
+    pub overall_rating: i16,
+    #[validate(range(min = 1, max = 5))]
+    pub cleanliness_rating: Option<i16>,
+    #[validate(range(min = 1, max = 5))]
+    pub service_rating: Option<i16>,
+    #[validate(range(min = 1, max = 5))]
+    pub location_rating: Option<i16>,
+    #[validate(range(min = 1, max = 5))]
+    pub value_rating: Option<i16>,
+    #[validate(length(max = 200))]
+    pub title: Option<String>,
+    #[validate(length(min = 10, max = 3000))]
+    pub comment: String,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReviewReplyRequest {
+    #[validate(length(min = 1, max = 2000))]
+    pub reply: String,
+}
+
+// Campos comunes de una reseña
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Review {
+    pub id: i32,
+    pub overall_rating: i16,
+    pub cleanliness_rating: Option<i16>,
+    pub service_rating: Option<i16>,
+    pub location_rating: Option<i16>,
+    pub value_rating: Option<i16>,
+    pub title: Option<String>,
+    pub comment: String,
+    pub owner_reply: Option<String>,
+    pub owner_replied_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+// Reseña en el listado de su autor, con el estado de moderación
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MyReview {
+    #[serde(flatten)]
+    pub review: Review,
+    pub hotel_id: Option<i32>,
+    pub business_id: Option<i32>,
+    pub listing_name: Option<String>,
+    pub status: String,
+    pub moderation_notes: Option<String>,
+}
+
+// Reseña publicada; del autor se muestra el nombre y la inicial del apellido
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicReview {
+    #[serde(flatten)]
+    pub review: Review,
+    pub reviewer: Option<String>,
+}
+
+// Medias de las reseñas aprobadas del listado completo
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RatingSummary {
+    pub review_count: i64,
+    pub overall: Option<f64>,
+    pub cleanliness: Option<f64>,
+    pub service: Option<f64>,
+    pub location: Option<f64>,
+    pub value: Option<f64>,
+    pub distribution: RatingDistribution,
+}
+
+// Número de reseñas por estrellas
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RatingDistribution {
+    #[serde(rename = "5")]
+    pub five: i64,
+    #[serde(rename = "4")]
+    pub four: i64,
+    #[serde(rename = "3")]
+    pub three: i64,
+    #[serde(rename = "2")]
+    pub two: i64,
+    #[serde(rename = "1")]
+    pub one: i64,
+}
+
+// Página de reseñas publicadas junto con el resumen de calificaciones
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicReviewPage {
+    pub summary: RatingSummary,
+    #[serde(flatten)]
+    #[schema(inline)]
+    pub page: PageResponse<PublicReview>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewSummary {
+    pub id: i32,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ReviewCreatedResponse {
+    pub message: String,
+    pub review: ReviewSummary,
+}
@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use validator::Validate;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SuspendListingRequest {
+    #[validate(length(min = 1, max = 1000))]
+    pub reason: String,
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ReinstateListingRequest {
+    #[validate(length(max = 1000))]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Suspension {
+    pub id: i32,
+    // "hotel" o "business"
+    pub listing_type: String,
+    pub listing_id: i32,
+    pub reason: String,
+    pub suspended_at: DateTime<Utc>,
+    // Sin fecha la suspensión dura hasta que se levante a mano
+    pub ends_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuspensionResponse {
+    pub message: String,
+    pub suspension: Suspension,
+}
+
+// Suspensión vigente en el panel de administración
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ActiveSuspension {
+    pub id: i32,
+    pub listing_type: String,
+    pub listing_id: i32,
+    pub listing_name: Option<String>,
+    pub reason: String,
+    pub suspended_at: DateTime<Utc>,
+    pub ends_at: Option<DateTime<Utc>>,
+    // Nombre del administrador que la aplicó
+    pub suspended_by: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SuspensionList {
+    pub suspensions: Vec<ActiveSuspension>,
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
+
+// Traducción de una publicación a un idioma. Los campos omitidos se toman del idioma de respaldo.
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct TranslationRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub name: Option<String>,
+    #[validate(length(max = 1000))]
+    pub description: Option<String>,
+    // Solo aplica a hoteles
+    pub amenities: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MissingTranslationsQuery {
+    pub locale: String,
+    // hotel, business o menu_item; sin valor se revisan todos
+    pub listing_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListingTranslation {
+    pub locale: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub amenities: Option<Vec<String>>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Traducciones guardadas de una publicación y los idiomas que aún faltan
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ListingTranslations {
+    pub listing_type: String,
+    pub id: i32,
+    pub content_locale: String,
+    pub translations: Vec<ListingTranslation>,
+    pub missing_locales: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TranslationSavedResponse {
+    pub message: String,
+    pub locale: String,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MissingTranslation {
+    pub listing_type: String,
+    pub id: i32,
+    pub name: String,
+    // Negocio del platillo o del propio negocio; null en los hoteles
+    pub business_id: Option<i32>,
+    pub owner_email: String,
+    pub missing_fields: Vec<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MissingTranslations {
+    pub locale: String,
+    pub total: usize,
+    pub missing: Vec<MissingTranslation>,
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDate, Utc};
+use validator::Validate;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VehicleRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(min = 1, max = 50))]
+    pub vehicle_type: String,
+    #[validate(length(max = 20))]
+    pub plate: Option<String>,
+    #[validate(range(min = 1, max = 60))]
+    pub seat_capacity: i32,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RouteRequest {
+    #[validate(length(min = 1, max = 255))]
+    pub origin: String,
+    #[validate(length(min = 1, max = 255))]
+    pub destination: String,
+    #[validate(range(min = 0.1))]
+    pub distance_km: f64,
+    #[validate(range(min = 1))]
+    pub duration_minutes: i32,
+    #[validate(range(min = 0.01))]
+    pub fixed_price: Option<f64>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateTripRequest {
+    pub route_id: i32,
+    pub vehicle_id: i32,
+    pub departure_at: DateTime<Utc>,
+    #[validate(range(min = 0.0))]
+    pub price_per_seat: Option<f64>,
+}
+
+// Reserva sobre una salida programada (`trip_id`) o un traslado privado a demanda
+// (`route_id` + `departure_at`)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateTransportBookingRequest {
+    pub trip_id: Option<i32>,
+    pub route_id: Option<i32>,
+    pub departure_at: Option<DateTime<Utc>>,
+    #[validate(range(min = 1, max = 60))]
+    pub seats: i32,
+    #[validate(length(max = 200))]
+    pub passenger_name: Option<String>,
+    #[validate(length(max = 20))]
+    pub flight_number: Option<String>,
+    #[validate(length(max = 1000))]
+    pub special_requests: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RouteSearchQuery {
+    pub origin: Option<String>,
+    pub destination: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TripSearchQuery {
+    pub date: Option<NaiveDate>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Vehicle {
+    pub id: i32,
+    pub name: String,
+    pub vehicle_type: String,
+    pub plate: Option<String>,
+    pub seat_capacity: i32,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct VehicleList {
+    pub vehicles: Vec<Vehicle>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Route {
+    pub id: i32,
+    pub origin: String,
+    pub destination: String,
+    pub distance_km: f64,
+    pub duration_minutes: i32,
+    pub fixed_price: Option<f64>,
+    // Tarifa vigente: el precio fijo o el calculado por distancia y duración
+    pub fare: f64,
+    pub is_active: bool,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RouteList {
+    pub routes: Vec<Route>,
+}
+
+// Ruta publicada con el operador que la ofrece
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PublicRoute {
+    #[serde(flatten)]
+    pub route: Route,
+    pub business_id: i32,
+    pub business_name: String,
+    // Plazas del vehículo más grande para traslados privados; null si el operador no tiene vehículos activos
+    pub on_demand_max_seats: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RouteCreatedResponse {
+    pub message: String,
+    pub id: i32,
+    pub fare: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RouteUpdatedResponse {
+    pub message: String,
+    pub fare: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TripCreatedResponse {
+    pub message: String,
+    pub id: i32,
+    pub departure_at: DateTime<Utc>,
+    pub arrival_at: DateTime<Utc>,
+    pub price_per_seat: f64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TripCancelledResponse {
+    pub message: String,
+    // Reservas canceladas junto con la salida
+    pub cancelled_bookings: u64,
+}
+
+// Salida programada con los asientos que quedan libres
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Trip {
+    pub id: i32,
+    pub departure_at: DateTime<Utc>,
+    pub arrival_at: DateTime<Utc>,
+    pub price_per_seat: f64,
+    pub vehicle_name: String,
+    pub vehicle_type: String,
+    pub seat_capacity: i32,
+    pub seats_available: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransportBookingSummary {
+    pub id: i32,
+    pub reference: String,
+    pub trip_id: i32,
+    // "scheduled" u "on_demand"
+    pub trip_type: String,
+    pub departure_at: DateTime<Utc>,
+    pub seats: i32,
+    pub total_price: f64,
+    pub status: String,
+    pub payment_status: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransportBookingCreatedResponse {
+    pub message: String,
+    pub booking: TransportBookingSummary,
+}
+
+// Reserva de transporte en el listado del cliente
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CustomerTransportBooking {
+    pub id: i32,
+    pub reference: String,
+    pub seats: i32,
+    pub total_price: f64,
+    pub status: String,
+    pub payment_status: String,
+    pub passenger_name: Option<String>,
+    pub flight_number: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub trip_type: String,
+    pub departure_at: DateTime<Utc>,
+    pub arrival_at: DateTime<Utc>,
+    pub origin: String,
+    pub destination: String,
+    pub vehicle_name: String,
+    pub vehicle_type: String,
+    pub business_name: String,
+    pub business_phone: Option<String>,
+}
+
+// Reserva de transporte en el listado del operador
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BusinessTransportBooking {
+    pub id: i32,
+    pub reference: String,
+    pub seats: i32,
+    pub total_price: f64,
+    pub status: String,
+    pub payment_status: String,
+    pub passenger_name: Option<String>,
+    pub flight_number: Option<String>,
+    pub special_requests: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub trip_id: i32,
+    pub trip_type: String,
+    pub departure_at: DateTime<Utc>,
+    pub origin: String,
+    pub destination: String,
+    pub vehicle_name: String,
+    pub customer_name: Option<String>,
+    pub customer_email: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransportBookingState {
+    pub id: i32,
+    pub reference: String,
+    pub status: String,
+    pub payment_status: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TransportBookingStateResponse {
+    pub message: String,
+    pub booking: TransportBookingState,
+}
@@ -1,5 +1,7 @@
 use serde::{Serialize, Deserialize};
 use chrono::{DateTime, Utc};
+use validator::Validate;
+use utoipa::ToSchema;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct User {
@@ -13,7 +15,7 @@ pub struct User {
     pub created_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct UserInfo {
     pub id: i32,
     pub email: String,
@@ -21,9 +23,11 @@ pub struct UserInfo {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub phone: Option<String>,
+    // Idioma preferido ("es", "en"); None si el usuario no lo ha elegido
+    pub locale: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, ToSchema)]
 pub enum UserRole {
     Admin,
     HotelOwner,
@@ -39,13 +43,15 @@ impl UserRole {
             _ => UserRole::Customer,
         }
     }
+}
 
-    pub fn to_string(&self) -> String {
-        match self {
-            UserRole::Admin => "admin".to_string(),
-            UserRole::HotelOwner => "hotel_owner".to_string(),
-            UserRole::Customer => "customer".to_string(),
-        }
+impl std::fmt::Display for UserRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UserRole::Admin => "admin",
+            UserRole::HotelOwner => "hotel_owner",
+            UserRole::Customer => "customer",
+        })
     }
 }
 
@@ -59,8 +65,50 @@ pub struct CreateUserRequest {
     pub role: UserRole,
 }
 
-#[derive(Debug, Deserialize)]
+// Request para registro (siempre como cliente)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct RegisterRequest {
+    #[validate(length(min = 2, max = 50, message = "El nombre debe tener entre 2 y 50 caracteres"))]
+    pub first_name: String,
+
+    #[validate(length(min = 2, max = 50, message = "El apellido debe tener entre 2 y 50 caracteres"))]
+    pub last_name: String,
+
+    #[validate(email(message = "Debe ser un email válido"))]
+    pub email: String,
+
+    #[validate(length(min = 6, message = "La contraseña debe tener al menos 6 caracteres"))]
+    pub password: String,
+
+    #[validate(length(max = 20))]
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct LoginRequest {
+    #[validate(email(message = "Debe ser un email válido"))]
     pub email: String,
+
+    #[validate(length(min = 1, message = "La contraseña es requerida"))]
     pub password: String,
 }
+
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateLocaleRequest {
+    #[validate(length(min = 2, max = 10))]
+    pub locale: String,
+}
+
+// Respuesta de autenticación
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AuthResponse {
+    pub message: String,
+    pub token: String,
+    pub user: UserInfo,
+}
+
+// Usuario del token en curso
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MeResponse {
+    pub user: UserInfo,
+}
@@ -0,0 +1,437 @@
+// Especificación OpenAPI 3 de la API v1, servida en /api/v1/openapi.json y visible en /api/docs/.
+// Los cuerpos, parámetros y respuestas salen de los tipos de models::* (ToSchema / IntoParams); cada ruta
+// registrada en lib.rs (V1_ROUTES) debe tener aquí su operación, y tests/openapi.rs lo comprueba.
+// Las rutas sin versión (/api/...) son las mismas y no se documentan aparte.
+
+use std::sync::OnceLock;
+
+use actix_web::HttpResponse;
+use utoipa::{
+    openapi::{
+        path::{HttpMethod, OperationBuilder, ParameterBuilder, ParameterIn},
+        request_body::RequestBodyBuilder,
+        schema::{KnownFormat, ObjectBuilder, SchemaFormat, Type},
+        security::{HttpAuthScheme, HttpBuilder, SecurityRequirement, SecurityScheme},
+        ComponentsBuilder, Content, InfoBuilder, OpenApi, OpenApiBuilder, Paths, Ref, RefOr,
+        Required, ResponseBuilder, Schema,
+    },
+    IntoParams, PartialSchema, ToSchema,
+};
+
+use crate::{
+    handlers::{
+        admin::{AllHotels, PendingBusinesses, PendingHotels},
+        booking::{HotelBookings, MyBookings},
+        business::{MyBusinesses, PublicBusinesses},
+        hotel::{MyHotels, PublicHotels},
+        moderation::ModerationQueue,
+        order::{BusinessOrders, MyOrders},
+        product::PublicProducts,
+        review::{MyReviews, PublicReviews},
+        transport::{BusinessTransportBookings, MyTransportBookings, PublicRoutes, RouteTrips},
+    },
+    models::{
+        analytics::*, booking::*, business::*, calendar::*, check_in::*, favorite::*, hotel::*,
+        itinerary::*, menu::*, moderation::*, notification::*, order::*, product::*, review::*, suspension::*,
+        translation::*, transport::*, user::*, CreatedResponse, MessageResponse,
+    },
+    utils::{errors::ErrorBody, pagination::{Page, PageResponse}},
+    API_V1,
+};
+
+const BEARER_AUTH: &str = "bearerAuth";
+
+type Components = Vec<(String, RefOr<Schema>)>;
+
+// Esquema publicado de los documentos OpenAPI 3.1, para describir /openapi.json
+const OPENAPI_SCHEMA_URL: &str = "https://spec.openapis.org/oas/3.1/schema/2022-10-07";
+
+// Respuesta correcta de una operación
+enum Success {
+    Typed(String),
+    // Esquema fuera de components, referenciado por URL
+    External(&'static str),
+    // Archivo generado: PDF, iCalendar o CSV
+    File(&'static str),
+}
+
+// Una operación de la especificación; se arma con los métodos de abajo
+struct Op {
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    public: bool,
+    body: Option<(String, bool)>,
+    raw_body: Option<&'static str>,
+    query: Vec<utoipa::openapi::path::Parameter>,
+    status: &'static str,
+    // Toda operación declara su respuesta con returns, page o produces
+    success: Option<Success>,
+    components: Components,
+}
+
+fn op(method: &'static str, path: &'static str, tag: &'static str, summary: &'static str) -> Op {
+    Op {
+        method,
+        path,
+        tag,
+        summary,
+        public: false,
+        body: None,
+        raw_body: None,
+        query: Vec::new(),
+        status: "200",
+        success: None,
+        components: Vec::new(),
+    }
+}
+
+fn component<T: ToSchema>(components: &mut Components) -> String {
+    let name = T::name().to_string();
+    components.push((name.clone(), T::schema()));
+    T::schemas(components);
+    name
+}
+
+impl Op {
+    // No requiere token
+    fn public(mut self) -> Self {
+        self.public = true;
+        self
+    }
+
+    fn body<T: ToSchema>(mut self) -> Self {
+        let name = component::<T>(&mut self.components);
+        self.body = Some((name, true));
+        self
+    }
+
+    // El handler acepta la petición sin cuerpo
+    fn optional_body<T: ToSchema>(mut self) -> Self {
+        let name = component::<T>(&mut self.components);
+        self.body = Some((name, false));
+        self
+    }
+
+    fn raw_body(mut self, content_type: &'static str) -> Self {
+        self.raw_body = Some(content_type);
+        self
+    }
+
+    // Se puede llamar varias veces: los filtros propios de un listado y su paginación (Page<L>)
+    fn query<T: IntoParams>(mut self) -> Self {
+        self.query.extend(T::into_params(|| Some(ParameterIn::Query)));
+        self
+    }
+
+    fn created(mut self) -> Self {
+        self.status = "201";
+        self
+    }
+
+    fn returns<T: ToSchema>(mut self) -> Self {
+        let name = component::<T>(&mut self.components);
+        self.success = Some(Success::Typed(name));
+        self
+    }
+
+    fn returns_external(mut self, url: &'static str) -> Self {
+        self.success = Some(Success::External(url));
+        self
+    }
+
+    // Listado paginado: el sobre PageResponse con elementos T, registrado como <T>Page
+    fn page<T: ToSchema + serde::Serialize>(mut self) -> Self {
+        let item = component::<T>(&mut self.components);
+        let name = format!("{}Page", item);
+        self.components.push((name.clone(), PageResponse::<T>::schema()));
+        self.success = Some(Success::Typed(name));
+        self
+    }
+
+    fn produces(mut self, content_type: &'static str) -> Self {
+        self.success = Some(Success::File(content_type));
+        self
+    }
+}
+
+fn http_method(method: &str) -> HttpMethod {
+    match method {
+        "get" => HttpMethod::Get,
+        "post" => HttpMethod::Post,
+        "put" => HttpMethod::Put,
+        "delete" => HttpMethod::Delete,
+        other => panic!("Método sin soporte en la especificación: {}", other),
+    }
+}
+
+fn schema_ref(name: &str) -> RefOr<Schema> {
+    Ref::from_schema_name(name).into()
+}
+
+fn binary() -> ObjectBuilder {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .format(Some(SchemaFormat::KnownFormat(KnownFormat::Binary)))
+}
+
+// Parámetros de ruta ({id}, {item_id}, {token}...): los identificadores son enteros
+fn path_parameters(path: &str) -> Vec<utoipa::openapi::path::Parameter> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|segment| segment.split('}').next())
+        .map(|name| {
+            let schema = if name == "id" || name.ends_with("_id") {
+                ObjectBuilder::new()
+                    .schema_type(Type::Integer)
+                    .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int32)))
+            } else {
+                ObjectBuilder::new().schema_type(Type::String)
+            };
+            ParameterBuilder::new()
+                .name(name)
+                .parameter_in(ParameterIn::Path)
+                .required(Required::True)
+                .schema(Some(schema))
+                .build()
+        })
+        .collect()
+}
+
+fn operations() -> Vec<Op> {
+    vec![
+        op("get", "/openapi.json", "docs", "Esta especificación OpenAPI").public().returns_external(OPENAPI_SCHEMA_URL),
+
+        op("post", "/auth/register", "auth", "Registrar un usuario").public().body::<RegisterRequest>().created().returns::<AuthResponse>(),
+        op("post", "/auth/login", "auth", "Iniciar sesión").public().body::<LoginRequest>().returns::<AuthResponse>(),
+        op("get", "/auth/me", "auth", "Usuario autenticado").returns::<MeResponse>(),
+        op("put", "/auth/me/locale", "auth", "Cambiar el idioma preferido").body::<UpdateLocaleRequest>().returns::<AuthResponse>(),
+
+        op("post", "/hotels", "hotels", "Registrar un hotel").body::<CreateHotelRequest>().created().returns::<HotelCreatedResponse>(),
+        op("get", "/hotels/my", "hotels", "Hoteles del propietario").query::<Page<MyHotels>>().page::<OwnerHotel>(),
+        op("get", "/hotels/public", "hotels", "Hoteles aprobados").public().query::<Page<PublicHotels>>().page::<PublicHotel>(),
+        op("put", "/hotels/{id}", "hotels", "Editar un hotel").body::<CreateHotelRequest>().returns::<HotelStatusResponse>(),
+        op("post", "/hotels/{id}/resubmit", "hotels", "Reenviar un hotel rechazado").optional_body::<ResubmitHotelRequest>().returns::<HotelStatusResponse>(),
+        op("get", "/hotels/{id}/review-history", "hotels", "Historial de revisión del hotel").returns::<HotelReviewHistory>(),
+        op("get", "/hotels/{id}/translations", "translations", "Traducciones del hotel").returns::<ListingTranslations>(),
+        op("put", "/hotels/{id}/translations/{locale}", "translations", "Guardar la traducción del hotel").body::<TranslationRequest>().returns::<TranslationSavedResponse>(),
+        op("delete", "/hotels/{id}/translations/{locale}", "translations", "Eliminar la traducción del hotel").returns::<MessageResponse>(),
+        op("get", "/hotels/{id}/bookings", "bookings", "Reservas del hotel").query::<Page<HotelBookings>>().page::<HotelBookingDetail>(),
+        op("put", "/hotels/{id}/bookings/{booking_id}/confirm", "bookings", "Confirmar una reserva pendiente").returns::<BookingStateResponse>(),
+        op("put", "/hotels/{id}/bookings/{booking_id}/payment", "bookings", "Registrar el pago de una reserva").returns::<BookingStateResponse>(),
+        op("put", "/hotels/{id}/bookings/{booking_id}/complete", "bookings", "Cerrar la estancia de una reserva").returns::<BookingStateResponse>(),
+        op("get", "/hotels/{id}/reviews", "reviews", "Reseñas publicadas del hotel").public().query::<Page<PublicReviews>>().returns::<PublicReviewPage>(),
+        op("get", "/hotels/{id}/analytics", "analytics", "Analíticas del hotel").query::<HotelAnalyticsQuery>().returns::<HotelAnalytics>(),
+        op("get", "/hotels/{id}/calendar", "calendar", "Estado de sincronización del calendario").returns::<CalendarSync>(),
+        op("put", "/hotels/{id}/calendar/token", "calendar", "Regenerar el enlace iCalendar del hotel").returns::<CalendarExportResponse>(),
+        op("get", "/hotels/{id}/calendar/conflicts", "calendar", "Conflictos con calendarios externos").returns::<CalendarConflicts>(),
+        op("post", "/hotels/{id}/calendar/imports", "calendar", "Añadir un calendario externo").body::<CalendarFeedRequest>().created().returns::<CalendarFeedAddedResponse>(),
+        op("post", "/hotels/{id}/calendar/imports/upload", "calendar", "Importar un archivo iCalendar").query::<CalendarUploadQuery>().raw_body("text/calendar").returns::<CalendarFeedResponse>(),
+        op("post", "/hotels/{id}/calendar/imports/{feed_id}/sync", "calendar", "Sincronizar un calendario externo").returns::<CalendarFeedResponse>(),
+        op("delete", "/hotels/{id}/calendar/imports/{feed_id}", "calendar", "Eliminar un calendario externo").returns::<MessageResponse>(),
+        op("get", "/calendar/hotels/{token}.ics", "calendar", "Calendario iCalendar del hotel").public().produces("text/calendar"),
+        op("get", "/hotels/{id}/analytics.csv", "analytics", "Exportar las analíticas del hotel").query::<HotelAnalyticsQuery>().produces("text/csv"),
+
+        op("post", "/businesses", "businesses", "Registrar un negocio").body::<CreateBusinessRequest>().created().returns::<BusinessCreatedResponse>(),
+        op("get", "/businesses/my", "businesses", "Negocios del propietario").query::<Page<MyBusinesses>>().page::<OwnerBusiness>(),
+        op("get", "/businesses", "businesses", "Negocios aprobados").public().query::<PublicBusinessQuery>().query::<Page<PublicBusinesses>>().page::<PublicBusiness>(),
+        op("get", "/businesses/{id}", "businesses", "Detalle público del negocio").public().returns::<PublicBusinessDetail>(),
+        op("put", "/businesses/{id}", "businesses", "Editar un negocio").body::<CreateBusinessRequest>().returns::<MessageResponse>(),
+        op("delete", "/businesses/{id}", "businesses", "Eliminar un negocio").returns::<MessageResponse>(),
+        op("get", "/businesses/{id}/manage", "businesses", "Detalle del negocio para el propietario").returns::<BusinessResponse>(),
+        op("get", "/businesses/{id}/translations", "translations", "Traducciones del negocio").returns::<ListingTranslations>(),
+        op("put", "/businesses/{id}/translations/{locale}", "translations", "Guardar la traducción del negocio").body::<TranslationRequest>().returns::<TranslationSavedResponse>(),
+        op("delete", "/businesses/{id}/translations/{locale}", "translations", "Eliminar la traducción del negocio").returns::<MessageResponse>(),
+        op("get", "/businesses/{id}/reviews", "reviews", "Reseñas publicadas del negocio").public().query::<Page<PublicReviews>>().returns::<PublicReviewPage>(),
+        op("get", "/businesses/{id}/menu", "menu", "Menú público").public().returns::<PublicMenu>(),
+        op("get", "/businesses/{id}/menu/manage", "menu", "Menú completo para el propietario").returns::<OwnerMenu>(),
+        op("post", "/businesses/{id}/menu/sections", "menu", "Crear una sección del menú").body::<MenuSectionRequest>().created().returns::<MenuSectionCreatedResponse>(),
+        op("put", "/businesses/{id}/menu/sections/{section_id}", "menu", "Editar una sección del menú").body::<MenuSectionRequest>().returns::<MessageResponse>(),
+        op("delete", "/businesses/{id}/menu/sections/{section_id}", "menu", "Eliminar una sección del menú").returns::<MessageResponse>(),
+        op("post", "/businesses/{id}/menu/items", "menu", "Crear un platillo").body::<MenuItemRequest>().created().returns::<MenuItemCreatedResponse>(),
+        op("put", "/businesses/{id}/menu/items/{item_id}", "menu", "Editar un platillo").body::<MenuItemRequest>().returns::<MessageResponse>(),
+        op("delete", "/businesses/{id}/menu/items/{item_id}", "menu", "Eliminar un platillo").returns::<MessageResponse>(),
+        op("post", "/businesses/{id}/menu/items/{item_id}/sold-out", "menu", "Marcar un platillo como agotado").returns::<MessageResponse>(),
+        op("delete", "/businesses/{id}/menu/items/{item_id}/sold-out", "menu", "Quitar la marca de agotado").returns::<MessageResponse>(),
+        op("get", "/businesses/{id}/menu/items/{item_id}/translations", "translations", "Traducciones del platillo").returns::<ListingTranslations>(),
+        op("put", "/businesses/{id}/menu/items/{item_id}/translations/{locale}", "translations", "Guardar la traducción del platillo").body::<TranslationRequest>().returns::<TranslationSavedResponse>(),
+        op("delete", "/businesses/{id}/menu/items/{item_id}/translations/{locale}", "translations", "Eliminar la traducción del platillo").returns::<MessageResponse>(),
+        op("post", "/businesses/{id}/products", "products", "Crear un producto").body::<CreateProductRequest>().created().returns::<ProductCreatedResponse>(),
+        op("get", "/businesses/{id}/orders", "orders", "Pedidos del negocio").query::<Page<BusinessOrders>>().page::<BusinessOrderLine>(),
+        op("put", "/businesses/{id}/orders/{order_id}/confirm", "orders", "Confirmar un pedido").returns::<OrderStateResponse>(),
+        op("put", "/businesses/{id}/orders/{order_id}/payment", "orders", "Registrar el pago de un pedido").returns::<OrderStateResponse>(),
+        op("put", "/businesses/{id}/orders/{order_id}/complete", "orders", "Marcar un pedido como entregado").returns::<OrderStateResponse>(),
+        op("get", "/businesses/{id}/transport/vehicles", "transport", "Vehículos del negocio").returns::<VehicleList>(),
+        op("post", "/businesses/{id}/transport/vehicles", "transport", "Registrar un vehículo").body::<VehicleRequest>().created().returns::<CreatedResponse>(),
+        op("put", "/businesses/{id}/transport/vehicles/{vehicle_id}", "transport", "Editar un vehículo").body::<VehicleRequest>().returns::<MessageResponse>(),
+        op("get", "/businesses/{id}/transport/routes", "transport", "Rutas del negocio").returns::<RouteList>(),
+        op("post", "/businesses/{id}/transport/routes", "transport", "Crear una ruta").body::<RouteRequest>().created().returns::<RouteCreatedResponse>(),
+        op("put", "/businesses/{id}/transport/routes/{route_id}", "transport", "Editar una ruta").body::<RouteRequest>().returns::<RouteUpdatedResponse>(),
+        op("post", "/businesses/{id}/transport/trips", "transport", "Programar un viaje").body::<CreateTripRequest>().created().returns::<TripCreatedResponse>(),
+        op("put", "/businesses/{id}/transport/trips/{trip_id}/cancel", "transport", "Cancelar un viaje").returns::<TripCancelledResponse>(),
+        op("get", "/businesses/{id}/transport/bookings", "transport", "Reservas de transporte del negocio").query::<Page<BusinessTransportBookings>>().page::<BusinessTransportBooking>(),
+        op("put", "/businesses/{id}/transport/bookings/{booking_id}/confirm", "transport", "Confirmar una reserva de transporte pendiente").returns::<TransportBookingStateResponse>(),
+
+        op("get", "/products", "products", "Productos publicados").public().query::<PublicProductQuery>().query::<Page<PublicProducts>>().page::<PublicProduct>(),
+        op("get", "/products/{id}", "products", "Detalle del producto").public().returns::<ProductDetail>(),
+        op("put", "/products/{id}", "products", "Editar un producto").body::<UpdateProductRequest>().returns::<MessageResponse>(),
+        op("delete", "/products/{id}", "products", "Eliminar un producto").returns::<MessageResponse>(),
+        op("post", "/products/{id}/variants", "products", "Añadir una variante").body::<ProductVariantInput>().created().returns::<VariantCreatedResponse>(),
+        op("put", "/products/{id}/variants/{variant_id}", "products", "Editar una variante").body::<ProductVariantInput>().returns::<MessageResponse>(),
+
+        op("get", "/cart", "orders", "Carrito actual").returns::<CartResponse>(),
+        op("delete", "/cart", "orders", "Vaciar el carrito").returns::<MessageResponse>(),
+        op("post", "/cart/items", "orders", "Añadir al carrito").body::<AddCartItemRequest>().returns::<CartItemAddedResponse>(),
+        op("put", "/cart/items/{variant_id}", "orders", "Cambiar la cantidad en el carrito").body::<UpdateCartItemRequest>().returns::<MessageResponse>(),
+        op("delete", "/cart/items/{variant_id}", "orders", "Quitar del carrito").returns::<MessageResponse>(),
+        op("post", "/cart/checkout", "orders", "Confirmar el pedido").body::<CheckoutRequest>().created().returns::<OrderCreatedResponse>(),
+        op("get", "/orders/my", "orders", "Pedidos del cliente").query::<Page<MyOrders>>().page::<CustomerOrder>(),
+        op("get", "/orders/{id}", "orders", "Detalle del pedido").returns::<OrderDetail>(),
+        op("put", "/orders/{id}/cancel", "orders", "Cancelar un pedido").body::<CancelOrderRequest>().returns::<MessageResponse>(),
+
+        op("get", "/transport/routes", "transport", "Rutas publicadas").public().query::<RouteSearchQuery>().query::<Page<PublicRoutes>>().page::<PublicRoute>(),
+        op("get", "/transport/routes/{id}/trips", "transport", "Viajes de la ruta").public().query::<TripSearchQuery>().query::<Page<RouteTrips>>().page::<Trip>(),
+        op("post", "/transport/bookings", "transport", "Reservar un viaje").body::<CreateTransportBookingRequest>().created().returns::<TransportBookingCreatedResponse>(),
+        op("get", "/transport/bookings/my", "transport", "Reservas de transporte del cliente").query::<Page<MyTransportBookings>>().page::<CustomerTransportBooking>(),
+        op("put", "/transport/bookings/{id}/cancel", "transport", "Cancelar una reserva de transporte").returns::<MessageResponse>(),
+
+        op("post", "/bookings", "bookings", "Reservar un hotel").body::<CreateBookingRequest>().created().returns::<BookingCreatedResponse>(),
+        op("get", "/bookings/my", "bookings", "Reservas del cliente").query::<Page<MyBookings>>().page::<CustomerBooking>(),
+        op("put", "/bookings/{id}/cancel", "bookings", "Cancelar una reserva").body::<UpdateBookingStatusRequest>().returns::<MessageResponse>(),
+        op("get", "/bookings/{id}/voucher.pdf", "documents", "Comprobante de la reserva").produces("application/pdf"),
+        op("get", "/bookings/{id}/invoice.pdf", "documents", "Factura de la reserva").produces("application/pdf"),
+        op("get", "/bookings/{id}/check-in-token", "check-in", "Código de check-in de la reserva").returns::<CheckInTokenResponse>(),
+        op("get", "/transport/bookings/{id}/check-in-token", "check-in", "Código de abordaje del viaje").returns::<CheckInTokenResponse>(),
+        op("post", "/check-in", "check-in", "Registrar un check-in").body::<CheckInRequest>().returns::<CheckInResponse>(),
+
+        op("get", "/itinerary", "itinerary", "Itinerario del cliente").query::<ItineraryQuery>().returns::<Itinerary>(),
+        op("get", "/itinerary.ics", "itinerary", "Exportar el itinerario").query::<ItineraryQuery>().produces("text/calendar"),
+
+        op("post", "/reviews", "reviews", "Publicar una reseña").body::<CreateReviewRequest>().created().returns::<ReviewCreatedResponse>(),
+        op("get", "/reviews/my", "reviews", "Reseñas del cliente").query::<Page<MyReviews>>().page::<MyReview>(),
+        op("put", "/reviews/{id}/reply", "reviews", "Responder a una reseña").body::<ReviewReplyRequest>().returns::<MessageResponse>(),
+
+        op("post", "/favorites", "favorites", "Guardar en favoritos").body::<AddFavoriteRequest>().created().returns::<FavoriteSavedResponse>(),
+        op("get", "/favorites/lists", "favorites", "Listas de favoritos").returns::<FavoriteLists>(),
+        op("post", "/favorites/lists", "favorites", "Crear una lista de favoritos").body::<FavoriteListRequest>().created().returns::<FavoriteListResponse>(),
+        op("get", "/favorites/lists/{id}", "favorites", "Detalle de la lista").returns::<FavoriteListDetailResponse>(),
+        op("put", "/favorites/lists/{id}", "favorites", "Renombrar la lista").body::<FavoriteListRequest>().returns::<FavoriteListResponse>(),
+        op("delete", "/favorites/lists/{id}", "favorites", "Eliminar la lista").returns::<MessageResponse>(),
+        op("post", "/favorites/lists/{id}/items", "favorites", "Añadir a la lista").body::<AddFavoriteRequest>().created().returns::<FavoriteSavedResponse>(),
+        op("delete", "/favorites/lists/{id}/items/{item_id}", "favorites", "Quitar de la lista").returns::<MessageResponse>(),
+        op("put", "/favorites/lists/{id}/share", "favorites", "Compartir la lista").returns::<FavoriteListResponse>(),
+        op("delete", "/favorites/lists/{id}/share", "favorites", "Dejar de compartir la lista").returns::<MessageResponse>(),
+        op("get", "/favorites/lists/{id}/availability", "favorites", "Disponibilidad de los hoteles de la lista").query::<FavoriteAvailabilityQuery>().returns::<FavoriteListAvailability>(),
+        op("get", "/favorites/shared/{token}", "favorites", "Lista compartida").public().returns::<SharedFavoriteListResponse>(),
+
+        op("get", "/admin/metrics", "admin", "Métricas del panel").query::<DashboardQuery>().returns::<DashboardStats>(),
+        op("get", "/admin/hotels", "admin", "Todos los hoteles").query::<Page<AllHotels>>().page::<AdminHotel>(),
+        op("get", "/admin/hotels/pending", "admin", "Hoteles pendientes").query::<Page<PendingHotels>>().page::<PendingHotel>(),
+        op("put", "/admin/hotels/{id}/approve", "admin", "Aprobar un hotel").optional_body::<ApproveNotes>().returns::<MessageResponse>(),
+        op("put", "/admin/hotels/{id}/reject", "admin", "Rechazar un hotel").body::<RejectReason>().returns::<MessageResponse>(),
+        op("get", "/admin/hotels/{id}/history", "admin", "Historial de revisión del hotel").returns::<HotelReviewHistory>(),
+        op("get", "/admin/businesses/pending", "admin", "Negocios pendientes").query::<Page<PendingBusinesses>>().page::<PendingBusiness>(),
+        op("put", "/admin/businesses/{id}/approve", "admin", "Aprobar un negocio").optional_body::<ApproveNotes>().returns::<MessageResponse>(),
+        op("put", "/admin/businesses/{id}/reject", "admin", "Rechazar un negocio").optional_body::<RejectReason>().returns::<MessageResponse>(),
+        op("put", "/admin/reviews/{id}/approve", "admin", "Aprobar una reseña").optional_body::<ApproveNotes>().returns::<MessageResponse>(),
+        op("put", "/admin/reviews/{id}/reject", "admin", "Rechazar una reseña").body::<RejectReason>().returns::<MessageResponse>(),
+        op("get", "/admin/moderation", "admin", "Cola de moderación").query::<ModerationQueueQuery>().query::<Page<ModerationQueue>>().page::<ModerationItem>(),
+        op("post", "/admin/moderation/bulk", "admin", "Moderación en lote").body::<BulkModerationRequest>().returns::<BulkModerationResponse>(),
+        op("get", "/admin/suspensions", "admin", "Suspensiones activas").returns::<SuspensionList>(),
+        op("put", "/admin/hotels/{id}/suspend", "admin", "Suspender un hotel").body::<SuspendListingRequest>().returns::<SuspensionResponse>(),
+        op("put", "/admin/hotels/{id}/reinstate", "admin", "Reactivar un hotel").optional_body::<ReinstateListingRequest>().returns::<MessageResponse>(),
+        op("put", "/admin/businesses/{id}/suspend", "admin", "Suspender un negocio").body::<SuspendListingRequest>().returns::<SuspensionResponse>(),
+        op("put", "/admin/businesses/{id}/reinstate", "admin", "Reactivar un negocio").optional_body::<ReinstateListingRequest>().returns::<MessageResponse>(),
+        op("get", "/admin/translations/missing", "admin", "Publicaciones sin traducir").query::<MissingTranslationsQuery>().returns::<MissingTranslations>(),
+
+        op("get", "/notifications", "notifications", "Notificaciones del usuario").returns::<NotificationList>(),
+        op("put", "/notifications/{id}/read", "notifications", "Marcar una notificación como leída").returns::<MessageResponse>(),
+    ]
+}
+
+fn build_spec() -> OpenApi {
+    let mut paths = Paths::new();
+    let mut schemas: Components = Vec::new();
+    component::<ErrorBody>(&mut schemas);
+
+    for op in operations() {
+        let mut operation = OperationBuilder::new()
+            .tag(op.tag)
+            .summary(Some(op.summary))
+            .parameters(Some(path_parameters(op.path).into_iter().chain(op.query)));
+
+        if let Some((name, required)) = op.body {
+            operation = operation.request_body(Some(
+                RequestBodyBuilder::new()
+                    .required(Some(if required { Required::True } else { Required::False }))
+                    .content("application/json", Content::new(Some(schema_ref(&name))))
+                    .build(),
+            ));
+        }
+        if let Some(content_type) = op.raw_body {
+            operation = operation.request_body(Some(
+                RequestBodyBuilder::new()
+                    .required(Some(Required::True))
+                    .content(content_type, Content::new(Some(binary())))
+                    .build(),
+            ));
+        }
+
+        let success = match op.success {
+            Some(Success::Typed(name)) => ResponseBuilder::new()
+                .description("Correcto")
+                .content("application/json", Content::new(Some(schema_ref(&name)))),
+            Some(Success::External(url)) => ResponseBuilder::new()
+                .description("Correcto")
+                .content("application/json", Content::new(Some(Ref::new(url)))),
+            Some(Success::File(content_type)) => ResponseBuilder::new()
+                .description("Archivo generado")
+                .content(content_type, Content::new(Some(binary()))),
+            None => panic!("{} {} no declara su respuesta", op.method, op.path),
+        };
+        operation = operation.response(op.status, success).response(
+            "default",
+            ResponseBuilder::new()
+                .description("Error con el formato común")
+                .content("application/json", Content::new(Some(schema_ref("ErrorBody")))),
+        );
+
+        if !op.public {
+            operation = operation.security(SecurityRequirement::new(BEARER_AUTH, Vec::<String>::new()));
+        }
+
+        paths.add_path_operation(format!("{}{}", API_V1, op.path), vec![http_method(op.method)], operation.build());
+        schemas.extend(op.components);
+    }
+
+    let components = ComponentsBuilder::new()
+        .schemas_from_iter(schemas)
+        .security_scheme(
+            BEARER_AUTH,
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        )
+        .build();
+
+    OpenApiBuilder::new()
+        .info(
+            InfoBuilder::new()
+                .title("Maya Digital API")
+                .version(env!("CARGO_PKG_VERSION"))
+                .description(Some("Todas las respuestas de error usan el esquema ErrorBody."))
+                .build(),
+        )
+        .paths(paths)
+        .components(Some(components))
+        .build()
+}
+
+// Se arma una sola vez por proceso
+pub fn spec() -> &'static OpenApi {
+    static SPEC: OnceLock<OpenApi> = OnceLock::new();
+    SPEC.get_or_init(build_spec)
+}
+
+pub async fn get_openapi_spec() -> HttpResponse {
+    HttpResponse::Ok().json(spec())
+}
@@ -1,51 +1,287 @@
 // backend/src/utils/errors.rs
-use actix_web::{HttpResponse, ResponseError};
-use serde_json::json;
-use std::fmt;
+// Todas las respuestas de error comparten el mismo formato (ErrorBody):
+// {"code": "not_found", "message": "...", "details": [...], "request_id": "...", "error": "..."}
+// `code` es estable y pensado para los clientes; `error` repite el mensaje para los clientes que
+// todavía lo leen. `details` solo aparece en errores de validación.
+// Los errores `Localized` y los genéricos se traducen al idioma de la petición con los catálogos
+// de utils::i18n; su `code` es la clave del catálogo. Los demás conservan su texto en español.
+use actix_web::{
+    error::{JsonPayloadError, PathError, QueryPayloadError},
+    http::StatusCode,
+    HttpRequest, HttpResponse, ResponseError,
+};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt};
+use utoipa::ToSchema;
+use validator::{ValidationError, ValidationErrors, ValidationErrorsKind};
+
+use crate::middleware::{locale::current_locale, request_id::current_request_id};
+use crate::utils::i18n::{self, Locale, DEFAULT_LOCALE};
+
+// Mensaje del catálogo con sus parámetros ({nombre} en la plantilla)
+#[derive(Debug, Clone)]
+pub struct Message {
+    pub key: &'static str,
+    pub args: Vec<(&'static str, String)>,
+}
+
+impl Message {
+    pub fn render(&self, locale: Locale) -> String {
+        let args: Vec<(&str, String)> = self.args.iter().map(|(k, v)| (*k, v.clone())).collect();
+        i18n::translate(locale, self.key, &args)
+    }
+}
+
+// Cuerpo de todas las respuestas de error; también es el esquema `ErrorBody` de la especificación OpenAPI
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ErrorBody {
+    pub code: String,
+    pub message: String,
+    // Igual que `message`, para los clientes que todavía lo leen
+    pub error: String,
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Vec<FieldError>>,
+}
+
+// Error de validación de un campo; `params` son los límites de la regla (min, max...)
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub code: String,
+    pub message: String,
+    #[schema(value_type = Object)]
+    pub params: BTreeMap<String, serde_json::Value>,
+}
 
 #[derive(Debug)]
 pub enum AppError {
     BadRequest(String),
+    // Errores de `validator`, reportados campo por campo en `details`
+    Validation(ValidationErrors),
     Unauthorized(String),
     Forbidden(String),
     NotFound(String),  // ✅ AHORA requiere String
     Conflict(String),
+    UnprocessableEntity(String),
     InternalServerError(String),
     DatabaseError(String),
+    // Error con mensaje traducible; el estado HTTP lo decide quien lo crea
+    Localized(StatusCode, Message),
+}
+
+impl AppError {
+    pub fn localized(status: StatusCode, key: &'static str) -> Self {
+        AppError::Localized(status, Message { key, args: Vec::new() })
+    }
+
+    // Añade un parámetro a un error traducible; en los demás no tiene efecto
+    pub fn arg(mut self, name: &'static str, value: impl ToString) -> Self {
+        if let AppError::Localized(_, message) = &mut self {
+            message.args.push((name, value.to_string()));
+        }
+        self
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Localized(_, message) => message.key,
+            AppError::BadRequest(_) => "bad_request",
+            AppError::Validation(_) => "validation_failed",
+            AppError::Unauthorized(_) => "unauthorized",
+            AppError::Forbidden(_) => "forbidden",
+            AppError::NotFound(_) => "not_found",
+            AppError::Conflict(_) => "conflict",
+            AppError::UnprocessableEntity(_) => "unprocessable_entity",
+            AppError::InternalServerError(_) | AppError::DatabaseError(_) => "internal_error",
+        }
+    }
+
+    // Mensaje que ve el cliente: los errores internos nunca exponen el detalle
+    pub fn public_message(&self) -> String {
+        let locale = current_locale();
+        match self {
+            AppError::Localized(_, message) => message.render(locale),
+            AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::Forbidden(msg)
+            | AppError::NotFound(msg)
+            | AppError::Conflict(msg)
+            | AppError::UnprocessableEntity(msg) => msg.clone(),
+            AppError::Validation(_) => i18n::translate(locale, "validation_failed", &[]),
+            AppError::InternalServerError(_) | AppError::DatabaseError(_) => {
+                i18n::translate(locale, "internal_error", &[])
+            }
+        }
+    }
+
+    pub fn details(&self) -> Option<Vec<FieldError>> {
+        match self {
+            AppError::Validation(errors) => {
+                let mut details = Vec::new();
+                collect_validation_errors(errors, "", current_locale(), &mut details);
+                Some(details)
+            }
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for AppError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             AppError::BadRequest(msg) => write!(f, "Bad Request: {}", msg),
+            AppError::Validation(errors) => write!(f, "Validation: {}", errors),
             AppError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             AppError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
             AppError::NotFound(msg) => write!(f, "Not Found: {}", msg),
             AppError::Conflict(msg) => write!(f, "Conflict: {}", msg),
+            AppError::UnprocessableEntity(msg) => write!(f, "Unprocessable Entity: {}", msg),
             AppError::InternalServerError(msg) => write!(f, "Internal Server Error: {}", msg),
             AppError::DatabaseError(msg) => write!(f, "Database Error: {}", msg),
+            AppError::Localized(_, message) => write!(f, "{}: {}", message.key, message.render(DEFAULT_LOCALE)),
         }
     }
 }
 
 impl ResponseError for AppError {
-    fn error_response(&self) -> HttpResponse {
+    fn status_code(&self) -> StatusCode {
         match self {
-            AppError::BadRequest(msg) => HttpResponse::BadRequest().json(json!({"error": msg})),
-            AppError::Unauthorized(msg) => HttpResponse::Unauthorized().json(json!({"error": msg})),
-            AppError::Forbidden(msg) => HttpResponse::Forbidden().json(json!({"error": msg})),
-            AppError::NotFound(msg) => HttpResponse::NotFound().json(json!({"error": msg})),
-            AppError::Conflict(msg) => HttpResponse::Conflict().json(json!({"error": msg})),
-            AppError::InternalServerError(msg) => HttpResponse::InternalServerError().json(json!({"error": msg})),
-            AppError::DatabaseError(msg) => HttpResponse::InternalServerError().json(json!({"error": msg})),
+            AppError::BadRequest(_) | AppError::Validation(_) => StatusCode::BAD_REQUEST,
+            AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::Conflict(_) => StatusCode::CONFLICT,
+            AppError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::InternalServerError(_) | AppError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            AppError::Localized(status, _) => *status,
         }
     }
+
+    fn error_response(&self) -> HttpResponse {
+        let request_id = current_request_id();
+
+        // El detalle de los errores internos solo queda en el log, asociado al request id
+        if self.status_code().is_server_error() {
+            log::error!("[{}] {}", request_id.as_deref().unwrap_or("-"), self);
+        }
+
+        let message = self.public_message();
+        HttpResponse::build(self.status_code()).json(ErrorBody {
+            code: self.code().to_string(),
+            error: message.clone(),
+            message,
+            request_id,
+            details: self.details(),
+        })
+    }
+}
+
+// Mensaje de un error de validación. Los mensajes propios de los modelos están en español; en otros
+// idiomas se usa el catálogo según el tipo de validación y sus límites.
+fn validation_message(error: &ValidationError, locale: Locale) -> String {
+    if locale == DEFAULT_LOCALE {
+        if let Some(message) = &error.message {
+            return message.to_string();
+        }
+    }
+
+    let param = |name: &str| error.params.get(name).map(|v| v.to_string().trim_matches('"').to_string());
+    let (min, max, equal) = (param("min"), param("max"), param("equal"));
+    let bounded = |prefix: &str| match (&min, &max) {
+        (Some(_), Some(_)) => format!("validation.{}_between", prefix),
+        (Some(_), None) => format!("validation.{}_min", prefix),
+        (None, Some(_)) => format!("validation.{}_max", prefix),
+        (None, None) => "validation.invalid".to_string(),
+    };
+
+    let key = match error.code.as_ref() {
+        "length" if equal.is_some() => "validation.length_equal".to_string(),
+        "length" => bounded("length"),
+        "range" => bounded("range"),
+        code if i18n::has_key(locale, &format!("validation.{}", code)) => format!("validation.{}", code),
+        _ => "validation.invalid".to_string(),
+    };
+
+    let args: Vec<(&str, String)> = [("min", min), ("max", max), ("equal", equal)]
+        .into_iter()
+        .filter_map(|(name, value)| value.map(|v| (name, v)))
+        .collect();
+    i18n::translate(locale, &key, &args)
+}
+
+// Aplana los errores anidados (structs y listas) como rutas: "items[0].quantity"
+fn collect_validation_errors(
+    errors: &ValidationErrors,
+    prefix: &str,
+    locale: Locale,
+    out: &mut Vec<FieldError>,
+) {
+    let mut fields: Vec<_> = errors.errors().iter().collect();
+    fields.sort_by_key(|(field, _)| **field);
+
+    for (field, kind) in fields {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+        match kind {
+            ValidationErrorsKind::Field(list) => {
+                for error in list {
+                    // El valor recibido no se devuelve: puede ser una contraseña
+                    let params = error
+                        .params
+                        .iter()
+                        .filter(|(name, _)| *name != "value")
+                        .map(|(name, value)| (name.to_string(), value.clone()))
+                        .collect();
+                    out.push(FieldError {
+                        field: path.clone(),
+                        code: error.code.to_string(),
+                        message: validation_message(error, locale),
+                        params,
+                    });
+                }
+            }
+            ValidationErrorsKind::Struct(inner) => collect_validation_errors(inner, &path, locale, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, inner) in items {
+                    collect_validation_errors(inner, &format!("{}[{}]", path, index), locale, out);
+                }
+            }
+        }
+    }
+}
+
+// Manejadores para que los errores de los extractores usen el mismo formato
+pub fn json_error_handler(err: JsonPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::localized(StatusCode::BAD_REQUEST, "invalid_json").arg("detail", err).into()
+}
+
+pub fn query_error_handler(err: QueryPayloadError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::localized(StatusCode::BAD_REQUEST, "invalid_query").arg("detail", err).into()
+}
+
+pub fn path_error_handler(err: PathError, _req: &HttpRequest) -> actix_web::Error {
+    AppError::localized(StatusCode::BAD_REQUEST, "invalid_path").arg("detail", err).into()
 }
 
 // ✅ IMPLEMENTAR From traits para las dependencias
+impl From<validator::ValidationErrors> for AppError {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        AppError::Validation(errors)
+    }
+}
+
 impl From<sqlx::Error> for AppError {
     fn from(err: sqlx::Error) -> Self {
-        AppError::DatabaseError(format!("Database error: {}", err))
+        match &err {
+            sqlx::Error::RowNotFound => AppError::localized(StatusCode::NOT_FOUND, "not_found"),
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                AppError::localized(StatusCode::CONFLICT, "unique_violation")
+            }
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                AppError::localized(StatusCode::UNPROCESSABLE_ENTITY, "foreign_key_violation")
+            }
+            _ => AppError::DatabaseError(format!("Database error: {}", err)),
+        }
     }
 }
 
@@ -59,4 +295,4 @@ impl From<jsonwebtoken::errors::Error> for AppError {
     fn from(_: jsonwebtoken::errors::Error) -> Self {
         AppError::InternalServerError("Error procesando token".to_string())
     }
-}
\ No newline at end of file
+}
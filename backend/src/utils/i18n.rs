@@ -0,0 +1,174 @@
+// Catálogos de mensajes por idioma (backend/locales/*.toml), embebidos en el binario.
+// Las claves coinciden con los `code` de la API; las tablas TOML se aplanan con puntos
+// ("booking.created"). Si falta una traducción se usa el español y, en último caso, la clave.
+
+use std::{collections::HashMap, sync::OnceLock};
+use serde::{Deserialize, Serialize};
+
+use crate::middleware::locale::current_locale;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    Es,
+    En,
+}
+
+pub const DEFAULT_LOCALE: Locale = Locale::Es;
+
+// Idiomas sin catálogo propio que se atienden con uno existente. Quien habla maya yucateco en la
+// península lee el español de la plataforma.
+const LOCALE_ALIASES: [(&str, Locale); 1] = [("yua", Locale::Es)];
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::Es, Locale::En];
+
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::Es => "es",
+            Locale::En => "en",
+        }
+    }
+
+    // Acepta etiquetas BCP 47 ("es-MX", "en_US"); solo importa el idioma principal
+    pub fn parse(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "es" => Some(Locale::Es),
+            "en" => Some(Locale::En),
+            other => LOCALE_ALIASES.iter().find(|(alias, _)| *alias == other).map(|(_, l)| *l),
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Locale::Es => include_str!("../../locales/es.toml"),
+            Locale::En => include_str!("../../locales/en.toml"),
+        }
+    }
+}
+
+// Idioma preferido según Accept-Language ("en-US,en;q=0.9,es;q=0.8"); None si ninguno está soportado
+pub fn from_accept_language(header: &str) -> Option<Locale> {
+    let mut candidates: Vec<(f32, usize, Locale)> = header
+        .split(',')
+        .enumerate()
+        .filter_map(|(position, part)| {
+            let mut pieces = part.split(';');
+            let locale = Locale::parse(pieces.next()?)?;
+            let quality = pieces
+                .find_map(|p| p.trim().strip_prefix("q="))
+                .map(|q| q.trim().parse::<f32>().unwrap_or(0.0))
+                .unwrap_or(1.0);
+            (quality > 0.0).then_some((quality, position, locale))
+        })
+        .collect();
+
+    // Mayor calidad primero; a igual calidad, el orden del encabezado
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+    candidates.first().map(|(_, _, locale)| *locale)
+}
+
+// La preferencia guardada del usuario manda sobre Accept-Language
+pub fn negotiate(preference: Option<&str>, accept_language: Option<&str>) -> Locale {
+    preference
+        .and_then(Locale::parse)
+        .or_else(|| accept_language.and_then(from_accept_language))
+        .unwrap_or(DEFAULT_LOCALE)
+}
+
+// Orden en que se buscan los contenidos traducidos: el idioma pedido, el español y después el resto
+pub fn fallback_chain(requested: Locale) -> Vec<Locale> {
+    let mut chain = vec![requested];
+    for locale in std::iter::once(DEFAULT_LOCALE).chain(Locale::ALL) {
+        if !chain.contains(&locale) {
+            chain.push(locale);
+        }
+    }
+    chain
+}
+
+// Mejor versión de un campo traducible. `source` es el idioma del texto original (`original`);
+// `translated` da el campo en otro idioma si el propietario lo tradujo. Si ningún idioma de la
+// cadena lo tiene, se devuelve el original.
+pub fn best_match<T: Clone>(
+    requested: Locale,
+    source: Locale,
+    original: Option<&T>,
+    translated: impl Fn(Locale) -> Option<T>,
+) -> (Option<T>, Locale) {
+    for locale in fallback_chain(requested) {
+        let value = if locale == source { original.cloned() } else { translated(locale) };
+        if value.is_some() {
+            return (value, locale);
+        }
+    }
+    (original.cloned(), source)
+}
+
+type Catalog = HashMap<String, String>;
+
+fn flatten(prefix: &str, table: toml::Table, out: &mut Catalog) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+        match value {
+            toml::Value::Table(inner) => flatten(&key, inner, out),
+            toml::Value::String(text) => {
+                out.insert(key, text);
+            }
+            other => {
+                out.insert(key, other.to_string());
+            }
+        }
+    }
+}
+
+fn catalogs() -> &'static HashMap<Locale, Catalog> {
+    static CATALOGS: OnceLock<HashMap<Locale, Catalog>> = OnceLock::new();
+    CATALOGS.get_or_init(|| {
+        Locale::ALL
+            .iter()
+            .map(|locale| {
+                let table: toml::Table = locale
+                    .source()
+                    .parse()
+                    .unwrap_or_else(|e| panic!("catálogo {} inválido: {}", locale.code(), e));
+                let mut catalog = Catalog::new();
+                flatten("", table, &mut catalog);
+                (*locale, catalog)
+            })
+            .collect()
+    })
+}
+
+pub fn catalog_keys(locale: Locale) -> Vec<&'static str> {
+    let mut keys: Vec<&str> = catalogs()[&locale].keys().map(String::as_str).collect();
+    keys.sort_unstable();
+    keys
+}
+
+pub fn has_key(locale: Locale, key: &str) -> bool {
+    catalogs()[&locale].contains_key(key)
+}
+
+pub fn translate(locale: Locale, key: &str, args: &[(&str, String)]) -> String {
+    let catalogs = catalogs();
+    let template = catalogs[&locale]
+        .get(key)
+        .or_else(|| catalogs[&DEFAULT_LOCALE].get(key))
+        .map(String::as_str)
+        .unwrap_or(key);
+
+    args.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+// Traduce al idioma de la petición en curso
+pub fn t(key: &str) -> String {
+    translate(current_locale(), key, &[])
+}
+
+pub fn t_args(key: &str, args: &[(&str, String)]) -> String {
+    translate(current_locale(), key, args)
+}
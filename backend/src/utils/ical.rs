@@ -0,0 +1,285 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, Utc};
+
+// Generación y lectura mínima de iCalendar (RFC 5545): eventos de día completo o con hora UTC
+
+pub enum IcsTime {
+    Date(NaiveDate),
+    Utc(DateTime<Utc>),
+}
+
+impl IcsTime {
+    fn property(&self, name: &str) -> String {
+        match self {
+            IcsTime::Date(date) => format!("{};VALUE=DATE:{}", name, date.format("%Y%m%d")),
+            IcsTime::Utc(at) => format!("{}:{}", name, at.format("%Y%m%dT%H%M%SZ")),
+        }
+    }
+}
+
+pub struct IcsEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: Option<String>,
+    pub location: Option<String>,
+    pub start: IcsTime,
+    pub end: IcsTime,
+    pub confirmed: bool,
+}
+
+pub struct Calendar {
+    lines: Vec<String>,
+    stamp: String,
+}
+
+impl Calendar {
+    pub fn new(name: &str) -> Self {
+        Calendar {
+            lines: vec![
+                "BEGIN:VCALENDAR".to_string(),
+                "VERSION:2.0".to_string(),
+                "PRODID:-//Maya Digital//Reservas//ES".to_string(),
+                "CALSCALE:GREGORIAN".to_string(),
+                "METHOD:PUBLISH".to_string(),
+                format!("X-WR-CALNAME:{}", escape_text(name)),
+            ],
+            stamp: Utc::now().format("%Y%m%dT%H%M%SZ").to_string(),
+        }
+    }
+
+    pub fn add_event(&mut self, event: &IcsEvent) {
+        self.lines.push("BEGIN:VEVENT".to_string());
+        self.lines.push(format!("UID:{}", escape_text(&event.uid)));
+        self.lines.push(format!("DTSTAMP:{}", self.stamp));
+        self.lines.push(event.start.property("DTSTART"));
+        self.lines.push(event.end.property("DTEND"));
+        self.lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(location) = &event.location {
+            self.lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.description {
+            self.lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        self.lines.push(format!("STATUS:{}", if event.confirmed { "CONFIRMED" } else { "TENTATIVE" }));
+        self.lines.push("END:VEVENT".to_string());
+    }
+
+    pub fn finish(mut self) -> String {
+        self.lines.push("END:VCALENDAR".to_string());
+        self.lines.iter().map(|l| fold_line(l)).collect::<Vec<_>>().join("")
+    }
+}
+
+pub fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+// Las líneas de más de 75 octetos se parten y continúan con un espacio
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += len;
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+// Evento leído de un calendario externo, reducido a las noches [start, end) que ocupa
+pub struct ParsedEvent {
+    pub uid: String,
+    pub summary: Option<String>,
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+// Día de un DTSTART/DTEND. DATE (20250101) ya es un día. DATE-TIME en UTC (20250101T030000Z) se
+// pasa primero a la zona del negocio (`offset`), porque en UTC puede caer en el día siguiente. La
+// hora flotante o con TZID (20250101T150000) ya está en la hora local del calendario de origen y se
+// toma su día tal cual.
+fn parse_date(value: &str, offset: FixedOffset) -> Option<NaiveDate> {
+    let value = value.trim();
+    if value.len() == 8 {
+        return NaiveDate::parse_from_str(value, "%Y%m%d").ok();
+    }
+    match value.strip_suffix(['Z', 'z']) {
+        Some(utc) => NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S")
+            .ok()
+            .map(|at| at.and_utc().with_timezone(&offset).date_naive()),
+        None => NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok().map(|at| at.date()),
+    }
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => text.push('\n'),
+            Some(other) => text.push(other),
+            None => {}
+        }
+    }
+    text
+}
+
+// Lee los VEVENT de un calendario. Los eventos cancelados o sin fecha de inicio se ignoran;
+// sin DTEND (o si termina el mismo día) el evento ocupa una noche. `offset` es el desfase de la zona
+// del negocio.
+pub fn parse_events(content: &str, offset: FixedOffset) -> Result<Vec<ParsedEvent>, String> {
+    // Desdoblar las líneas de continuación (empiezan con espacio o tabulador)
+    let mut lines: Vec<String> = Vec::new();
+    for raw in content.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix(' ').or_else(|| raw.strip_prefix('\t')), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+
+    if !lines.iter().any(|l| l.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR")) {
+        return Err("El archivo no es un calendario iCalendar válido".to_string());
+    }
+
+    let mut events = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in &lines {
+        let line = line.trim_end();
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") {
+            current = Some(Vec::new());
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VEVENT") {
+            if let Some(props) = current.take() {
+                let get = |name: &str| props.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str());
+
+                if matches!(get("STATUS"), Some(status) if status.eq_ignore_ascii_case("CANCELLED")) {
+                    continue;
+                }
+                let Some(start) = get("DTSTART").and_then(|v| parse_date(v, offset)) else {
+                    continue;
+                };
+                let end = get("DTEND")
+                    .and_then(|v| parse_date(v, offset))
+                    .filter(|end| *end > start)
+                    .unwrap_or_else(|| start + chrono::Duration::days(1));
+
+                events.push(ParsedEvent {
+                    uid: get("UID").map(str::to_string).unwrap_or_else(|| format!("sin-uid-{}", start)),
+                    summary: get("SUMMARY").map(unescape_text),
+                    start,
+                    end,
+                });
+            }
+            continue;
+        }
+
+        if let Some(props) = current.as_mut() {
+            // NOMBRE;PARAM=...:VALOR
+            if let Some((name, value)) = line.split_once(':') {
+                let name = name.split(';').next().unwrap_or(name).to_ascii_uppercase();
+                props.push((name, value.to_string()));
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merida() -> FixedOffset {
+        FixedOffset::west_opt(6 * 3600).unwrap()
+    }
+
+    fn day(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    fn calendar(events: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", events)
+    }
+
+    #[test]
+    fn las_fechas_utc_se_pasan_a_la_zona_del_negocio() {
+        assert_eq!(parse_date("20250101", merida()), Some(day(2025, 1, 1)));
+        // 03:00 UTC del día 1 son las 21:00 del 31 de diciembre en Mérida
+        assert_eq!(parse_date("20250101T030000Z", merida()), Some(day(2024, 12, 31)));
+        assert_eq!(parse_date("20250101T150000Z", merida()), Some(day(2025, 1, 1)));
+        // La hora flotante ya es local
+        assert_eq!(parse_date("20250101T030000", merida()), Some(day(2025, 1, 1)));
+        assert_eq!(parse_date("2025-01-01", merida()), None);
+        assert_eq!(parse_date("20250101T25", merida()), None);
+    }
+
+    #[test]
+    fn lee_eventos_con_tzid_y_sin_dtend() {
+        let content = calendar(concat!(
+            "BEGIN:VEVENT\r\nUID:a\r\nDTSTART;TZID=America/Merida:20250310T150000\r\n",
+            "DTEND;TZID=America/Merida:20250312T110000\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:b\r\nDTSTART;VALUE=DATE:20250320\r\nEND:VEVENT\r\n",
+        ));
+        let events = parse_events(&content, merida()).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!((events[0].start, events[0].end), (day(2025, 3, 10), day(2025, 3, 12)));
+        // Sin DTEND ocupa una noche
+        assert_eq!((events[1].start, events[1].end), (day(2025, 3, 20), day(2025, 3, 21)));
+    }
+
+    #[test]
+    fn ignora_los_eventos_cancelados_y_sin_inicio() {
+        let content = calendar(concat!(
+            "BEGIN:VEVENT\r\nUID:a\r\nSTATUS:CANCELLED\r\nDTSTART;VALUE=DATE:20250101\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:b\r\nSUMMARY:Sin fecha\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:c\r\nDTSTART;VALUE=DATE:20250105\r\nDTEND;VALUE=DATE:20250107\r\nEND:VEVENT\r\n",
+        ));
+        let events = parse_events(&content, merida()).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].uid, "c");
+        assert!(parse_events("no es un calendario", merida()).is_err());
+    }
+
+    #[test]
+    fn las_lineas_largas_se_parten_y_se_vuelven_a_unir() {
+        let summary = "Reserva de la familia Pérez Canché, habitación doble con vista al jardín y desayuno incluido";
+        let folded = fold_line(&format!("SUMMARY:{}", summary));
+
+        assert!(folded.split("\r\n").all(|line| line.len() <= 75));
+        assert!(folded.contains("\r\n "));
+
+        let content = calendar(&format!(
+            "BEGIN:VEVENT\r\nUID:a\r\nDTSTART;VALUE=DATE:20250101\r\n{}END:VEVENT\r\n",
+            folded
+        ));
+        let events = parse_events(&content, merida()).unwrap();
+        assert_eq!(events[0].summary.as_deref(), Some(summary));
+    }
+
+    #[test]
+    fn el_texto_se_escapa_y_se_recupera() {
+        let text = "Llegada tarde; traer cuna, toallas\\extra\nGracias";
+        let escaped = escape_text(text);
+
+        assert_eq!(escaped, "Llegada tarde\\; traer cuna\\, toallas\\\\extra\\nGracias");
+        assert_eq!(unescape_text(&escaped), text);
+    }
+}
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
 use jsonwebtoken::{encode, decode, Header, Algorithm, Validation, EncodingKey, DecodingKey};
-use chrono::{Utc, Duration};
-use std::env;
+use chrono::{DateTime, Utc, Duration};
+
+use crate::config::AuthSettings;
+use crate::models::UserInfo;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Claims {
@@ -11,23 +13,27 @@ pub struct Claims {
     pub first_name: Option<String>,
     pub last_name: Option<String>,
     pub phone: Option<String>,
+    // Idioma preferido; los tokens emitidos antes de existir el campo no lo traen
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
     pub exp: usize,
     pub iat: usize,
 }
 
-pub fn create_jwt(user_id: i32, email: &str, role: &str, first_name: Option<&str>, last_name: Option<&str>, phone: Option<&str>) -> Result<String, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "default_secret".to_string());
+pub fn create_jwt(auth: &AuthSettings, user: &UserInfo) -> Result<String, jsonwebtoken::errors::Error> {
+    let secret = &auth.jwt_secret;
     
     let now = Utc::now();
-    let expires_at = now + Duration::hours(24);
+    let expires_at = now + Duration::hours(auth.token_lifetime_hours);
     
     let claims = Claims {
-        sub: user_id.to_string(),
-        email: email.to_string(),
-        role: role.to_string(),
-        first_name: first_name.map(|s| s.to_string()),
-        last_name: last_name.map(|s| s.to_string()),
-        phone: phone.map(|s| s.to_string()),
+        sub: user.id.to_string(),
+        email: user.email.clone(),
+        role: user.role.to_string(),
+        first_name: user.first_name.clone(),
+        last_name: user.last_name.clone(),
+        phone: user.phone.clone(),
+        locale: user.locale.clone(),
         exp: expires_at.timestamp() as usize,
         iat: now.timestamp() as usize,
     };
@@ -35,11 +41,56 @@ pub fn create_jwt(user_id: i32, email: &str, role: &str, first_name: Option<&str
     encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_ref()))
 }
 
-pub fn verify_jwt(token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
-    let secret = env::var("JWT_SECRET").unwrap_or_else(|_| "default_secret".to_string());
+pub fn verify_jwt(auth: &AuthSettings, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    let secret = &auth.jwt_secret;
     
     let validation = Validation::new(Algorithm::HS256);
     
     decode::<Claims>(token, &DecodingKey::from_secret(secret.as_ref()), &validation)
         .map(|data| data.claims)
 }
+
+// Código de check-in del comprobante: identifica la reserva y solo sirve para registrar la llegada
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CheckInClaims {
+    pub purpose: String,
+    pub kind: String,
+    pub booking_id: i32,
+    pub reference: String,
+    pub exp: usize,
+    pub iat: usize,
+}
+
+const CHECK_IN_PURPOSE: &str = "check_in";
+
+// Vence check_in_token_grace_hours después de que termina la reserva
+pub fn create_check_in_token(auth: &AuthSettings, kind: &str, booking_id: i32, reference: &str, ends_at: DateTime<Utc>) -> Result<String, jsonwebtoken::errors::Error> {
+    let secret = &auth.jwt_secret;
+    let expires_at = ends_at + Duration::hours(auth.check_in_token_grace_hours);
+
+    let claims = CheckInClaims {
+        purpose: CHECK_IN_PURPOSE.to_string(),
+        kind: kind.to_string(),
+        booking_id,
+        reference: reference.to_string(),
+        exp: expires_at.timestamp() as usize,
+        iat: Utc::now().timestamp() as usize,
+    };
+
+    encode(&Header::default(), &claims, &EncodingKey::from_secret(secret.as_ref()))
+}
+
+pub fn verify_check_in_token(auth: &AuthSettings, token: &str) -> Result<CheckInClaims, jsonwebtoken::errors::Error> {
+    let secret = &auth.jwt_secret;
+
+    let validation = Validation::new(Algorithm::HS256);
+
+    let claims = decode::<CheckInClaims>(token, &DecodingKey::from_secret(secret.as_ref()), &validation)
+        .map(|data| data.claims)?;
+
+    if claims.purpose != CHECK_IN_PURPOSE {
+        return Err(jsonwebtoken::errors::ErrorKind::InvalidToken.into());
+    }
+
+    Ok(claims)
+}
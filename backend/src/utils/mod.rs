@@ -1,2 +1,6 @@
 pub mod jwt;
 pub mod errors;
+pub mod ical;
+pub mod pdf;
+pub mod i18n;
+pub mod pagination;
@@ -0,0 +1,375 @@
+// Paginación por cursor (keyset) compartida por los listados.
+// Cada listado declara con `Listing` por qué columnas se puede ordenar y filtrar; el extractor `Page`
+// lee limit, cursor, sort y los filtros de la query string y genera los fragmentos SQL. El cursor
+// guarda el orden, el valor de la columna de orden y la clave de la última fila, así que las páginas
+// no se desplazan aunque se inserten filas nuevas mientras el cliente recorre el listado.
+//
+//   GET /api/v1/hotels/my?limit=10&sort=-price&status=approved,pending
+//   → { "data": [...], "next_cursor": "…", "limit": 10, "sort": "-price" }
+//
+// Para la página siguiente se envía cursor=next_cursor con los mismos filtros; next_cursor es null en
+// la última página.
+//
+// Es el único contrato de paginación de la API: todo listado que crece con el uso (hoteles, negocios,
+// productos, reservas, pedidos, reseñas, cola de moderación) se sirve así. Se devuelven completos solo
+// los que tienen un tamaño acotado por naturaleza: la flota, rutas y menú de un negocio, las listas de
+// favoritos, las traducciones, el carrito y las suspensiones activas; las notificaciones se limitan a
+// las 100 más recientes.
+
+use std::marker::PhantomData;
+
+use actix_web::{dev::Payload, http::StatusCode, web, FromRequest, HttpRequest};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, NaiveDate};
+use futures::future::{ready, Ready};
+use serde::Serialize;
+use sqlx::{
+    postgres::{PgArguments, PgRow},
+    query::Query,
+    Postgres, Row,
+};
+use utoipa::{
+    openapi::{
+        path::{Parameter, ParameterBuilder, ParameterIn},
+        schema::{ObjectBuilder, Type},
+        Required,
+    },
+    IntoParams, ToSchema,
+};
+
+use crate::utils::errors::AppError;
+
+pub const DEFAULT_LIMIT: i64 = 20;
+pub const MAX_LIMIT: i64 = 100;
+
+// Tipo SQL de una columna de orden o de filtro; sirve para convertir el texto recibido y validarlo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Text,
+    Integer,
+    Numeric,
+    Date,
+    Timestamp,
+}
+
+impl Kind {
+    fn sql_type(self) -> &'static str {
+        match self {
+            Kind::Text => "text",
+            Kind::Integer => "integer",
+            Kind::Numeric => "numeric",
+            Kind::Date => "date",
+            Kind::Timestamp => "timestamptz",
+        }
+    }
+
+    // Los timestamps llegan tal como los devuelve Postgres al convertirlos a texto
+    fn accepts(self, value: &str) -> bool {
+        match self {
+            Kind::Text => true,
+            Kind::Integer => value.parse::<i64>().is_ok(),
+            Kind::Numeric => value.parse::<f64>().map(f64::is_finite).unwrap_or(false),
+            Kind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            Kind::Timestamp => DateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f%#z").is_ok()
+                || DateTime::parse_from_rfc3339(value).is_ok(),
+        }
+    }
+}
+
+// Columna por la que se puede ordenar; la expresión no debe ser NULL (usar COALESCE si hace falta)
+pub struct SortField {
+    pub name: &'static str,
+    pub column: &'static str,
+    pub kind: Kind,
+}
+
+impl SortField {
+    pub const fn new(name: &'static str, column: &'static str, kind: Kind) -> Self {
+        SortField { name, column, kind }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterOp {
+    // Igual a alguno de los valores separados por comas
+    AnyOf,
+    AtLeast,
+    AtMost,
+}
+
+pub struct Filter {
+    pub name: &'static str,
+    pub column: &'static str,
+    pub kind: Kind,
+    pub op: FilterOp,
+}
+
+impl Filter {
+    pub const fn any_of(name: &'static str, column: &'static str, kind: Kind) -> Self {
+        Filter { name, column, kind, op: FilterOp::AnyOf }
+    }
+
+    pub const fn at_least(name: &'static str, column: &'static str, kind: Kind) -> Self {
+        Filter { name, column, kind, op: FilterOp::AtLeast }
+    }
+
+    pub const fn at_most(name: &'static str, column: &'static str, kind: Kind) -> Self {
+        Filter { name, column, kind, op: FilterOp::AtMost }
+    }
+}
+
+// Descripción de un listado paginable
+pub trait Listing {
+    // Expresión única que desempata el orden y se guarda en el cursor (entera, normalmente el id)
+    const KEY: &'static str;
+    const SORTS: &'static [SortField];
+    // Orden por defecto; un "-" delante indica descendente
+    const DEFAULT_SORT: &'static str;
+    const FILTERS: &'static [Filter];
+    const MAX_LIMIT: i64 = MAX_LIMIT;
+}
+
+// Parámetros de paginación ya validados de un listado
+pub struct Page<L: Listing> {
+    pub limit: i64,
+    sort: &'static SortField,
+    descending: bool,
+    after: Option<(String, i64)>,
+    filters: Vec<(&'static Filter, Vec<String>)>,
+    listing: PhantomData<L>,
+}
+
+// Sobre común de los listados; en la especificación cada listado lo documenta como <Elemento>Page
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PageResponse<T: Serialize> {
+    pub data: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub limit: i64,
+    pub sort: String,
+}
+
+fn parse_sort<L: Listing>(value: &str) -> Option<(&'static SortField, bool)> {
+    let (name, descending) = match value.strip_prefix('-') {
+        Some(name) => (name, true),
+        None => (value, false),
+    };
+    L::SORTS.iter().find(|field| field.name == name).map(|field| (field, descending))
+}
+
+fn invalid_cursor() -> AppError {
+    AppError::localized(StatusCode::BAD_REQUEST, "pagination.invalid_cursor")
+}
+
+// El cursor es opaco para el cliente: orden, clave y valor de la última fila en base64url
+fn encode_cursor(sort: &str, key: i64, value: &str) -> String {
+    URL_SAFE_NO_PAD.encode(format!("{}\n{}\n{}", sort, key, value))
+}
+
+fn decode_cursor(cursor: &str) -> Option<(String, i64, String)> {
+    let bytes = URL_SAFE_NO_PAD.decode(cursor.trim()).ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let mut parts = text.splitn(3, '\n');
+    let sort = parts.next()?.to_string();
+    let key = parts.next()?.parse().ok()?;
+    let value = parts.next()?.to_string();
+    Some((sort, key, value))
+}
+
+impl<L: Listing> Page<L> {
+    pub fn from_query(query_string: &str) -> Result<Self, AppError> {
+        let params = web::Query::<Vec<(String, String)>>::from_query(query_string)
+            .map_err(|e| AppError::localized(StatusCode::BAD_REQUEST, "invalid_query").arg("detail", e))?
+            .into_inner();
+        let param = |name: &str| {
+            params
+                .iter()
+                .rev()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+
+        let limit = match param("limit") {
+            Some(value) => value
+                .parse::<i64>()
+                .map_err(|_| AppError::localized(StatusCode::BAD_REQUEST, "pagination.invalid_limit"))?,
+            None => DEFAULT_LIMIT,
+        }
+        .clamp(1, L::MAX_LIMIT);
+
+        let cursor = param("cursor").map(|c| decode_cursor(c).ok_or_else(invalid_cursor)).transpose()?;
+
+        // Sin sort explícito se sigue el orden con que se generó el cursor
+        let sort_param = param("sort")
+            .map(str::to_string)
+            .or_else(|| cursor.as_ref().map(|(sort, _, _)| sort.clone()))
+            .unwrap_or_else(|| L::DEFAULT_SORT.to_string());
+        let (sort, descending) = parse_sort::<L>(&sort_param).ok_or_else(|| {
+            let allowed: Vec<&str> = L::SORTS.iter().map(|field| field.name).collect();
+            AppError::localized(StatusCode::BAD_REQUEST, "pagination.invalid_sort")
+                .arg("sort", &sort_param)
+                .arg("allowed", allowed.join(", "))
+        })?;
+
+        let after = match cursor {
+            Some((cursor_sort, key, value)) => {
+                let matches = parse_sort::<L>(&cursor_sort)
+                    .is_some_and(|(field, desc)| field.name == sort.name && desc == descending);
+                if !matches || !sort.kind.accepts(&value) {
+                    return Err(invalid_cursor());
+                }
+                Some((value, key))
+            }
+            None => None,
+        };
+
+        let mut filters = Vec::new();
+        for filter in L::FILTERS {
+            let Some(raw) = param(filter.name) else { continue };
+            let values: Vec<String> = match filter.op {
+                FilterOp::AnyOf => raw.split(',').map(str::trim).filter(|v| !v.is_empty()).map(str::to_string).collect(),
+                FilterOp::AtLeast | FilterOp::AtMost => vec![raw.to_string()],
+            };
+            if values.is_empty() || !values.iter().all(|value| filter.kind.accepts(value)) {
+                return Err(AppError::localized(StatusCode::BAD_REQUEST, "pagination.invalid_filter")
+                    .arg("filter", filter.name)
+                    .arg("value", raw));
+            }
+            filters.push((filter, values));
+        }
+
+        Ok(Page { limit, sort, descending, after, filters, listing: PhantomData })
+    }
+
+    pub fn sort(&self) -> String {
+        format!("{}{}", if self.descending { "-" } else { "" }, self.sort.name)
+    }
+
+    // Columnas extra para el SELECT; `finish` las usa para construir el siguiente cursor
+    pub fn columns(&self) -> String {
+        format!("({})::text AS page_sort, ({})::bigint AS page_key", self.sort.column, L::KEY)
+    }
+
+    // Condiciones de filtros y cursor, cada una precedida de AND. Los parámetros se numeran desde
+    // `first_param`, después de los propios de la consulta.
+    pub fn conditions(&self, first_param: usize) -> String {
+        let mut sql = String::new();
+        let mut param = first_param;
+        for (filter, _) in &self.filters {
+            let condition = match filter.op {
+                FilterOp::AnyOf => format!("({})::text = ANY(${})", filter.column, param),
+                FilterOp::AtLeast => format!("{} >= ${}::{}", filter.column, param, filter.kind.sql_type()),
+                FilterOp::AtMost => format!("{} <= ${}::{}", filter.column, param, filter.kind.sql_type()),
+            };
+            sql.push_str(" AND ");
+            sql.push_str(&condition);
+            param += 1;
+        }
+        if self.after.is_some() {
+            sql.push_str(&format!(
+                " AND ({}, {}) {} (${}::{}, ${})",
+                self.sort.column,
+                L::KEY,
+                if self.descending { "<" } else { ">" },
+                param,
+                self.sort.kind.sql_type(),
+                param + 1
+            ));
+        }
+        sql
+    }
+
+    // Se pide una fila de más para saber si hay página siguiente
+    pub fn order_and_limit(&self) -> String {
+        let direction = if self.descending { "DESC" } else { "ASC" };
+        format!(
+            "ORDER BY {} {}, {} {} LIMIT {}",
+            self.sort.column, direction, L::KEY, direction, self.limit + 1
+        )
+    }
+
+    // Enlaza los valores de `conditions` en el mismo orden
+    pub fn bind<'q>(&self, mut query: Query<'q, Postgres, PgArguments>) -> Query<'q, Postgres, PgArguments> {
+        for (filter, values) in &self.filters {
+            query = match filter.op {
+                FilterOp::AnyOf => query.bind(values.clone()),
+                FilterOp::AtLeast | FilterOp::AtMost => query.bind(values[0].clone()),
+            };
+        }
+        if let Some((value, key)) = &self.after {
+            query = query.bind(value.clone()).bind(*key);
+        }
+        query
+    }
+
+    // Recorta la fila sobrante y genera el cursor de la página siguiente
+    pub fn finish(&self, mut rows: Vec<PgRow>) -> (Vec<PgRow>, Option<String>) {
+        if rows.len() as i64 <= self.limit {
+            return (rows, None);
+        }
+        rows.truncate(self.limit as usize);
+        let next_cursor = rows.last().map(|row| {
+            encode_cursor(&self.sort(), row.get::<i64, _>("page_key"), &row.get::<String, _>("page_sort"))
+        });
+        (rows, next_cursor)
+    }
+
+    pub fn response<T: Serialize>(&self, data: Vec<T>, next_cursor: Option<String>) -> PageResponse<T> {
+        PageResponse { data, next_cursor, limit: self.limit, sort: self.sort() }
+    }
+}
+
+impl<L: Listing> FromRequest for Page<L> {
+    type Error = AppError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Self::from_query(req.query_string()))
+    }
+}
+
+// Parámetros de la especificación OpenAPI
+impl<L: Listing> IntoParams for Page<L> {
+    fn into_params(_parameter_in_provider: impl Fn() -> Option<ParameterIn>) -> Vec<Parameter> {
+        let query_param = |name: &str, description: String, schema: ObjectBuilder| {
+            ParameterBuilder::new()
+                .name(name)
+                .parameter_in(ParameterIn::Query)
+                .required(Required::False)
+                .description(Some(description))
+                .schema(Some(schema))
+                .build()
+        };
+        let sorts: Vec<String> = L::SORTS
+            .iter()
+            .flat_map(|field| [field.name.to_string(), format!("-{}", field.name)])
+            .collect();
+
+        let mut params = vec![
+            query_param(
+                "limit",
+                format!("Filas por página (por defecto {}, máximo {})", DEFAULT_LIMIT, L::MAX_LIMIT),
+                ObjectBuilder::new().schema_type(Type::Integer).minimum(Some(1)).maximum(Some(L::MAX_LIMIT)),
+            ),
+            query_param(
+                "cursor",
+                "next_cursor de la página anterior".to_string(),
+                ObjectBuilder::new().schema_type(Type::String),
+            ),
+            query_param(
+                "sort",
+                format!("Orden; un \"-\" delante indica descendente (por defecto {})", L::DEFAULT_SORT),
+                ObjectBuilder::new().schema_type(Type::String).enum_values(Some(sorts)),
+            ),
+        ];
+        for filter in L::FILTERS {
+            let description = match filter.op {
+                FilterOp::AnyOf => "Uno o varios valores separados por comas",
+                FilterOp::AtLeast => "Valor mínimo (incluido)",
+                FilterOp::AtMost => "Valor máximo (incluido)",
+            };
+            params.push(query_param(filter.name, description.to_string(), ObjectBuilder::new().schema_type(Type::String)));
+        }
+        params
+    }
+}
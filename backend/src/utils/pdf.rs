@@ -0,0 +1,206 @@
+use qrcode::{Color, QrCode};
+
+// Generador mínimo de PDF: páginas A4 con texto en Helvetica, líneas, rectángulos y códigos QR.
+// Usa las fuentes estándar del lector (sin incrustar) con codificación WinAnsi.
+
+pub const PAGE_WIDTH: f32 = 595.0;
+pub const PAGE_HEIGHT: f32 = 842.0;
+
+#[derive(Clone, Copy)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+
+    // Ancho aproximado de Helvetica; suficiente para alinear importes a la derecha
+    fn char_width(self, c: char) -> f32 {
+        let base = match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '\'' | '|' | '!' => 0.28,
+            'f' | 't' | 'r' | ' ' | '(' | ')' | '-' | '/' | 'I' => 0.33,
+            'm' | 'M' | 'W' => 0.83,
+            'w' => 0.72,
+            c if c.is_ascii_digit() => 0.556,
+            c if c.is_uppercase() => 0.68,
+            _ => 0.53,
+        };
+        match self {
+            Font::Regular => base,
+            Font::Bold => base * 1.06,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Page {
+    content: Vec<u8>,
+}
+
+impl Page {
+    pub fn new() -> Self {
+        Page::default()
+    }
+
+    // (x, y) en puntos desde la esquina inferior izquierda
+    pub fn text(&mut self, x: f32, y: f32, size: f32, font: Font, text: &str) {
+        self.content.extend_from_slice(format!("BT /{} {} Tf {:.2} {:.2} Td (", font.resource(), size, x, y).as_bytes());
+        self.content.extend(encode_text(text));
+        self.content.extend_from_slice(b") Tj ET\n");
+    }
+
+    pub fn text_right(&mut self, right: f32, y: f32, size: f32, font: Font, text: &str) {
+        self.text(right - text_width(text, size, font), y, size, font, text);
+    }
+
+    pub fn line(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} w {:.2} {:.2} m {:.2} {:.2} l S\n", width, x1, y1, x2, y2).as_bytes(),
+        );
+    }
+
+    // Rectángulo relleno en escala de grises (0 = negro, 1 = blanco)
+    pub fn fill_rect(&mut self, x: f32, y: f32, width: f32, height: f32, gray: f32) {
+        self.content.extend_from_slice(
+            format!("{:.2} g {:.2} {:.2} {:.2} {:.2} re f 0 g\n", gray, x, y, width, height).as_bytes(),
+        );
+    }
+
+    // Código QR cuadrado de `size` puntos con su esquina inferior izquierda en (x, y)
+    pub fn qr_code(&mut self, x: f32, y: f32, size: f32, data: &str) -> Result<(), String> {
+        let code = QrCode::new(data.as_bytes()).map_err(|e| e.to_string())?;
+        let width = code.width();
+        let module = size / width as f32;
+
+        for (index, color) in code.to_colors().into_iter().enumerate() {
+            if color != Color::Dark {
+                continue;
+            }
+            let (col, row) = (index % width, index / width);
+            self.content.extend_from_slice(
+                format!(
+                    "{:.3} {:.3} {:.3} {:.3} re\n",
+                    x + col as f32 * module,
+                    y + size - (row + 1) as f32 * module,
+                    module,
+                    module
+                )
+                .as_bytes(),
+            );
+        }
+        self.content.extend_from_slice(b"f\n");
+        Ok(())
+    }
+}
+
+pub fn text_width(text: &str, size: f32, font: Font) -> f32 {
+    text.chars().map(|c| font.char_width(c)).sum::<f32>() * size
+}
+
+// Unicode a WinAnsi (cp1252); los caracteres sin equivalente se sustituyen
+fn encode_text(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                bytes.push(b'\\');
+                bytes.push(c as u8);
+            }
+            ' '..='~' => bytes.push(c as u8),
+            '\u{a0}'..='\u{ff}' => bytes.push(c as u32 as u8),
+            '€' => bytes.push(0x80),
+            '‘' => bytes.push(0x91),
+            '’' => bytes.push(0x92),
+            '“' => bytes.push(0x93),
+            '”' => bytes.push(0x94),
+            '•' => bytes.push(0x95),
+            '–' => bytes.push(0x96),
+            '—' => bytes.push(0x97),
+            '→' => bytes.extend_from_slice(b"->"),
+            '\n' | '\r' | '\t' => bytes.push(b' '),
+            _ => bytes.push(b'?'),
+        }
+    }
+    bytes
+}
+
+pub struct Document {
+    title: String,
+    pages: Vec<Page>,
+}
+
+impl Document {
+    pub fn new(title: &str) -> Self {
+        Document { title: title.to_string(), pages: Vec::new() }
+    }
+
+    pub fn add_page(&mut self, page: Page) {
+        self.pages.push(page);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        // Objetos: 1 catálogo, 2 árbol de páginas, 3-4 fuentes, 5 info, y por página su objeto y su contenido
+        let page_ids: Vec<usize> = (0..self.pages.len()).map(|i| 6 + i * 2).collect();
+        let mut objects: Vec<Vec<u8>> = vec![
+            b"<< /Type /Catalog /Pages 2 0 R >>".to_vec(),
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                page_ids.iter().map(|id| format!("{} 0 R", id)).collect::<Vec<_>>().join(" "),
+                page_ids.len()
+            )
+            .into_bytes(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_vec(),
+            b"<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_vec(),
+            {
+                let mut info = b"<< /Producer (Maya Digital) /Title (".to_vec();
+                info.extend(encode_text(&self.title));
+                info.extend_from_slice(b") >>");
+                info
+            },
+        ];
+
+        for (page, id) in self.pages.into_iter().zip(&page_ids) {
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                    PAGE_WIDTH, PAGE_HEIGHT, id + 1
+                )
+                .into_bytes(),
+            );
+            let mut stream = format!("<< /Length {} >>\nstream\n", page.content.len()).into_bytes();
+            stream.extend(page.content);
+            stream.extend_from_slice(b"\nendstream");
+            objects.push(stream);
+        }
+
+        let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(pdf.len());
+            pdf.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            pdf.extend_from_slice(object);
+            pdf.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = pdf.len();
+        pdf.extend_from_slice(format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes());
+        for offset in offsets {
+            pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+        }
+        pdf.extend_from_slice(
+            format!(
+                "trailer\n<< /Size {} /Root 1 0 R /Info 5 0 R >>\nstartxref\n{}\n%%EOF\n",
+                objects.len() + 1,
+                xref_offset
+            )
+            .as_bytes(),
+        );
+        pdf
+    }
+}
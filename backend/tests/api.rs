@@ -0,0 +1,308 @@
+// Pruebas de integración sobre la aplicación completa (build_app).
+// El pool es perezoso: estas rutas responden antes de tocar la base de datos.
+
+use actix_web::{http::StatusCode, test};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::{UserInfo, UserRole},
+    utils::jwt::create_jwt,
+};
+
+fn test_settings() -> Settings {
+    Settings::for_profile(Profile::Test)
+}
+
+fn lazy_pool() -> PgPool {
+    PgPoolOptions::new()
+        .connect_lazy(&test_settings().database.url)
+        .expect("DATABASE_URL inválida")
+}
+
+fn ana() -> UserInfo {
+    UserInfo {
+        id: 7,
+        email: "ana@example.com".to_string(),
+        role: UserRole::Customer,
+        first_name: Some("Ana".to_string()),
+        last_name: Some("Pérez".to_string()),
+        phone: None,
+        locale: None,
+    }
+}
+
+#[actix_web::test]
+async fn health_responde_ok() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/health").to_request()).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["status"], "OK");
+}
+
+#[actix_web::test]
+async fn ruta_inexistente_responde_404() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/api/v1/no-existe").to_request()).await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "route_not_found");
+    assert_eq!(body["message"], "Ruta no encontrada");
+}
+
+#[actix_web::test]
+async fn registro_valida_los_datos() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/register")
+        .set_json(serde_json::json!({
+            "first_name": "A",
+            "last_name": "Pérez",
+            "email": "no-es-un-correo",
+            "password": "123"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(resp.headers().contains_key("x-request-id"));
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "validation_failed");
+    assert!(body["request_id"].is_string());
+
+    let details = body["details"].as_array().unwrap();
+    let fields: Vec<&str> = details.iter().map(|d| d["field"].as_str().unwrap()).collect();
+    assert_eq!(fields, ["email", "first_name", "password"]);
+    // El valor enviado (p. ej. la contraseña) no se devuelve
+    assert!(details.iter().all(|d| d["params"].get("value").is_none()));
+    assert_eq!(details[0]["message"], "Debe ser un email válido");
+}
+
+#[actix_web::test]
+async fn json_mal_formado_usa_el_formato_de_error() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/login")
+        .insert_header(("Content-Type", "application/json"))
+        .set_payload("{no es json")
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "invalid_json");
+    assert!(body.get("details").is_none());
+}
+
+#[actix_web::test]
+async fn el_request_id_entrante_se_conserva() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/auth/me")
+        .insert_header(("X-Request-Id", "prueba-123"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(resp.headers().get("x-request-id").unwrap(), "prueba-123");
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "auth.token_missing");
+    assert_eq!(body["request_id"], "prueba-123");
+}
+
+#[actix_web::test]
+async fn los_errores_respetan_accept_language() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/auth/register")
+        .insert_header(("Accept-Language", "en-US,en;q=0.9,es;q=0.8"))
+        .set_json(serde_json::json!({
+            "first_name": "A",
+            "last_name": "Pérez",
+            "email": "no-es-un-correo",
+            "password": "123"
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(resp.headers().get("content-language").unwrap(), "en");
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["message"], "Invalid data");
+    assert_eq!(body["details"][0]["message"], "Must be a valid email");
+}
+
+#[actix_web::test]
+async fn la_preferencia_del_token_manda_sobre_accept_language() {
+    let settings = test_settings();
+    let mut user = ana();
+    user.locale = Some("en".to_string());
+    let token = create_jwt(&settings.auth, &user).unwrap();
+    let app = test::init_service(build_app(lazy_pool(), settings)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/no-existe")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .insert_header(("Accept-Language", "es-MX"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.headers().get("content-language").unwrap(), "en");
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["message"], "Route not found");
+}
+
+// Sin token, una ruta registrada responde 401; si faltara en configure() respondería 404
+#[actix_web::test]
+async fn rutas_protegidas_de_cada_modulo_estan_registradas() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let routes = [
+        ("GET", "/api/v1/auth/me"),
+        ("PUT", "/api/v1/auth/me/locale"),
+        ("GET", "/api/v1/hotels/my"),
+        ("GET", "/api/v1/hotels/1/bookings"),
+        ("PUT", "/api/v1/hotels/1/bookings/2/confirm"),
+        ("PUT", "/api/v1/businesses/1/transport/bookings/2/confirm"),
+        ("GET", "/api/v1/bookings/my"),
+        ("GET", "/api/v1/bookings/1/voucher.pdf"),
+        ("GET", "/api/v1/bookings/1/check-in-token"),
+        ("GET", "/api/v1/businesses/my"),
+        ("GET", "/api/v1/businesses/1/manage"),
+        ("DELETE", "/api/v1/businesses/1"),
+        ("GET", "/api/v1/businesses/1/menu/manage"),
+        ("GET", "/api/v1/businesses/1/orders"),
+        ("GET", "/api/v1/businesses/1/transport/vehicles"),
+        ("GET", "/api/v1/cart"),
+        ("GET", "/api/v1/orders/my"),
+        ("GET", "/api/v1/transport/bookings/my"),
+        ("GET", "/api/v1/itinerary"),
+        ("GET", "/api/v1/reviews/my"),
+        ("GET", "/api/v1/favorites/lists"),
+        ("GET", "/api/v1/hotels/1/calendar"),
+        ("GET", "/api/v1/hotels/1/analytics"),
+        ("GET", "/api/v1/notifications"),
+        ("GET", "/api/v1/admin/hotels"),
+        ("GET", "/api/v1/admin/businesses/pending"),
+        ("GET", "/api/v1/admin/moderation"),
+        ("GET", "/api/v1/admin/suspensions"),
+        ("GET", "/api/v1/admin/metrics"),
+        ("GET", "/api/v1/admin/translations/missing?locale=en"),
+        ("GET", "/api/v1/hotels/1/translations"),
+        ("PUT", "/api/v1/hotels/1/translations/en"),
+        ("DELETE", "/api/v1/businesses/1/translations/en"),
+        ("PUT", "/api/v1/businesses/1/menu/items/1/translations/en"),
+    ];
+
+    for (method, uri) in routes {
+        let req = match method {
+            "DELETE" => test::TestRequest::delete(),
+            "PUT" => test::TestRequest::put().set_json(serde_json::json!({ "locale": "en" })),
+            _ => test::TestRequest::get(),
+        }
+        .uri(uri)
+        .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED, "{} {}", method, uri);
+    }
+}
+
+#[actix_web::test]
+async fn token_invalido_es_rechazado() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/bookings/my")
+        .insert_header(("Authorization", "Bearer no-es-un-jwt"))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[actix_web::test]
+async fn token_firmado_con_la_configuracion_autentica() {
+    let settings = test_settings();
+    let token = create_jwt(&settings.auth, &ana()).unwrap();
+    let app = test::init_service(build_app(lazy_pool(), settings)).await;
+
+    let req = test::TestRequest::get()
+        .uri("/api/v1/auth/me")
+        .insert_header(("Authorization", format!("Bearer {}", token)))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["user"]["id"], 7);
+}
+
+#[actix_web::test]
+async fn las_rutas_sin_version_siguen_funcionando_con_aviso_de_retirada() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/api/auth/me").to_request()).await;
+
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let headers = resp.headers();
+    assert_eq!(headers.get("deprecation").unwrap(), "@1792368000");
+    assert_eq!(headers.get("sunset").unwrap(), "Fri, 30 Apr 2027 00:00:00 GMT");
+    assert_eq!(headers.get("link").unwrap(), r#"</api/v1/auth/me>; rel="successor-version""#);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "auth.token_missing");
+}
+
+// /register y /login de la primera versión siguen en la raíz, también con aviso de retirada
+#[actix_web::test]
+async fn registro_e_inicio_de_sesion_en_la_raiz_siguen_funcionando() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let register = test::TestRequest::post()
+        .uri("/register")
+        .set_json(serde_json::json!({
+            "first_name": "A",
+            "last_name": "Pérez",
+            "email": "no-es-un-correo",
+            "password": "123"
+        }))
+        .to_request();
+    let login = test::TestRequest::post()
+        .uri("/login")
+        .set_json(serde_json::json!({ "email": "no-es-un-correo", "password": "" }))
+        .to_request();
+
+    for (req, successor) in [(register, "/api/v1/auth/register"), (login, "/api/v1/auth/login")] {
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", successor);
+        let headers = resp.headers();
+        assert_eq!(headers.get("deprecation").unwrap(), "@1792368000");
+        assert_eq!(headers.get("sunset").unwrap(), "Fri, 30 Apr 2027 00:00:00 GMT");
+        assert_eq!(headers.get("link").unwrap().to_str().unwrap(), format!("<{}>; rel=\"successor-version\"", successor));
+        let body: serde_json::Value = test::read_body_json(resp).await;
+        assert_eq!(body["code"], "validation_failed");
+    }
+}
+
+#[actix_web::test]
+async fn las_rutas_versionadas_no_llevan_aviso_de_retirada() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    for uri in ["/api/v1/auth/me", "/api/v1/no-existe", "/health"] {
+        let resp = test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        assert!(resp.headers().get("deprecation").is_none(), "{}", uri);
+        assert!(resp.headers().get("sunset").is_none(), "{}", uri);
+    }
+}
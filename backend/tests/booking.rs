@@ -0,0 +1,74 @@
+// Reservas de hotel contra la base de datos (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::Row;
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::{UserInfo, UserRole},
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+// Dos reservas simultáneas por la última habitación: solo una puede quedarse con ella
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn reservas_simultaneas_no_sobrevenden_la_ultima_habitacion() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let first = create_user(&pool, UserRole::Customer).await;
+    let second = create_user(&pool, UserRole::Customer).await;
+    let hotel_id = sqlx::query(
+        "INSERT INTO hotels (owner_id, name, location, address, price, status, rooms_available) VALUES ($1, 'Hotel Lleno', 'Mérida', 'Calle 60', 900, 'approved', 1) RETURNING id"
+    )
+    .bind(owner.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap()
+    .get::<i32, _>("id");
+
+    let app = test::init_service(build_app(pool.clone(), settings.clone())).await;
+    let check_in = chrono::Utc::now().date_naive() + chrono::Duration::days(30);
+    let booking = |user: &UserInfo| {
+        test::TestRequest::post()
+            .uri("/api/v1/bookings")
+            .insert_header(("Authorization", bearer(&settings.auth, user)))
+            .set_json(serde_json::json!({
+                "hotel_id": hotel_id,
+                "check_in": check_in,
+                "check_out": check_in + chrono::Duration::days(3),
+                "guests": 2,
+                "rooms": 1
+            }))
+            .to_request()
+    };
+
+    let (a, b) = futures::join!(test::call_service(&app, booking(&first)), test::call_service(&app, booking(&second)));
+    let mut statuses = [a.status(), b.status()];
+    statuses.sort();
+    assert_eq!(statuses, [StatusCode::CREATED, StatusCode::CONFLICT]);
+
+    let booked = sqlx::query("SELECT COUNT(*) AS booked FROM bookings WHERE hotel_id = $1")
+        .bind(hotel_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<i64, _>("booked");
+    assert_eq!(booked, 1);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM email_outbox WHERE user_id = ANY($1)",
+            "DELETE FROM bookings WHERE user_id = ANY($1)",
+            "DELETE FROM hotels WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, first.id, second.id],
+    )
+    .await;
+}
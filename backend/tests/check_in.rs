@@ -0,0 +1,139 @@
+// Flujo completo de check-in contra la base de datos: reserva nueva, confirmación del propietario y
+// registro de llegada (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::Row;
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::UserRole,
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn una_reserva_nueva_admite_el_check_in_tras_la_confirmacion_del_propietario() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let customer = create_user(&pool, UserRole::Customer).await;
+    let hotel_id = sqlx::query(
+        "INSERT INTO hotels (owner_id, name, location, address, price, status) VALUES ($1, 'Hotel Check-in', 'Mérida', 'Calle 60', 900, 'approved') RETURNING id"
+    )
+    .bind(owner.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap()
+    .get::<i32, _>("id");
+    // La estancia empieza hoy en la zona horaria del negocio
+    let today = sqlx::query("SELECT (NOW() AT TIME ZONE 'America/Merida')::date AS today")
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<chrono::NaiveDate, _>("today");
+
+    let owner_token = bearer(&settings.auth, &owner);
+    let customer_token = bearer(&settings.auth, &customer);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/bookings")
+        .insert_header(("Authorization", customer_token.clone()))
+        .set_json(serde_json::json!({
+            "hotel_id": hotel_id,
+            "check_in": today,
+            "check_out": today + chrono::Duration::days(2),
+            "guests": 2,
+            "rooms": 1
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    let booking_id = body["booking"]["id"].as_i64().unwrap();
+    assert_eq!(body["booking"]["status"], "pending");
+
+    let req = test::TestRequest::get()
+        .uri(&format!("/api/v1/bookings/{}/check-in-token", booking_id))
+        .insert_header(("Authorization", customer_token))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    let check_in = serde_json::json!({ "token": body["token"] });
+
+    // Pendiente: todavía no se puede registrar la llegada
+    let req = test::TestRequest::post()
+        .uri("/api/v1/check-in")
+        .insert_header(("Authorization", owner_token.clone()))
+        .set_json(&check_in)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "check_in.not_confirmed");
+
+    // El aviso al cliente se escribe en su idioma, no en el del propietario
+    sqlx::query("UPDATE users SET preferred_locale = 'en' WHERE id = $1")
+        .bind(customer.id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/v1/hotels/{}/bookings/{}/confirm", hotel_id, booking_id))
+        .insert_header(("Authorization", owner_token.clone()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["message"], "Reserva confirmada exitosamente");
+    let title = sqlx::query("SELECT title FROM notifications WHERE user_id = $1 AND kind = 'booking_confirmed'")
+        .bind(customer.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<String, _>("title");
+    assert_eq!(title, "Your booking was confirmed");
+
+    // Una reserva ya confirmada no se vuelve a confirmar
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/v1/hotels/{}/bookings/{}/confirm", hotel_id, booking_id))
+        .insert_header(("Authorization", owner_token.clone()))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/check-in")
+        .insert_header(("Authorization", owner_token.clone()))
+        .set_json(&check_in)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["booking"]["booking_id"], booking_id);
+    assert!(body["booking"]["checked_in_at"].is_string());
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/check-in")
+        .insert_header(("Authorization", owner_token))
+        .set_json(&check_in)
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM notifications WHERE user_id = ANY($1)",
+            "DELETE FROM email_outbox WHERE user_id = ANY($1)",
+            "DELETE FROM bookings WHERE user_id = ANY($1)",
+            "DELETE FROM hotels WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, customer.id],
+    )
+    .await;
+}
@@ -0,0 +1,56 @@
+// Utilidades compartidas por las pruebas que necesitan base de datos. Esas pruebas están marcadas
+// con #[ignore] y se ejecutan con `cargo test -- --ignored` y DATABASE_URL apuntando a una base de
+// pruebas; si falta la variable fallan en lugar de pasar sin comprobar nada.
+
+#![allow(dead_code)]
+
+use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+use maya_digital_backend::{
+    config::AuthSettings,
+    models::{UserInfo, UserRole},
+    utils::jwt::create_jwt,
+    MIGRATOR,
+};
+
+pub async fn test_pool() -> PgPool {
+    let url = std::env::var("DATABASE_URL").expect("DATABASE_URL no definida: las pruebas con base de datos la necesitan");
+    let pool = PgPoolOptions::new().max_connections(4).connect(&url).await.expect("No se pudo conectar a DATABASE_URL");
+    MIGRATOR.run(&pool).await.expect("No se pudieron aplicar las migraciones");
+    pool
+}
+
+// Usuario con un correo único para que las pruebas no choquen entre sí
+pub async fn create_user(pool: &PgPool, role: UserRole) -> UserInfo {
+    let email = format!("{}-{}@example.com", role, uuid::Uuid::new_v4().simple());
+    let id = sqlx::query(
+        "INSERT INTO users (email, password_hash, first_name, last_name, role) VALUES ($1, 'x', 'Prueba', 'Integración', $2) RETURNING id"
+    )
+    .bind(&email)
+    .bind(role.to_string())
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .get::<i32, _>("id");
+
+    UserInfo {
+        id,
+        email,
+        role,
+        first_name: Some("Prueba".to_string()),
+        last_name: Some("Integración".to_string()),
+        phone: None,
+        locale: None,
+    }
+}
+
+pub fn bearer(auth: &AuthSettings, user: &UserInfo) -> String {
+    format!("Bearer {}", create_jwt(auth, user).unwrap())
+}
+
+// Borra en orden las filas que dejaron los usuarios de la prueba
+pub async fn cleanup(pool: &PgPool, statements: &[&str], users: &[i32]) {
+    for sql in statements {
+        sqlx::query(sql).bind(users).execute(pool).await.unwrap();
+    }
+}
@@ -0,0 +1,100 @@
+// Carga y validación de la configuración (sin leer el entorno del proceso)
+
+use maya_digital_backend::config::{Profile, Settings};
+
+const PROD_SECRET: &str = "una-clave-larga-y-propia-para-produccion-0123456789";
+
+fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+    vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+}
+
+#[test]
+fn dev_arranca_con_los_valores_por_defecto() {
+    let settings = Settings::from_sources(Profile::Dev, None, &[]).unwrap();
+
+    assert_eq!(settings.server.port, 8080);
+    assert_eq!(settings.database.max_connections, 5);
+    assert_eq!(settings.server.cors_origins, vec!["*"]);
+}
+
+#[test]
+fn prod_rechaza_secretos_por_defecto() {
+    for secret in ["default_secret", "tu-clave-secreta-muy-segura-cambiar-en-produccion-maya2024", "corta"] {
+        let vars = env(&[
+            ("DATABASE_URL", "postgres://db/maya"),
+            ("JWT_SECRET", secret),
+            ("CORS_ORIGINS", "https://maya.example"),
+        ]);
+        let error = Settings::from_sources(Profile::Prod, None, &vars).unwrap_err();
+        assert!(error.to_string().contains("JWT_SECRET"), "{}", error);
+    }
+
+    let error = Settings::from_sources(Profile::Prod, None, &[]).unwrap_err().to_string();
+    assert!(error.contains("DATABASE_URL"), "{}", error);
+    assert!(error.contains("CORS_ORIGINS"), "{}", error);
+}
+
+#[test]
+fn prod_con_configuracion_completa_es_valida() {
+    let vars = env(&[
+        ("DATABASE_URL", "postgres://db/maya"),
+        ("JWT_SECRET", PROD_SECRET),
+        ("CORS_ORIGINS", "https://maya.example, https://admin.maya.example"),
+        ("PORT", "9000"),
+    ]);
+    let settings = Settings::from_sources(Profile::Prod, None, &vars).unwrap();
+
+    assert_eq!(settings.server.port, 9000);
+    assert_eq!(settings.server.cors_origins, vec!["https://maya.example", "https://admin.maya.example"]);
+}
+
+#[test]
+fn el_archivo_aplica_el_perfil_y_el_entorno_tiene_prioridad() {
+    let file = r#"
+        [database]
+        max_connections = 8
+
+        [auth]
+        token_lifetime_hours = 12
+
+        [prod.server]
+        cors_origins = ["https://maya.example"]
+
+        [prod.database]
+        url = "postgres://db/maya"
+        max_connections = 30
+
+        [dev.database]
+        max_connections = 2
+    "#;
+    let vars = env(&[("JWT_SECRET", PROD_SECRET), ("DATABASE_MAX_CONNECTIONS", "40")]);
+
+    let prod = Settings::from_sources(Profile::Prod, Some(file), &vars).unwrap();
+    assert_eq!(prod.database.max_connections, 40);
+    assert_eq!(prod.auth.token_lifetime_hours, 12);
+    assert_eq!(prod.server.cors_origins, vec!["https://maya.example"]);
+
+    let dev = Settings::from_sources(Profile::Dev, Some(file), &[]).unwrap();
+    assert_eq!(dev.database.max_connections, 2);
+}
+
+#[test]
+fn valores_invalidos_se_rechazan() {
+    assert!(Settings::from_sources(Profile::Dev, None, &env(&[("PORT", "ocho")])).is_err());
+    assert!(Settings::from_sources(Profile::Dev, None, &env(&[("DATABASE_MAX_CONNECTIONS", "0")])).is_err());
+    assert!(Settings::from_sources(Profile::Dev, None, &env(&[("LEGACY_API_SUNSET", "pronto")])).is_err());
+    // La retirada tiene que ser posterior al aviso de obsolescencia
+    assert!(Settings::from_sources(Profile::Dev, None, &env(&[("LEGACY_API_DEPRECATED_SINCE", "2027-05-01")])).is_err());
+    assert!(Settings::from_sources(Profile::Dev, Some("[server\nport = 1"), &[]).is_err());
+}
+
+#[test]
+fn run_migrations_se_activa_desde_el_entorno() {
+    let settings = Settings::from_sources(Profile::Dev, None, &[]).unwrap();
+    assert!(!settings.database.run_migrations);
+
+    let settings = Settings::from_sources(Profile::Dev, None, &env(&[("RUN_MIGRATIONS", "true")])).unwrap();
+    assert!(settings.database.run_migrations);
+
+    assert!(Settings::from_sources(Profile::Dev, None, &env(&[("RUN_MIGRATIONS", "quizás")])).is_err());
+}
@@ -0,0 +1,102 @@
+// Documentos de reserva contra la base de datos (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::Row;
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::UserRole,
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+// La factura se emite cuando el hotel cerró la estancia y registró el pago, en cualquier orden
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn la_factura_se_emite_al_cerrar_y_cobrar_la_estancia() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let customer = create_user(&pool, UserRole::Customer).await;
+    let booking = sqlx::query(
+        r#"
+        WITH h AS (
+            INSERT INTO hotels (owner_id, name, location, address, price, status)
+            VALUES ($1, 'Hotel Factura', 'Mérida', 'Calle 60', 900, 'approved') RETURNING id
+        )
+        INSERT INTO bookings (user_id, hotel_id, check_in, check_out, guests, rooms, total_price, status, checked_in_at)
+        SELECT $2, h.id, CURRENT_DATE, CURRENT_DATE + 2, 2, 1, 1800, 'checked_in', NOW() FROM h
+        RETURNING id, hotel_id
+        "#
+    )
+    .bind(owner.id)
+    .bind(customer.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let booking_id = booking.get::<i32, _>("id");
+    let hotel_id = booking.get::<i32, _>("hotel_id");
+
+    let owner_token = bearer(&settings.auth, &owner);
+    let customer_token = bearer(&settings.auth, &customer);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let invoice = || {
+        test::TestRequest::get()
+            .uri(&format!("/api/v1/bookings/{}/invoice.pdf", booking_id))
+            .insert_header(("Authorization", customer_token.clone()))
+            .to_request()
+    };
+    let step = |action: &str| {
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/hotels/{}/bookings/{}/{}", hotel_id, booking_id, action))
+            .insert_header(("Authorization", owner_token.clone()))
+            .to_request()
+    };
+
+    assert_eq!(test::call_service(&app, invoice()).await.status(), StatusCode::CONFLICT);
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, step("complete")).await;
+    assert_eq!(body["booking"]["status"], "completed");
+    assert_eq!(body["booking"]["payment_status"], "pending");
+    let resp = test::call_service(&app, step("complete")).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "booking.not_completable");
+
+    // Cerrada pero sin pagar todavía no se factura
+    assert_eq!(test::call_service(&app, invoice()).await.status(), StatusCode::CONFLICT);
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, step("payment")).await;
+    assert_eq!(body["booking"]["payment_status"], "paid");
+    assert_eq!(test::call_service(&app, step("payment")).await.status(), StatusCode::CONFLICT);
+
+    let resp = test::call_service(&app, invoice()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers().get("content-type").unwrap(), "application/pdf");
+    let pdf = test::read_body(resp).await;
+    assert!(pdf.starts_with(b"%PDF"));
+
+    let invoices = sqlx::query("SELECT COUNT(*) AS invoices FROM booking_invoices WHERE booking_id = $1")
+        .bind(booking_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<i64, _>("invoices");
+    assert_eq!(invoices, 1);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM notifications WHERE user_id = ANY($1)",
+            "DELETE FROM bookings WHERE user_id = ANY($1)",
+            "DELETE FROM hotels WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, customer.id],
+    )
+    .await;
+}
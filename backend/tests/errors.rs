@@ -0,0 +1,48 @@
+// Formato de las respuestas de error fuera de una petición HTTP
+
+use actix_web::{body::to_bytes, http::StatusCode, ResponseError};
+use maya_digital_backend::utils::errors::AppError;
+
+async fn body_of(error: &AppError) -> serde_json::Value {
+    let bytes = to_bytes(error.error_response().into_body()).await.unwrap();
+    serde_json::from_slice(&bytes).unwrap()
+}
+
+#[actix_web::test]
+async fn los_errores_internos_no_se_exponen() {
+    let error = AppError::DatabaseError("relation \"users\" does not exist".to_string());
+
+    assert_eq!(error.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+    let body = body_of(&error).await;
+    assert_eq!(body["code"], "internal_error");
+    assert_eq!(body["message"], "Error interno del servidor");
+    assert!(!body.to_string().contains("users"));
+}
+
+#[actix_web::test]
+async fn cada_variante_tiene_su_codigo_y_estado() {
+    let cases = [
+        (AppError::BadRequest("x".into()), StatusCode::BAD_REQUEST, "bad_request"),
+        (AppError::Unauthorized("x".into()), StatusCode::UNAUTHORIZED, "unauthorized"),
+        (AppError::Forbidden("x".into()), StatusCode::FORBIDDEN, "forbidden"),
+        (AppError::NotFound("x".into()), StatusCode::NOT_FOUND, "not_found"),
+        (AppError::Conflict("x".into()), StatusCode::CONFLICT, "conflict"),
+        (AppError::UnprocessableEntity("x".into()), StatusCode::UNPROCESSABLE_ENTITY, "unprocessable_entity"),
+    ];
+
+    for (error, status, code) in cases {
+        assert_eq!(error.status_code(), status);
+        let body = body_of(&error).await;
+        assert_eq!(body["code"], code);
+        assert_eq!(body["message"], "x");
+        // Compatibilidad con los clientes que leen `error`
+        assert_eq!(body["error"], "x");
+        assert!(body["request_id"].is_null());
+    }
+}
+
+#[test]
+fn fila_inexistente_es_404() {
+    let error = AppError::from(sqlx::Error::RowNotFound);
+    assert_eq!(error.status_code(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,219 @@
+// Catálogos de mensajes y negociación de idioma
+
+use maya_digital_backend::utils::i18n::{self, catalog_keys, negotiate, translate, Locale};
+
+// Marcadores {nombre} de una plantilla, ordenados
+fn placeholders(text: &str) -> Vec<String> {
+    let mut found: Vec<String> = text
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name.to_string()))
+        .collect();
+    found.sort();
+    found
+}
+
+#[test]
+fn los_catalogos_tienen_las_mismas_claves_y_parametros() {
+    let reference = catalog_keys(Locale::Es);
+
+    for locale in Locale::ALL {
+        assert_eq!(catalog_keys(locale), reference, "claves distintas en {}", locale.code());
+        for key in &reference {
+            assert_eq!(
+                placeholders(&translate(locale, key, &[])),
+                placeholders(&translate(Locale::Es, key, &[])),
+                "parámetros distintos en {}:{}",
+                locale.code(),
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn accept_language_respeta_la_calidad_y_el_orden() {
+    assert_eq!(i18n::from_accept_language("en-US,en;q=0.9,es;q=0.8"), Some(Locale::En));
+    assert_eq!(i18n::from_accept_language("fr;q=1.0, es;q=0.5, en;q=0.7"), Some(Locale::En));
+    assert_eq!(i18n::from_accept_language("en, es"), Some(Locale::En));
+    assert_eq!(i18n::from_accept_language("en;q=0, es-MX"), Some(Locale::Es));
+    assert_eq!(i18n::from_accept_language("fr, de"), None);
+}
+
+#[test]
+fn el_maya_yucateco_se_atiende_en_espanol() {
+    assert_eq!(Locale::parse("yua"), Some(Locale::Es));
+    assert_eq!(i18n::from_accept_language("yua, en;q=0.5"), Some(Locale::Es));
+}
+
+#[test]
+fn la_preferencia_manda_y_el_espanol_es_el_ultimo_recurso() {
+    assert_eq!(negotiate(Some("en"), Some("es")), Locale::En);
+    assert_eq!(negotiate(Some("xx"), Some("en")), Locale::En);
+    assert_eq!(negotiate(None, Some("fr")), Locale::Es);
+    assert_eq!(negotiate(None, None), Locale::Es);
+}
+
+#[test]
+fn las_plantillas_reciben_sus_parametros() {
+    let args = [("date", "01/09/2025".to_string())];
+    assert!(translate(Locale::En, "check_in.too_early", &args).contains("01/09/2025"));
+    // Una clave desconocida se devuelve tal cual
+    assert_eq!(translate(Locale::En, "no.existe", &[]), "no.existe");
+}
+
+#[test]
+fn la_cadena_de_respaldo_empieza_por_el_idioma_pedido() {
+    assert_eq!(i18n::fallback_chain(Locale::En), [Locale::En, Locale::Es]);
+    assert_eq!(i18n::fallback_chain(Locale::Es), [Locale::Es, Locale::En]);
+}
+
+#[test]
+fn el_contenido_traducido_usa_la_mejor_version_disponible() {
+    let original = "Sopa de lima".to_string();
+    let english = |locale: Locale| (locale == Locale::En).then(|| "Lime soup".to_string());
+    let nothing = |_: Locale| None::<String>;
+
+    // Traducción disponible en el idioma pedido
+    assert_eq!(
+        i18n::best_match(Locale::En, Locale::Es, Some(&original), english),
+        (Some("Lime soup".to_string()), Locale::En)
+    );
+    // Sin traducción se sirve el original
+    assert_eq!(
+        i18n::best_match(Locale::En, Locale::Es, Some(&original), nothing),
+        (Some(original.clone()), Locale::Es)
+    );
+    // Un original en inglés pedido en español sin traducción sigue en inglés
+    assert_eq!(
+        i18n::best_match(Locale::Es, Locale::En, Some(&original), nothing),
+        (Some(original.clone()), Locale::En)
+    );
+    // Si el original no tiene el campo, cualquier traducción sirve
+    assert_eq!(
+        i18n::best_match(Locale::Es, Locale::Es, None, english),
+        (Some("Lime soup".to_string()), Locale::En)
+    );
+}
+
+// Archivos .rs que compila el servidor: todo src salvo la herramienta de administración (src/bin),
+// que habla con el operador en la terminal, y los handlers que no declara handlers/mod.rs
+fn server_sources() -> Vec<(std::path::PathBuf, String)> {
+    fn walk(dir: &std::path::Path, out: &mut Vec<std::path::PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                if path.file_name().is_some_and(|name| name != "bin") {
+                    walk(&path, out);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                out.push(path);
+            }
+        }
+    }
+
+    let src = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let handlers = std::fs::read_to_string(src.join("handlers/mod.rs")).unwrap();
+    let declared: Vec<String> = handlers
+        .lines()
+        .filter_map(|line| line.strip_prefix("pub mod ")?.strip_suffix(';'))
+        .map(|name| format!("{}.rs", name))
+        .collect();
+
+    let mut files = Vec::new();
+    walk(&src, &mut files);
+    files
+        .into_iter()
+        .filter(|path| {
+            path.parent().and_then(|dir| dir.file_name()).is_none_or(|dir| dir != "handlers")
+                || path.file_name().is_some_and(|name| name == "mod.rs" || declared.iter().any(|d| name == d.as_str()))
+        })
+        .map(|path| {
+            let text = std::fs::read_to_string(&path).unwrap();
+            (path, text)
+        })
+        .collect()
+}
+
+// Literales con forma de clave ("seccion.clave") pasados a localized, t, t_args o translate,
+// o devueltos por un match que elige la clave
+fn referenced_keys(source: &str) -> Vec<String> {
+    let is_key = |text: &str| {
+        text.contains('.')
+            && text.split('.').all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+    };
+    let mut keys = Vec::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('"') {
+        let before = rest[..start].trim_end();
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('"') else { break };
+        let literal = &after[..end];
+        let call = before.rsplit_once('(').map(|(head, args)| (head, args.trim()));
+        let passed = before.ends_with("t(")
+            || before.ends_with("t_args(")
+            || before.ends_with("=>")
+            || call.is_some_and(|(head, args)| {
+                (head.ends_with("localized") || head.ends_with("translate"))
+                    && args.ends_with(',')
+                    && !args[..args.len() - 1].contains([',', '"'])
+            });
+        if passed && is_key(literal) {
+            keys.push(literal.to_string());
+        }
+        rest = &after[end + 1..];
+    }
+    keys
+}
+
+#[test]
+fn las_claves_del_codigo_existen_en_todos_los_catalogos() {
+    let mut checked = 0;
+    for (path, source) in server_sources() {
+        for key in referenced_keys(&source) {
+            checked += 1;
+            for locale in Locale::ALL {
+                assert!(
+                    catalog_keys(locale).contains(&key.as_str()),
+                    "{} usa {} que no está en {}",
+                    path.display(),
+                    key,
+                    locale.code()
+                );
+            }
+        }
+    }
+    assert!(checked > 200, "solo se encontraron {} claves; revisa referenced_keys", checked);
+}
+
+// Los errores que ve el cliente y los "message" de las respuestas salen de los catálogos
+#[test]
+fn los_handlers_no_devuelven_textos_fijos() {
+    let free_text = [
+        "AppError::BadRequest(",
+        "AppError::Unauthorized(",
+        "AppError::Forbidden(",
+        "AppError::NotFound(",
+        "AppError::Conflict(",
+        "AppError::UnprocessableEntity(",
+        "\"message\": \"",
+        "\"message\": format!",
+    ];
+    for (path, source) in server_sources() {
+        if path.ends_with("utils/errors.rs") {
+            continue;
+        }
+        for (number, line) in source.lines().enumerate() {
+            let code = line.split("//").next().unwrap_or("");
+            for pattern in free_text {
+                assert!(
+                    !code.contains(pattern),
+                    "{}:{} devuelve un texto fijo: {}",
+                    path.display(),
+                    number + 1,
+                    line.trim()
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+// Las migraciones embebidas deben poder revertirse con maya-admin migrate down
+
+use std::collections::HashSet;
+use maya_digital_backend::MIGRATOR;
+
+#[test]
+fn cada_migracion_tiene_su_reversion() {
+    let ups: HashSet<i64> = MIGRATOR
+        .iter()
+        .filter(|m| !m.migration_type.is_down_migration())
+        .map(|m| m.version)
+        .collect();
+    let downs: HashSet<i64> = MIGRATOR
+        .iter()
+        .filter(|m| m.migration_type.is_down_migration())
+        .map(|m| m.version)
+        .collect();
+
+    assert!(!ups.is_empty());
+    assert_eq!(ups, downs);
+}
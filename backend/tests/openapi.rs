@@ -0,0 +1,141 @@
+// La especificación OpenAPI debe describir exactamente las rutas registradas en lib.rs (V1_ROUTES).
+
+use std::collections::BTreeSet;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    API_V1, V1_ROUTES,
+};
+
+fn test_settings() -> Settings {
+    Settings::for_profile(Profile::Test)
+}
+
+fn lazy_pool() -> PgPool {
+    PgPoolOptions::new()
+        .connect_lazy(&test_settings().database.url)
+        .expect("DATABASE_URL inválida")
+}
+
+async fn fetch_spec() -> serde_json::Value {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/api/v1/openapi.json").to_request()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    test::read_body_json(resp).await
+}
+
+fn spec_operations(spec: &serde_json::Value) -> BTreeSet<(String, String)> {
+    spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, item)| {
+            item.as_object()
+                .unwrap()
+                .keys()
+                .map(move |method| (method.clone(), path.clone()))
+        })
+        .collect()
+}
+
+#[actix_web::test]
+async fn cada_ruta_registrada_esta_en_la_especificacion() {
+    let spec = fetch_spec().await;
+    let documented = spec_operations(&spec);
+    let registered: BTreeSet<(String, String)> = V1_ROUTES
+        .iter()
+        .map(|(method, path)| (method.to_string(), format!("{}{}", API_V1, path)))
+        .collect();
+
+    let missing: Vec<_> = registered.difference(&documented).collect();
+    assert!(missing.is_empty(), "Rutas sin documentar en src/openapi.rs: {:?}", missing);
+
+    let stale: Vec<_> = documented.difference(&registered).collect();
+    assert!(stale.is_empty(), "Operaciones documentadas que no están registradas: {:?}", stale);
+}
+
+#[actix_web::test]
+async fn los_esquemas_referenciados_existen() {
+    let spec = fetch_spec().await;
+    assert_eq!(spec["openapi"].as_str().unwrap().chars().next(), Some('3'));
+
+    let text = spec.to_string();
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+    for reference in text.split("\"#/components/schemas/").skip(1) {
+        let name = reference.split('"').next().unwrap();
+        assert!(schemas.contains_key(name), "Esquema sin definir: {}", name);
+    }
+
+    // Los cuerpos salen de los tipos de models::*
+    let register = &spec["paths"]["/api/v1/auth/register"]["post"];
+    assert_eq!(register["requestBody"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/RegisterRequest");
+    assert_eq!(register["responses"]["201"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/AuthResponse");
+    assert!(register.get("security").is_none());
+    assert!(schemas["CreateHotelRequest"]["required"].as_array().unwrap().iter().any(|f| f == "name"));
+
+    let update = &spec["paths"]["/api/v1/hotels/{id}"]["put"];
+    assert_eq!(update["parameters"][0]["name"], "id");
+    assert_eq!(update["parameters"][0]["schema"]["type"], "integer");
+    assert!(update["security"].is_array());
+}
+
+#[actix_web::test]
+async fn cada_respuesta_json_tiene_un_esquema_tipado() {
+    let spec = fetch_spec().await;
+    let schemas = spec["components"]["schemas"].as_object().unwrap();
+
+    let mut untyped = Vec::new();
+    for (path, item) in spec["paths"].as_object().unwrap() {
+        for (method, operation) in item.as_object().unwrap() {
+            for (status, response) in operation["responses"].as_object().unwrap() {
+                let Some(schema) = response["content"].get("application/json").map(|c| &c["schema"]) else {
+                    continue;
+                };
+                if !status.starts_with('2') {
+                    continue;
+                }
+                // La propia especificación se describe con el esquema oficial de OpenAPI
+                if path == "/api/v1/openapi.json" {
+                    assert!(schema["$ref"].as_str().unwrap().starts_with("https://spec.openapis.org/oas/"));
+                    continue;
+                }
+                let typed = schema["$ref"]
+                    .as_str()
+                    .and_then(|r| r.strip_prefix("#/components/schemas/"))
+                    .and_then(|name| schemas.get(name))
+                    .is_some_and(|component| {
+                        component.get("properties").is_some() || component.get("allOf").is_some() || component.get("oneOf").is_some()
+                    });
+                if !typed {
+                    untyped.push(format!("{} {} {}", method, path, status));
+                }
+            }
+        }
+    }
+    assert!(untyped.is_empty(), "Respuestas JSON sin tipo: {:?}", untyped);
+
+    // Los listados paginados documentan el sobre con el tipo de sus elementos
+    let hotels = &spec["paths"]["/api/v1/hotels/public"]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(hotels["$ref"], "#/components/schemas/PublicHotelPage");
+    let page = &schemas["PublicHotelPage"];
+    assert_eq!(page["properties"]["data"]["items"]["$ref"], "#/components/schemas/PublicHotel");
+    assert!(page["properties"]["next_cursor"].is_object());
+
+    let me = &spec["paths"]["/api/v1/auth/me"]["get"]["responses"]["200"]["content"]["application/json"]["schema"];
+    assert_eq!(me["$ref"], "#/components/schemas/MeResponse");
+}
+
+#[actix_web::test]
+async fn la_documentacion_interactiva_se_sirve() {
+    let app = test::init_service(build_app(lazy_pool(), test_settings())).await;
+
+    let resp = test::call_service(&app, test::TestRequest::get().uri("/api/docs/").to_request()).await;
+
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = test::read_body(resp).await;
+    assert!(String::from_utf8_lossy(&body).contains("swagger-ui"));
+}
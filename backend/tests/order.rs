@@ -0,0 +1,203 @@
+// Carrito y pedidos de artesanías contra la base de datos (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::{PgPool, Row};
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::{UserInfo, UserRole},
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+// Taller aprobado con una variante en existencia y un cliente
+struct Shop {
+    owner: UserInfo,
+    customer: UserInfo,
+    business_id: i32,
+    variant_id: i32,
+}
+
+async fn create_shop(pool: &PgPool) -> Shop {
+    let owner = create_user(pool, UserRole::Customer).await;
+    let customer = create_user(pool, UserRole::Customer).await;
+    let business_id = sqlx::query(
+        "INSERT INTO businesses (owner_id, business_type, name, location, address, status) VALUES ($1, 'artisan', 'Taller', 'Mérida', 'Calle 60', 'approved') RETURNING id"
+    )
+    .bind(owner.id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .get::<i32, _>("id");
+    let product_id = sqlx::query("INSERT INTO products (business_id, name, category) VALUES ($1, 'Hamaca', 'textil') RETURNING id")
+        .bind(business_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get::<i32, _>("id");
+    let variant_id = sqlx::query("INSERT INTO product_variants (product_id, name, price, stock) VALUES ($1, 'Matrimonial', 1200, 100) RETURNING id")
+        .bind(product_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get::<i32, _>("id");
+
+    Shop { owner, customer, business_id, variant_id }
+}
+
+async fn remove_shop(pool: &PgPool, shop: &Shop) {
+    cleanup(
+        pool,
+        &[
+            "DELETE FROM notifications WHERE user_id = ANY($1)",
+            "DELETE FROM order_items WHERE order_id IN (SELECT id FROM orders WHERE user_id = ANY($1))",
+            "DELETE FROM orders WHERE user_id = ANY($1)",
+            "DELETE FROM cart_items WHERE user_id = ANY($1)",
+            "DELETE FROM businesses WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[shop.owner.id, shop.customer.id],
+    )
+    .await;
+}
+
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn el_carrito_no_pasa_del_maximo_por_variante() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let shop = create_shop(&pool).await;
+    let token = bearer(&settings.auth, &shop.customer);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let add = |quantity: i32| {
+        test::TestRequest::post()
+            .uri("/api/v1/cart/items")
+            .insert_header(("Authorization", token.clone()))
+            .set_json(serde_json::json!({ "variant_id": shop.variant_id, "quantity": quantity }))
+            .to_request()
+    };
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, add(30)).await;
+    assert_eq!(body["quantity"], 30);
+    let body: serde_json::Value = test::call_and_read_body_json(&app, add(20)).await;
+    assert_eq!(body["quantity"], 50);
+
+    let resp = test::call_service(&app, add(1)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "order.cart_quantity_limit");
+
+    let quantity = sqlx::query("SELECT quantity FROM cart_items WHERE user_id = $1")
+        .bind(shop.customer.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<i32, _>("quantity");
+    assert_eq!(quantity, 50);
+
+    remove_shop(&pool, &shop).await;
+}
+
+// El taller confirma, cobra y entrega; cada paso exige el anterior y avisa al cliente
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn el_taller_lleva_el_pedido_hasta_entregado() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let shop = create_shop(&pool).await;
+    let customer = bearer(&settings.auth, &shop.customer);
+    let owner = bearer(&settings.auth, &shop.owner);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/cart/items")
+        .insert_header(("Authorization", customer.clone()))
+        .set_json(serde_json::json!({ "variant_id": shop.variant_id, "quantity": 2 }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+    let req = test::TestRequest::post()
+        .uri("/api/v1/cart/checkout")
+        .insert_header(("Authorization", customer.clone()))
+        .set_json(serde_json::json!({ "shipping_address": "Calle 60 por 55, Centro" }))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    let order_id = body["order"]["id"].as_i64().unwrap();
+
+    let step = |token: &str, action: &str| {
+        test::TestRequest::put()
+            .uri(&format!("/api/v1/businesses/{}/orders/{}/{}", shop.business_id, order_id, action))
+            .insert_header(("Authorization", token.to_string()))
+            .to_request()
+    };
+
+    assert_eq!(test::call_service(&app, step(&customer, "confirm")).await.status(), StatusCode::FORBIDDEN);
+
+    let resp = test::call_service(&app, step(&owner, "complete")).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "order.not_completable");
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, step(&owner, "confirm")).await;
+    assert_eq!(body["order"]["status"], "confirmed");
+    assert_eq!(body["order"]["payment_status"], "pending");
+    assert_eq!(test::call_service(&app, step(&owner, "confirm")).await.status(), StatusCode::CONFLICT);
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, step(&owner, "payment")).await;
+    assert_eq!(body["order"]["payment_status"], "paid");
+
+    // Un pedido pagado ya no lo cancela el cliente
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/v1/orders/{}/cancel", order_id))
+        .insert_header(("Authorization", customer.clone()))
+        .set_json(serde_json::json!({}))
+        .to_request();
+    assert!(!test::call_service(&app, req).await.status().is_success());
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, step(&owner, "complete")).await;
+    assert_eq!(body["order"]["status"], "completed");
+
+    let kinds: Vec<String> = sqlx::query("SELECT kind FROM notifications WHERE user_id = $1 ORDER BY id")
+        .bind(shop.customer.id)
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.get::<String, _>("kind"))
+        .collect();
+    assert_eq!(kinds, ["order_confirmed", "order_paid", "order_completed"]);
+
+    remove_shop(&pool, &shop).await;
+}
+
+// Un pedido es de un solo taller, así que el carrito no acepta productos de otro
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn el_carrito_no_mezcla_talleres() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let shop = create_shop(&pool).await;
+    let other = create_shop(&pool).await;
+    let token = bearer(&settings.auth, &shop.customer);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let add = |variant_id: i32| {
+        test::TestRequest::post()
+            .uri("/api/v1/cart/items")
+            .insert_header(("Authorization", token.clone()))
+            .set_json(serde_json::json!({ "variant_id": variant_id, "quantity": 1 }))
+            .to_request()
+    };
+
+    assert_eq!(test::call_service(&app, add(shop.variant_id)).await.status(), StatusCode::OK);
+    let resp = test::call_service(&app, add(other.variant_id)).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "order.cart_single_business");
+
+    remove_shop(&pool, &shop).await;
+    remove_shop(&pool, &other).await;
+}
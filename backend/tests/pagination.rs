@@ -0,0 +1,321 @@
+// Paginación por cursor: lectura de parámetros, SQL generado y errores del extractor
+
+mod common;
+
+use actix_web::{
+    http::StatusCode,
+    test::{call_service, init_service, read_body_json, TestRequest},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use sqlx::{postgres::PgPoolOptions, PgPool, Row};
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::{UserInfo, UserRole},
+    utils::{
+        errors::AppError,
+        jwt::create_jwt,
+        pagination::{Filter, Kind, Listing, Page, SortField, DEFAULT_LIMIT},
+    },
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+struct Rooms;
+
+impl Listing for Rooms {
+    const KEY: &'static str = "r.id";
+    const SORTS: &'static [SortField] = &[
+        SortField::new("created_at", "r.created_at", Kind::Timestamp),
+        SortField::new("price", "r.price", Kind::Numeric),
+    ];
+    const DEFAULT_SORT: &'static str = "-created_at";
+    const FILTERS: &'static [Filter] = &[
+        Filter::any_of("status", "r.status", Kind::Text),
+        Filter::at_least("from", "r.day", Kind::Date),
+    ];
+    const MAX_LIMIT: i64 = 50;
+}
+
+fn cursor(raw: &str) -> String {
+    URL_SAFE_NO_PAD.encode(raw)
+}
+
+fn error_code(result: Result<Page<Rooms>, AppError>) -> String {
+    match result {
+        Ok(_) => panic!("se esperaba un error"),
+        Err(e) => e.code().to_string(),
+    }
+}
+
+#[test]
+fn sin_parametros_usa_el_orden_y_el_limite_por_defecto() {
+    let page = Page::<Rooms>::from_query("").unwrap();
+
+    assert_eq!(page.limit, DEFAULT_LIMIT);
+    assert_eq!(page.sort(), "-created_at");
+    assert_eq!(page.conditions(2), "");
+    assert_eq!(page.order_and_limit(), format!("ORDER BY r.created_at DESC, r.id DESC LIMIT {}", DEFAULT_LIMIT + 1));
+    assert_eq!(page.columns(), "(r.created_at)::text AS page_sort, (r.id)::bigint AS page_key");
+}
+
+#[test]
+fn el_limite_se_acota_al_maximo_del_listado() {
+    assert_eq!(Page::<Rooms>::from_query("limit=500").unwrap().limit, 50);
+    assert_eq!(Page::<Rooms>::from_query("limit=0").unwrap().limit, 1);
+    assert_eq!(error_code(Page::<Rooms>::from_query("limit=diez")), "pagination.invalid_limit");
+}
+
+#[test]
+fn filtros_y_cursor_se_numeran_despues_de_los_parametros_propios() {
+    let after = cursor("price\n42\n1200.50");
+    let page = Page::<Rooms>::from_query(&format!("sort=price&status=libre,%20ocupado&from=2025-01-01&cursor={}", after)).unwrap();
+
+    assert_eq!(
+        page.conditions(2),
+        " AND (r.status)::text = ANY($2) AND r.day >= $3::date AND (r.price, r.id) > ($4::numeric, $5)"
+    );
+    assert_eq!(page.order_and_limit(), format!("ORDER BY r.price ASC, r.id ASC LIMIT {}", DEFAULT_LIMIT + 1));
+}
+
+#[test]
+fn el_cursor_conserva_su_orden_si_no_se_indica_otro() {
+    let after = cursor("-price\n7\n99");
+    let page = Page::<Rooms>::from_query(&format!("cursor={}", after)).unwrap();
+
+    assert_eq!(page.sort(), "-price");
+    assert_eq!(page.conditions(1), " AND (r.price, r.id) < ($1::numeric, $2)");
+}
+
+#[test]
+fn se_rechazan_ordenes_cursores_y_filtros_invalidos() {
+    assert_eq!(error_code(Page::<Rooms>::from_query("sort=rating")), "pagination.invalid_sort");
+    assert_eq!(error_code(Page::<Rooms>::from_query("cursor=%%%")), "pagination.invalid_cursor");
+    // Un cursor de otro orden no sirve para continuar este
+    let by_price = cursor("price\n7\n99");
+    assert_eq!(error_code(Page::<Rooms>::from_query(&format!("sort=-created_at&cursor={}", by_price))), "pagination.invalid_cursor");
+    let bad_value = cursor("price\n7\nbarato");
+    assert_eq!(error_code(Page::<Rooms>::from_query(&format!("cursor={}", bad_value))), "pagination.invalid_cursor");
+    assert_eq!(error_code(Page::<Rooms>::from_query("from=ayer")), "pagination.invalid_filter");
+
+    // Los timestamps del cursor llegan con el formato de texto de Postgres
+    let by_date = cursor("-created_at\n3\n2025-08-19 10:00:00.123456+00");
+    assert!(Page::<Rooms>::from_query(&format!("cursor={}", by_date)).is_ok());
+}
+
+#[actix_web::test]
+async fn los_listados_validan_la_paginacion_antes_de_consultar() {
+    let settings = Settings::for_profile(Profile::Test);
+    let user = UserInfo {
+        id: 7,
+        email: "ana@example.com".to_string(),
+        role: UserRole::Customer,
+        first_name: Some("Ana".to_string()),
+        last_name: None,
+        phone: None,
+        locale: None,
+    };
+    let token = create_jwt(&settings.auth, &user).unwrap();
+    let pool: PgPool = PgPoolOptions::new().connect_lazy(&settings.database.url).unwrap();
+    let app = init_service(build_app(pool, settings)).await;
+
+    for uri in [
+        "/api/v1/hotels/my?sort=rating",
+        "/api/v1/bookings/my?limit=muchos",
+        "/api/v1/businesses/my?cursor=no-es-un-cursor",
+        "/api/v1/hotels/1/bookings?check_in_from=pronto",
+        "/api/v1/hotels/public?sort=stars",
+        "/api/v1/hotels/1/reviews?min_rating=alta",
+        "/api/v1/businesses?cursor=no-es-un-cursor",
+        "/api/v1/products?limit=todos",
+        "/api/v1/orders/my?sort=price",
+        "/api/v1/transport/bookings/my?limit=muchos",
+        "/api/v1/reviews/my?sort=rating",
+        "/api/v1/admin/moderation?sort=oldest",
+    ] {
+        let req = TestRequest::get()
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let resp = call_service(&app, req).await;
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST, "{}", uri);
+        let body: serde_json::Value = read_body_json(resp).await;
+        assert!(body["code"].as_str().unwrap().starts_with("pagination."), "{}: {}", uri, body);
+    }
+}
+
+// Un directorio público y la cola de moderación se recorren con next_cursor hasta la última página
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn los_listados_publicos_y_la_moderacion_se_recorren_por_cursor() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let admin = create_user(&pool, UserRole::Admin).await;
+    // Un lugar único para que los filtros solo vean las filas de esta prueba
+    let place = format!("Pueblo {}", uuid::Uuid::new_v4().simple());
+
+    for name in ["Taller Uno", "Taller Dos", "Taller Tres"] {
+        sqlx::query(
+            "INSERT INTO businesses (owner_id, business_type, name, location, address, status) VALUES ($1, 'artisan', $2, $3, 'Calle 1', 'approved')"
+        )
+        .bind(owner.id)
+        .bind(name)
+        .bind(&place)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    // En la cola un hotel y un negocio pendientes pueden tener el mismo id
+    sqlx::query(
+        "INSERT INTO businesses (owner_id, business_type, name, location, address, status) VALUES ($1, 'artisan', 'Taller Pendiente', $2, 'Calle 1', 'pending')"
+    )
+    .bind(owner.id)
+    .bind(&place)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sqlx::query(
+        "INSERT INTO hotels (owner_id, name, location, address, price, status) VALUES ($1, 'Hotel Pendiente', $2, 'Calle 1', 900, 'pending')"
+    )
+    .bind(owner.id)
+    .bind(&place)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let admin_token = bearer(&settings.auth, &admin);
+    let app = init_service(build_app(pool.clone(), settings)).await;
+
+    let walk = |first: String| {
+        let app = &app;
+        let admin_token = &admin_token;
+        async move {
+            let mut pages = Vec::new();
+            let mut uri = first.clone();
+            loop {
+                let req = TestRequest::get()
+                    .uri(&uri)
+                    .insert_header(("Authorization", admin_token.clone()))
+                    .to_request();
+                let resp = call_service(app, req).await;
+                assert_eq!(resp.status(), StatusCode::OK, "{}", uri);
+                let body: serde_json::Value = read_body_json(resp).await;
+                pages.push(body["data"].as_array().unwrap().clone());
+                match body["next_cursor"].as_str() {
+                    Some(cursor) => uri = format!("{}&cursor={}", first, cursor),
+                    None => break pages,
+                }
+            }
+        }
+    };
+    let encoded = place.replace(' ', "%20");
+
+    let pages = walk(format!("/api/v1/businesses?location={}&limit=2&sort=name", encoded)).await;
+    let names: Vec<Vec<&str>> = pages
+        .iter()
+        .map(|page| page.iter().map(|b| b["name"].as_str().unwrap()).collect())
+        .collect();
+    assert_eq!(names, vec![vec!["Taller Dos", "Taller Tres"], vec!["Taller Uno"]]);
+
+    let pages = walk(format!("/api/v1/admin/moderation?location={}&limit=1", encoded)).await;
+    let mut types: Vec<&str> = pages.iter().flatten().map(|item| item["type"].as_str().unwrap()).collect();
+    types.sort_unstable();
+    assert_eq!(pages.len(), 2);
+    assert_eq!(types, vec!["business", "hotel"]);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM hotels WHERE owner_id = ANY($1)",
+            "DELETE FROM businesses WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, admin.id],
+    )
+    .await;
+}
+
+// Todos los listados paginados responden con el mismo sobre, también al pedir un orden que no es el
+// de por defecto
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn todos_los_listados_responden_con_el_sobre_de_pagina() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let admin = create_user(&pool, UserRole::Admin).await;
+    let ids = sqlx::query(
+        r#"
+        WITH h AS (
+            INSERT INTO hotels (owner_id, name, location, address, price, status)
+            VALUES ($1, 'Hotel Listado', 'Mérida', 'Calle 60', 900, 'approved') RETURNING id
+        ), b AS (
+            INSERT INTO businesses (owner_id, business_type, name, location, address, status)
+            VALUES ($1, 'transport', 'Traslados Listado', 'Mérida', 'Calle 60', 'approved') RETURNING id
+        ), r AS (
+            INSERT INTO transport_routes (business_id, origin, destination, distance_km, duration_minutes)
+            SELECT id, 'Mérida', 'Izamal', 70, 80 FROM b RETURNING id
+        )
+        SELECT h.id AS hotel_id, b.id AS business_id, r.id AS route_id FROM h, b, r
+        "#
+    )
+    .bind(owner.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let hotel_id = ids.get::<i32, _>("hotel_id");
+    let business_id = ids.get::<i32, _>("business_id");
+    let route_id = ids.get::<i32, _>("route_id");
+
+    let token = bearer(&settings.auth, &admin);
+    let app = init_service(build_app(pool.clone(), settings)).await;
+
+    for uri in [
+        "/api/v1/hotels/public?sort=-rating".to_string(),
+        "/api/v1/hotels/my?sort=name".to_string(),
+        format!("/api/v1/hotels/{}/reviews?sort=-rating", hotel_id),
+        format!("/api/v1/hotels/{}/bookings?sort=created_at", hotel_id),
+        "/api/v1/businesses?sort=name&open_now=true".to_string(),
+        "/api/v1/businesses/my".to_string(),
+        format!("/api/v1/businesses/{}/reviews?min_rating=3", business_id),
+        format!("/api/v1/businesses/{}/orders?status=pending", business_id),
+        format!("/api/v1/businesses/{}/transport/bookings?sort=-created_at", business_id),
+        "/api/v1/products?sort=name&q=hamaca".to_string(),
+        "/api/v1/bookings/my".to_string(),
+        "/api/v1/orders/my?sort=-total_price".to_string(),
+        "/api/v1/transport/routes?sort=duration".to_string(),
+        format!("/api/v1/transport/routes/{}/trips?sort=price", route_id),
+        "/api/v1/transport/bookings/my".to_string(),
+        "/api/v1/reviews/my?status=approved".to_string(),
+        "/api/v1/admin/hotels?sort=name".to_string(),
+        "/api/v1/admin/hotels/pending?sort=name".to_string(),
+        "/api/v1/admin/businesses/pending?business_type=artisan".to_string(),
+        "/api/v1/admin/moderation?sort=-submitted_at&type=review".to_string(),
+    ] {
+        let req = TestRequest::get()
+            .uri(&uri)
+            .insert_header(("Authorization", token.clone()))
+            .to_request();
+        let resp = call_service(&app, req).await;
+        let status = resp.status();
+        let body: serde_json::Value = read_body_json(resp).await;
+
+        assert_eq!(status, StatusCode::OK, "{}: {}", uri, body);
+        assert!(body["data"].is_array(), "{}: {}", uri, body);
+        assert!(body.get("next_cursor").is_some() && body["limit"].is_i64() && body["sort"].is_string(), "{}: {}", uri, body);
+    }
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM hotels WHERE owner_id = ANY($1)",
+            "DELETE FROM businesses WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, admin.id],
+    )
+    .await;
+}
@@ -0,0 +1,118 @@
+// Validación del catálogo de productos contra la base de datos (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::{PgPool, Row};
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::{UserInfo, UserRole},
+};
+
+use common::{bearer, create_user, test_pool};
+
+// Artesano con un negocio aprobado y un producto
+struct Artisan {
+    user: UserInfo,
+    business_id: i32,
+    product_id: i32,
+}
+
+async fn create_artisan(pool: &PgPool) -> Artisan {
+    let user = create_user(pool, UserRole::Customer).await;
+    let business_id = sqlx::query(
+        "INSERT INTO businesses (owner_id, business_type, name, location, address, status) VALUES ($1, 'artisan', 'Taller', 'Mérida', 'Calle 60', 'approved') RETURNING id"
+    )
+    .bind(user.id)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .get::<i32, _>("id");
+    let product_id = sqlx::query("INSERT INTO products (business_id, name, category) VALUES ($1, 'Hamaca', 'textil') RETURNING id")
+        .bind(business_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+        .get::<i32, _>("id");
+
+    Artisan { user, business_id, product_id }
+}
+
+async fn remove_artisan(pool: &PgPool, artisan: &Artisan) {
+    // Productos, variantes e imágenes se borran en cascada con el negocio
+    sqlx::query("DELETE FROM businesses WHERE id = $1").bind(artisan.business_id).execute(pool).await.unwrap();
+    sqlx::query("DELETE FROM users WHERE id = $1").bind(artisan.user.id).execute(pool).await.unwrap();
+}
+
+fn fields(body: &serde_json::Value) -> Vec<String> {
+    body["details"]
+        .as_array()
+        .unwrap_or(&Vec::new())
+        .iter()
+        .map(|detail| detail["field"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn una_variante_invalida_devuelve_el_detalle_por_campo() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let artisan = create_artisan(&pool).await;
+    let token = bearer(&settings.auth, &artisan.user);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/v1/products/{}/variants", artisan.product_id))
+        .insert_header(("Authorization", token))
+        .set_json(serde_json::json!({ "name": "Grande", "price": 0, "stock": -1 }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(fields(&body), ["price", "stock"]);
+
+    remove_artisan(&pool, &artisan).await;
+}
+
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn una_imagen_con_url_invalida_se_rechaza() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let artisan = create_artisan(&pool).await;
+    let token = bearer(&settings.auth, &artisan.user);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let req = test::TestRequest::post()
+        .uri(&format!("/api/v1/businesses/{}/products", artisan.business_id))
+        .insert_header(("Authorization", token))
+        .set_json(serde_json::json!({
+            "name": "Rebozo",
+            "category": "textil",
+            "variants": [{ "name": "Único", "price": 450, "stock": 3 }],
+            "images": [
+                { "image_url": "https://cdn.example.com/rebozo.jpg", "display_order": 0 },
+                { "image_url": "no es una url", "display_order": 1 }
+            ]
+        }))
+        .to_request();
+    let resp = test::call_service(&app, req).await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(fields(&body), ["images[1].image_url"]);
+    let created = sqlx::query("SELECT COUNT(*) AS created FROM products WHERE business_id = $1 AND name = 'Rebozo'")
+        .bind(artisan.business_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<i64, _>("created");
+    assert_eq!(created, 0);
+
+    remove_artisan(&pool, &artisan).await;
+}
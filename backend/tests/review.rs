@@ -0,0 +1,168 @@
+// Reseñas contra la base de datos: solo se reseña lo que el negocio ya cerró (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::Row;
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::UserRole,
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+fn review(token: &str, target: serde_json::Value) -> test::TestRequest {
+    let mut body = serde_json::json!({ "overall_rating": 5, "comment": "Todo estuvo excelente, volvería." });
+    body.as_object_mut().unwrap().extend(target.as_object().unwrap().clone());
+    test::TestRequest::post()
+        .uri("/api/v1/reviews")
+        .insert_header(("Authorization", token.to_string()))
+        .set_json(body)
+}
+
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn la_estancia_se_resena_cuando_el_hotel_la_cierra() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let customer = create_user(&pool, UserRole::Customer).await;
+    let booking = sqlx::query(
+        r#"
+        WITH h AS (
+            INSERT INTO hotels (owner_id, name, location, address, price, status)
+            VALUES ($1, 'Hotel Reseña', 'Mérida', 'Calle 60', 900, 'approved') RETURNING id
+        )
+        INSERT INTO bookings (user_id, hotel_id, check_in, check_out, guests, rooms, total_price, status, checked_in_at)
+        SELECT $2, h.id, CURRENT_DATE, CURRENT_DATE + 2, 2, 1, 1800, 'checked_in', NOW() FROM h
+        RETURNING id, hotel_id
+        "#
+    )
+    .bind(owner.id)
+    .bind(customer.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let booking_id = booking.get::<i32, _>("id");
+    let hotel_id = booking.get::<i32, _>("hotel_id");
+
+    let owner_token = bearer(&settings.auth, &owner);
+    let customer_token = bearer(&settings.auth, &customer);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+    let target = serde_json::json!({ "booking_id": booking_id });
+
+    // El huésped sigue hospedado
+    let resp = test::call_service(&app, review(&customer_token, target.clone()).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/v1/hotels/{}/bookings/{}/complete", hotel_id, booking_id))
+        .insert_header(("Authorization", owner_token))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+    let resp = test::call_service(&app, review(&customer_token, target.clone()).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let body: serde_json::Value = test::read_body_json(resp).await;
+    assert_eq!(body["review"]["status"], "pending");
+
+    let resp = test::call_service(&app, review(&customer_token, target).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM reviews WHERE user_id = ANY($1)",
+            "DELETE FROM notifications WHERE user_id = ANY($1)",
+            "DELETE FROM bookings WHERE user_id = ANY($1)",
+            "DELETE FROM hotels WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, customer.id],
+    )
+    .await;
+}
+
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn el_pedido_se_resena_cuando_el_taller_lo_entrega() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::Customer).await;
+    let customer = create_user(&pool, UserRole::Customer).await;
+    let shop = sqlx::query(
+        r#"
+        WITH b AS (
+            INSERT INTO businesses (owner_id, business_type, name, location, address, status)
+            VALUES ($1, 'artisan', 'Taller Reseña', 'Mérida', 'Calle 60', 'approved') RETURNING id
+        ), p AS (
+            INSERT INTO products (business_id, name, category) SELECT id, 'Huipil', 'textil' FROM b RETURNING id
+        )
+        INSERT INTO product_variants (product_id, name, price, stock)
+        SELECT p.id, 'Mediano', 800, 10 FROM p
+        RETURNING id, (SELECT id FROM b) AS business_id
+        "#
+    )
+    .bind(owner.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let variant_id = shop.get::<i32, _>("id");
+    let business_id = shop.get::<i32, _>("business_id");
+
+    let owner_token = bearer(&settings.auth, &owner);
+    let customer_token = bearer(&settings.auth, &customer);
+    let app = test::init_service(build_app(pool.clone(), settings)).await;
+
+    let req = test::TestRequest::post()
+        .uri("/api/v1/cart/items")
+        .insert_header(("Authorization", customer_token.clone()))
+        .set_json(serde_json::json!({ "variant_id": variant_id, "quantity": 1 }))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+    let req = test::TestRequest::post()
+        .uri("/api/v1/cart/checkout")
+        .insert_header(("Authorization", customer_token.clone()))
+        .set_json(serde_json::json!({ "shipping_address": "Calle 60 por 55, Centro" }))
+        .to_request();
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    let order_id = body["order"]["id"].as_i64().unwrap();
+    let target = serde_json::json!({ "order_id": order_id, "business_id": business_id });
+
+    for action in ["confirm", "payment"] {
+        let req = test::TestRequest::put()
+            .uri(&format!("/api/v1/businesses/{}/orders/{}/{}", business_id, order_id, action))
+            .insert_header(("Authorization", owner_token.clone()))
+            .to_request();
+        assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK, "{}", action);
+    }
+
+    // Pagado pero aún sin entregar
+    let resp = test::call_service(&app, review(&customer_token, target.clone()).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let req = test::TestRequest::put()
+        .uri(&format!("/api/v1/businesses/{}/orders/{}/complete", business_id, order_id))
+        .insert_header(("Authorization", owner_token))
+        .to_request();
+    assert_eq!(test::call_service(&app, req).await.status(), StatusCode::OK);
+
+    let resp = test::call_service(&app, review(&customer_token, target).to_request()).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM reviews WHERE user_id = ANY($1)",
+            "DELETE FROM notifications WHERE user_id = ANY($1)",
+            "DELETE FROM order_items WHERE order_id IN (SELECT id FROM orders WHERE user_id = ANY($1))",
+            "DELETE FROM orders WHERE user_id = ANY($1)",
+            "DELETE FROM businesses WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, customer.id],
+    )
+    .await;
+}
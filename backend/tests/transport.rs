@@ -0,0 +1,96 @@
+// Reservas de traslados contra la base de datos (ver tests/common).
+
+mod common;
+
+use actix_web::{http::StatusCode, test};
+use sqlx::Row;
+
+use maya_digital_backend::{
+    build_app,
+    config::{Profile, Settings},
+    models::UserRole,
+};
+
+use common::{bearer, cleanup, create_user, test_pool};
+
+// Mientras update_vehicle tiene bloqueado el vehículo, la reserva de una salida espera y después
+// ve la capacidad nueva en lugar de vender asientos que ya no existen
+#[actix_web::test]
+#[ignore = "necesita DATABASE_URL"]
+async fn la_reserva_espera_a_que_termine_el_cambio_de_capacidad() {
+    let pool = test_pool().await;
+    let settings = Settings::for_profile(Profile::Test);
+    let owner = create_user(&pool, UserRole::HotelOwner).await;
+    let customer = create_user(&pool, UserRole::Customer).await;
+
+    let trip = sqlx::query(
+        r#"
+        WITH b AS (
+            INSERT INTO businesses (owner_id, business_type, name, location, address, status)
+            VALUES ($1, 'transport', 'Traslados Prueba', 'Mérida', 'Calle 60', 'approved') RETURNING id
+        ), r AS (
+            INSERT INTO transport_routes (business_id, origin, destination, distance_km, duration_minutes)
+            SELECT id, 'Mérida', 'Progreso', 36, 45 FROM b RETURNING id
+        ), v AS (
+            INSERT INTO transport_vehicles (business_id, name, vehicle_type, seat_capacity)
+            SELECT id, 'Van', 'van', 3 FROM b RETURNING id
+        )
+        INSERT INTO transport_trips (route_id, vehicle_id, trip_type, departure_at, arrival_at, price_per_seat)
+        SELECT r.id, v.id, 'scheduled', NOW() + INTERVAL '2 days', NOW() + INTERVAL '2 days 45 minutes', 150
+        FROM r, v
+        RETURNING id, vehicle_id
+        "#
+    )
+    .bind(owner.id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let trip_id = trip.get::<i32, _>("id");
+    let vehicle_id = trip.get::<i32, _>("vehicle_id");
+
+    let app = test::init_service(build_app(pool.clone(), settings.clone())).await;
+
+    // Un cambio de capacidad a medio camino: vehículo bloqueado y capacidad ya reducida a 1
+    let mut update = pool.begin().await.unwrap();
+    sqlx::query("SELECT id FROM transport_vehicles WHERE id = $1 FOR UPDATE")
+        .bind(vehicle_id)
+        .execute(&mut *update)
+        .await
+        .unwrap();
+    sqlx::query("UPDATE transport_vehicles SET seat_capacity = 1 WHERE id = $1")
+        .bind(vehicle_id)
+        .execute(&mut *update)
+        .await
+        .unwrap();
+
+    let booking = test::TestRequest::post()
+        .uri("/api/v1/transport/bookings")
+        .insert_header(("Authorization", bearer(&settings.auth, &customer)))
+        .set_json(serde_json::json!({ "trip_id": trip_id, "seats": 2 }))
+        .to_request();
+    let finish_update = async {
+        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+        update.commit().await.unwrap();
+    };
+    let (resp, _) = futures::join!(test::call_service(&app, booking), finish_update);
+
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let booked = sqlx::query("SELECT COUNT(*) AS booked FROM transport_bookings WHERE trip_id = $1")
+        .bind(trip_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .get::<i64, _>("booked");
+    assert_eq!(booked, 0);
+
+    cleanup(
+        &pool,
+        &[
+            "DELETE FROM transport_bookings WHERE user_id = ANY($1)",
+            "DELETE FROM businesses WHERE owner_id = ANY($1)",
+            "DELETE FROM users WHERE id = ANY($1)",
+        ],
+        &[owner.id, customer.id],
+    )
+    .await;
+}
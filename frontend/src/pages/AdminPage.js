@@ -105,13 +105,20 @@ function AdminPage() {
 
     // Función para manejar la aprobación/rechazo de HOTELES
     const handleHotelDecision = async (hotelId, action) => {
+        let body;
+        if (action === 'reject') {
+            const reason = window.prompt('Motivo del rechazo (el propietario lo verá):');
+            if (!reason || !reason.trim()) return;
+            body = JSON.stringify({ reason: reason.trim() });
+        }
+
         const originalPendingHotels = [...pendingHotels];
         setPendingHotels(current => current.filter(h => h.id !== hotelId));
         setProcessing(hotelId);
 
         try {
             const endpoint = `hotels/${hotelId}/${action}`;
-            await fetchAdminData(endpoint, { method: 'PUT' });
+            await fetchAdminData(endpoint, { method: 'PUT', body });
             console.log(`✅ Hotel ${hotelId} ${action === 'approve' ? 'aprobado' : 'rechazado'}`);
             await loadAllData(); // Recargamos todo para mantener consistencia
         } catch (err) {
@@ -1,159 +0,0 @@
--- init.sql - Script de inicialización para Docker
--- Se ejecuta automáticamente cuando se crea el contenedor de PostgreSQL
-
--- 1. Crear tipos enum
-DO $$ BEGIN
-    CREATE TYPE user_role AS ENUM ('admin', 'hotel_owner', 'business_owner', 'customer');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
-
-DO $$ BEGIN
-    CREATE TYPE hotel_status AS ENUM ('pending', 'approved', 'rejected');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
-
-DO $$ BEGIN
-    CREATE TYPE business_status AS ENUM ('pending', 'approved', 'rejected', 'suspended');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
-
-DO $$ BEGIN
-    CREATE TYPE booking_status AS ENUM ('pending', 'confirmed', 'cancelled', 'completed');
-EXCEPTION
-    WHEN duplicate_object THEN null;
-END $$;
-
--- 2. Crear tabla users
-CREATE TABLE IF NOT EXISTS users (
-    id SERIAL PRIMARY KEY,
-    email VARCHAR(255) UNIQUE NOT NULL,
-    password_hash VARCHAR(255) NOT NULL,
-    first_name VARCHAR(100) NOT NULL,
-    last_name VARCHAR(100) NOT NULL,
-    phone VARCHAR(20),
-    role user_role DEFAULT 'customer',
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
-    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
-);
-
--- 3. Crear tabla hotels
-CREATE TABLE IF NOT EXISTS hotels (
-    id SERIAL PRIMARY KEY,
-    owner_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    name VARCHAR(255) NOT NULL,
-    description TEXT,
-    location VARCHAR(255) NOT NULL,
-    address TEXT NOT NULL,
-    price DECIMAL(10,2) NOT NULL CHECK (price > 0),
-    image_url TEXT,
-    phone VARCHAR(20),
-    email VARCHAR(255),
-    website TEXT,
-    rooms_available INTEGER DEFAULT 1 CHECK (rooms_available > 0),
-    rating DECIMAL(3,2) DEFAULT 0 CHECK (rating >= 0 AND rating <= 5),
-    status hotel_status DEFAULT 'pending',
-    admin_notes TEXT,
-    approved_by INTEGER REFERENCES users(id),
-    approved_at TIMESTAMP WITH TIME ZONE,
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
-    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
-);
-
--- 4. Crear tabla businesses
-CREATE TABLE IF NOT EXISTS businesses (
-    id SERIAL PRIMARY KEY,
-    owner_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    business_type VARCHAR(50) NOT NULL,
-    name VARCHAR(255) NOT NULL,
-    description TEXT,
-    location VARCHAR(255) NOT NULL,
-    address TEXT NOT NULL,
-    phone VARCHAR(20),
-    email VARCHAR(255),
-    website TEXT,
-    status business_status DEFAULT 'pending',
-    business_data JSONB DEFAULT '{}',
-    operating_hours JSONB DEFAULT '{}',
-    approved_by INTEGER REFERENCES users(id),
-    approved_at TIMESTAMP WITH TIME ZONE,
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
-    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
-);
-
--- 5. Crear tabla business_images
-CREATE TABLE IF NOT EXISTS business_images (
-    id SERIAL PRIMARY KEY,
-    business_id INTEGER NOT NULL REFERENCES businesses(id) ON DELETE CASCADE,
-    image_url TEXT NOT NULL,
-    image_type VARCHAR(50) DEFAULT 'gallery',
-    display_order INTEGER DEFAULT 0,
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
-);
-
--- 6. Crear tabla bookings
-CREATE TABLE IF NOT EXISTS bookings (
-    id SERIAL PRIMARY KEY,
-    user_id INTEGER NOT NULL REFERENCES users(id) ON DELETE CASCADE,
-    hotel_id INTEGER REFERENCES hotels(id) ON DELETE CASCADE,
-    business_id INTEGER REFERENCES businesses(id) ON DELETE CASCADE,
-    booking_type VARCHAR(50) NOT NULL,
-    check_in_date DATE,
-    check_out_date DATE,
-    guests INTEGER NOT NULL DEFAULT 1 CHECK (guests > 0),
-    total_price DECIMAL(10,2) NOT NULL CHECK (total_price >= 0),
-    status booking_status DEFAULT 'pending',
-    booking_data JSONB DEFAULT '{}',
-    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
-    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
-    
-    CONSTRAINT booking_target_check CHECK (
-        (hotel_id IS NOT NULL AND business_id IS NULL) OR 
-        (hotel_id IS NULL AND business_id IS NOT NULL)
-    )
-);
-
--- 7. Crear índices
-CREATE INDEX IF NOT EXISTS idx_users_email ON users(email);
-CREATE INDEX IF NOT EXISTS idx_users_role ON users(role);
-CREATE INDEX IF NOT EXISTS idx_hotels_owner_id ON hotels(owner_id);
-CREATE INDEX IF NOT EXISTS idx_hotels_status ON hotels(status);
-CREATE INDEX IF NOT EXISTS idx_hotels_location ON hotels(location);
-CREATE INDEX IF NOT EXISTS idx_businesses_owner_id ON businesses(owner_id);
-CREATE INDEX IF NOT EXISTS idx_businesses_status ON businesses(status);
-CREATE INDEX IF NOT EXISTS idx_bookings_user_id ON bookings(user_id);
-CREATE INDEX IF NOT EXISTS idx_bookings_hotel_id ON bookings(hotel_id);
-
--- 8. Función para updated_at
-CREATE OR REPLACE FUNCTION update_updated_at_column()
-RETURNS TRIGGER AS $$
-BEGIN
-    NEW.updated_at = NOW();
-    RETURN NEW;
-END;
-$$ language 'plpgsql';
-
--- 9. Triggers
-DROP TRIGGER IF EXISTS update_users_updated_at ON users;
-CREATE TRIGGER update_users_updated_at 
-    BEFORE UPDATE ON users 
-    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
-
-DROP TRIGGER IF EXISTS update_hotels_updated_at ON hotels;
-CREATE TRIGGER update_hotels_updated_at 
-    BEFORE UPDATE ON hotels 
-    FOR EACH ROW EXECUTE FUNCTION update_updated_at_column();
-
--- 10. Usuario admin por defecto
-INSERT INTO users (email, password_hash, first_name, last_name, role)
-SELECT 
-    'admin@mayadigital.com',
-    '$2b$12$LQv3c1yqBWVHxkd0LHAkCOYz6TtxMQJqhN8/LewXbhd5ZK5fGbZQW',
-    'Admin',
-    'Maya Digital',
-    'admin'
-WHERE NOT EXISTS (
-    SELECT 1 FROM users WHERE email = 'admin@mayadigital.com'
-);
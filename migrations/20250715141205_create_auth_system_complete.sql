@@ -1 +0,0 @@
--- Add migration script here